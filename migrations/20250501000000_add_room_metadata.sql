@@ -0,0 +1,9 @@
+-- Room metadata used by the app map and for capacity planning
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS capacity INTEGER;
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS floor VARCHAR(50);
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS building VARCHAR(255);
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS accessibility_notes VARCHAR(2000);
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS map_x DOUBLE PRECISION;
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS map_y DOUBLE PRECISION;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_room ON conference_sessions(room_id);
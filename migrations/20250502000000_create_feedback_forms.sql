@@ -0,0 +1,43 @@
+-- Structured feedback forms with configurable questions
+CREATE TABLE IF NOT EXISTS feedback_forms (
+    id SERIAL PRIMARY KEY,
+    title VARCHAR(500) NOT NULL,
+    session_id VARCHAR(50) REFERENCES conference_sessions(id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES conference_categories(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT form_single_scope CHECK (session_id IS NULL OR category_id IS NULL)
+);
+
+CREATE TABLE IF NOT EXISTS feedback_questions (
+    id SERIAL PRIMARY KEY,
+    form_id INTEGER NOT NULL REFERENCES feedback_forms(id) ON DELETE CASCADE,
+    prompt VARCHAR(1000) NOT NULL,
+    question_type VARCHAR(20) NOT NULL,
+    scale_min INTEGER,
+    scale_max INTEGER,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    sort INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT question_type_valid CHECK (question_type IN ('rating', 'choice', 'text'))
+);
+
+CREATE TABLE IF NOT EXISTS feedback_answers (
+    uuid VARCHAR(50) NOT NULL,
+    session_id VARCHAR(50) NOT NULL,
+    question_id INTEGER NOT NULL REFERENCES feedback_questions(id) ON DELETE CASCADE,
+    rating INTEGER,
+    choice VARCHAR(500),
+    text_value VARCHAR(5000),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (uuid, session_id, question_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_feedback_forms_session ON feedback_forms(session_id);
+CREATE INDEX IF NOT EXISTS idx_feedback_forms_category ON feedback_forms(category_id);
+CREATE INDEX IF NOT EXISTS idx_feedback_questions_form ON feedback_questions(form_id);
+CREATE INDEX IF NOT EXISTS idx_feedback_answers_session ON feedback_answers(session_id);
+CREATE INDEX IF NOT EXISTS idx_feedback_answers_question ON feedback_answers(question_id);
@@ -0,0 +1,5 @@
+-- Per-session overrides for the voting and feedback windows
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS vote_opens_at TIMESTAMPTZ;
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS vote_closes_at TIMESTAMPTZ;
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS feedback_opens_at TIMESTAMPTZ;
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS feedback_closes_at TIMESTAMPTZ;
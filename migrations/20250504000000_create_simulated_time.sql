@@ -0,0 +1,18 @@
+-- Simulated conference time, shared by all backend instances and kept across restarts.
+-- The empty cohort is the global simulation, any other cohort only applies to its members.
+CREATE TABLE IF NOT EXISTS simulated_time (
+    cohort VARCHAR(100) PRIMARY KEY,
+    simulated_at TIMESTAMPTZ NOT NULL,
+    set_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS test_user_cohorts (
+    uuid VARCHAR(50) PRIMARY KEY REFERENCES users(uuid) ON DELETE CASCADE,
+    cohort VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_user_cohorts_cohort ON test_user_cohorts(cohort);
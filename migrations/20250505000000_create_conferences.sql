@@ -0,0 +1,50 @@
+-- Conferences (editions and regional events) served from one deployment.
+-- Everything that existed before belongs to the default conference.
+CREATE TABLE IF NOT EXISTS conferences (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    time_zone VARCHAR(100),
+    admin_secret VARCHAR(255),
+    settings JSONB NOT NULL DEFAULT '{}',
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_conferences_default ON conferences(is_default) WHERE is_default;
+
+INSERT INTO conferences (slug, name, is_default)
+VALUES ('default', 'Default conference', TRUE)
+ON CONFLICT (slug) DO NOTHING;
+
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE conference_categories ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE feedback_forms ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE simulated_time ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+
+UPDATE conference_sessions SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE conference_speakers SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE conference_rooms SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE conference_categories SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE feedback_forms SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE simulated_time SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+
+ALTER TABLE conference_sessions ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE conference_speakers ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE conference_rooms ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE conference_categories ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE feedback_forms ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE simulated_time ALTER COLUMN conference_id SET NOT NULL;
+
+-- Simulated time is kept per conference, the cohort stays the key within one
+ALTER TABLE simulated_time DROP CONSTRAINT IF EXISTS simulated_time_pkey;
+ALTER TABLE simulated_time ADD PRIMARY KEY (conference_id, cohort);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_conference ON conference_sessions(conference_id);
+CREATE INDEX IF NOT EXISTS idx_speakers_conference ON conference_speakers(conference_id);
+CREATE INDEX IF NOT EXISTS idx_rooms_conference ON conference_rooms(conference_id);
+CREATE INDEX IF NOT EXISTS idx_categories_conference ON conference_categories(conference_id);
+CREATE INDEX IF NOT EXISTS idx_feedback_forms_conference ON feedback_forms(conference_id);
\ No newline at end of file
@@ -0,0 +1,34 @@
+-- Push notifications: registered devices, per-user opt-out and an outbox the worker delivers from
+ALTER TABLE users ADD COLUMN IF NOT EXISTS notifications_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE TABLE IF NOT EXISTS device_tokens (
+    token VARCHAR(500) PRIMARY KEY,
+    uuid VARCHAR(50) NOT NULL REFERENCES users(uuid) ON DELETE CASCADE,
+    platform VARCHAR(20) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT device_platform_valid CHECK (platform IN ('fcm', 'apns'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_tokens_uuid ON device_tokens(uuid);
+
+-- One row per recipient. The dedup key makes enqueueing the same notice twice a no-op.
+CREATE TABLE IF NOT EXISTS notification_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id) ON DELETE CASCADE,
+    uuid VARCHAR(50) NOT NULL REFERENCES users(uuid) ON DELETE CASCADE,
+    kind VARCHAR(30) NOT NULL,
+    session_id VARCHAR(50),
+    title VARCHAR(500) NOT NULL,
+    body VARCHAR(2000) NOT NULL,
+    dedup_key VARCHAR(300) NOT NULL UNIQUE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_error VARCHAR(2000),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT outbox_status_valid CHECK (status IN ('pending', 'sent', 'failed', 'skipped'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_outbox_due ON notification_outbox(next_attempt_at) WHERE status = 'pending';
\ No newline at end of file
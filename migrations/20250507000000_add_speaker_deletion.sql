@@ -0,0 +1,5 @@
+-- Deleted and merged speakers are kept as tombstones so /sync/speakers can report them
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS merged_into VARCHAR(50) REFERENCES conference_speakers(id);
+
+CREATE INDEX IF NOT EXISTS idx_speakers_live ON conference_speakers(conference_id) WHERE deleted_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Richer speaker profiles. Every column is optional or defaulted so existing rows and
+-- clients that send the old request shape keep working.
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS company VARCHAR(255);
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS job_title VARCHAR(255);
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS pronouns VARCHAR(50);
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS country VARCHAR(100);
+-- [{"kind": "github", "url": "..."}]
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS links JSONB NOT NULL DEFAULT '[]';
+-- [{"variant": "square", "url": "...", "width": 400, "height": 400}]
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS photos JSONB NOT NULL DEFAULT '[]';
\ No newline at end of file
@@ -0,0 +1,28 @@
+-- Uploaded images. Ids are derived from the content hash, so uploading the same file twice
+-- yields the same asset. Each asset is stored in several sizes and formats.
+CREATE TABLE IF NOT EXISTS media_assets (
+    id VARCHAR(64) PRIMARY KEY,
+    content_type VARCHAR(50) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    byte_size BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS media_variants (
+    asset_id VARCHAR(64) NOT NULL REFERENCES media_assets(id) ON DELETE CASCADE,
+    variant VARCHAR(20) NOT NULL,
+    format VARCHAR(10) NOT NULL,
+    storage_key VARCHAR(500) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    byte_size BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (asset_id, variant, format),
+    CONSTRAINT media_variant_valid CHECK (variant IN ('original', 'square', 'thumbnail')),
+    CONSTRAINT media_format_valid CHECK (format IN ('jpeg', 'webp'))
+);
+
+ALTER TABLE conference_speakers ADD COLUMN IF NOT EXISTS photo_asset_id VARCHAR(64) REFERENCES media_assets(id);
+ALTER TABLE podcast_channels ADD COLUMN IF NOT EXISTS image_asset_id VARCHAR(64) REFERENCES media_assets(id);
\ No newline at end of file
@@ -0,0 +1,38 @@
+-- Localised content. The base columns hold the conference's default locale, a translation
+-- overrides the fields it sets and falls back to the base value for the others.
+CREATE TABLE IF NOT EXISTS session_translations (
+    session_id VARCHAR(50) NOT NULL REFERENCES conference_sessions(id) ON DELETE CASCADE,
+    locale VARCHAR(35) NOT NULL,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id),
+    title VARCHAR(500),
+    description TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (session_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS speaker_translations (
+    speaker_id VARCHAR(50) NOT NULL REFERENCES conference_speakers(id) ON DELETE CASCADE,
+    locale VARCHAR(35) NOT NULL,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id),
+    bio VARCHAR(5000),
+    tag_line VARCHAR(500),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (speaker_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS category_translations (
+    category_id INTEGER NOT NULL REFERENCES conference_categories(id) ON DELETE CASCADE,
+    locale VARCHAR(35) NOT NULL,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id),
+    title VARCHAR(255),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (category_id, locale)
+);
+
+-- Locale negotiation looks up the locales a conference has content in
+CREATE INDEX IF NOT EXISTS idx_session_translations_conference ON session_translations(conference_id, locale);
+CREATE INDEX IF NOT EXISTS idx_speaker_translations_conference ON speaker_translations(conference_id, locale);
+CREATE INDEX IF NOT EXISTS idx_category_translations_conference ON category_translations(conference_id, locale);
\ No newline at end of file
@@ -0,0 +1,29 @@
+-- Category groups such as "Track", "Level" or "Format". The categories of a type are its
+-- allowed values, single-choice types allow one of them per session.
+CREATE TABLE IF NOT EXISTS category_types (
+    id SERIAL PRIMARY KEY,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id),
+    name VARCHAR(100) NOT NULL,
+    sort INTEGER,
+    multiple BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (conference_id, name)
+);
+
+ALTER TABLE conference_categories ADD COLUMN IF NOT EXISTS type_id INTEGER REFERENCES category_types(id);
+-- Children belong to the same type as their parent
+ALTER TABLE conference_categories ADD COLUMN IF NOT EXISTS parent_id INTEGER REFERENCES conference_categories(id);
+
+CREATE INDEX IF NOT EXISTS idx_categories_type ON conference_categories(type_id);
+CREATE INDEX IF NOT EXISTS idx_categories_parent ON conference_categories(parent_id);
+
+-- Turn the free-form types used so far into category types
+INSERT INTO category_types (conference_id, name)
+SELECT DISTINCT conference_id, type FROM conference_categories
+WHERE type IS NOT NULL AND type <> ''
+ON CONFLICT (conference_id, name) DO NOTHING;
+
+UPDATE conference_categories cc SET type_id = ct.id
+FROM category_types ct
+WHERE ct.conference_id = cc.conference_id AND ct.name = cc.type AND cc.type_id IS NULL;
\ No newline at end of file
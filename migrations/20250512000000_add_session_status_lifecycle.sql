@@ -0,0 +1,17 @@
+-- Sessions move through draft -> scheduled -> published and may end up cancelled or archived.
+-- Attendees only see published and cancelled sessions.
+UPDATE conference_sessions SET status = 'draft'
+WHERE status IS NULL OR status NOT IN ('draft', 'scheduled', 'published', 'cancelled', 'archived');
+
+ALTER TABLE conference_sessions ALTER COLUMN status SET NOT NULL;
+ALTER TABLE conference_sessions DROP CONSTRAINT IF EXISTS session_status_valid;
+ALTER TABLE conference_sessions ADD CONSTRAINT session_status_valid
+    CHECK (status IN ('draft', 'scheduled', 'published', 'cancelled', 'archived'));
+
+-- Set once a session has been visible to attendees, /sync/sessions keeps delivering it so
+-- clients learn when it is archived
+ALTER TABLE conference_sessions ADD COLUMN IF NOT EXISTS published_at TIMESTAMPTZ;
+UPDATE conference_sessions SET published_at = COALESCE(updated_at, NOW())
+WHERE status IN ('published', 'cancelled', 'archived') AND published_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_status ON conference_sessions(conference_id, status);
\ No newline at end of file
@@ -0,0 +1,32 @@
+-- History of every content mutation. `before` and `after` are full snapshots of the entity,
+-- NULL where it did not exist. Versions count the changes of one entity.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    -- NULL for podcasts, which are shared by all conferences
+    conference_id INTEGER REFERENCES conferences(id),
+    entity VARCHAR(30) NOT NULL,
+    entity_id VARCHAR(64) NOT NULL,
+    version INTEGER NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    actor VARCHAR(255) NOT NULL,
+    request_id VARCHAR(100),
+    before JSONB,
+    after JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (entity, entity_id, version),
+    CONSTRAINT audit_action_valid CHECK (action IN ('created', 'updated', 'deleted', 'restored'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_conference ON audit_log(conference_id, id);
+
+-- Entries are never changed or removed
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION audit_log_append_only();
\ No newline at end of file
@@ -0,0 +1,28 @@
+-- Batches of schedule edits that are prepared as drafts and go live together, either on demand
+-- or once `apply_at` has passed
+CREATE TABLE IF NOT EXISTS changesets (
+    id SERIAL PRIMARY KEY,
+    conference_id INTEGER NOT NULL REFERENCES conferences(id),
+    title VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft',
+    apply_at TIMESTAMPTZ,
+    applied_at TIMESTAMPTZ,
+    -- Why the last scheduled apply failed
+    error TEXT,
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT changeset_status_valid CHECK (status IN ('draft', 'scheduled', 'applied', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_changesets_due ON changesets(apply_at) WHERE status = 'scheduled';
+
+-- Edits run in id order when the changeset is applied
+CREATE TABLE IF NOT EXISTS changeset_operations (
+    id SERIAL PRIMARY KEY,
+    changeset_id INTEGER NOT NULL REFERENCES changesets(id) ON DELETE CASCADE,
+    operation JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_changeset_operations_changeset ON changeset_operations(changeset_id, id);
\ No newline at end of file
@@ -0,0 +1,11 @@
+-- Erasing a user rewrites the actor of their audit entries, every other column stays as written
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND to_jsonb(NEW) - 'actor' = to_jsonb(OLD) - 'actor' THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log(actor);
\ No newline at end of file
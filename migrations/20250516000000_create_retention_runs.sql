@@ -0,0 +1,13 @@
+-- One row per run of the retention job, dry runs included
+CREATE TABLE IF NOT EXISTS retention_runs (
+    id SERIAL PRIMARY KEY,
+    started_by VARCHAR(255) NOT NULL,
+    dry_run BOOLEAN NOT NULL,
+    -- What the run changed, or would have changed; NULL if it failed
+    report JSONB,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_podcast_request_created ON podcast_request_table(created_at);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Deleted rooms are kept as tombstones so /sync/rooms can report them
+ALTER TABLE conference_rooms ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_rooms_live ON conference_rooms(conference_id) WHERE deleted_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Cohorts belong to one conference, a test user can be in a cohort of every conference
+ALTER TABLE test_user_cohorts ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id) ON DELETE CASCADE;
+
+UPDATE test_user_cohorts SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+
+ALTER TABLE test_user_cohorts ALTER COLUMN conference_id SET NOT NULL;
+
+ALTER TABLE test_user_cohorts DROP CONSTRAINT IF EXISTS test_user_cohorts_pkey;
+ALTER TABLE test_user_cohorts ADD CONSTRAINT test_user_cohorts_pkey PRIMARY KEY (conference_id, uuid);
+
+DROP INDEX IF EXISTS idx_test_user_cohorts_cohort;
+CREATE INDEX IF NOT EXISTS idx_test_user_cohorts_conference_cohort ON test_user_cohorts(conference_id, cohort);
+CREATE INDEX IF NOT EXISTS idx_test_user_cohorts_uuid ON test_user_cohorts(uuid);
\ No newline at end of file
@@ -0,0 +1,17 @@
+-- Conference admin secrets are kept as salted SHA-256 digests, "<salt>$<hex digest of salt and secret>"
+ALTER TABLE conferences ADD COLUMN IF NOT EXISTS admin_secret_hash VARCHAR(255);
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'conferences' AND column_name = 'admin_secret'
+    ) THEN
+        UPDATE conferences
+        SET admin_secret_hash = salted.salt || '$' || encode(sha256(convert_to(salted.salt || conferences.admin_secret, 'UTF8')), 'hex')
+        FROM (SELECT id, replace(gen_random_uuid()::text, '-', '') AS salt FROM conferences) salted
+        WHERE conferences.id = salted.id AND conferences.admin_secret IS NOT NULL;
+
+        ALTER TABLE conferences DROP COLUMN admin_secret;
+    END IF;
+END $$;
\ No newline at end of file
@@ -0,0 +1,93 @@
+-- Session and speaker ids come from the programme sources of each conference and only need to
+-- be unique within it, so two editions can import the same ids. Rows referring to a session or
+-- speaker name its conference as part of the reference.
+ALTER TABLE votes ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE feedback ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE feedback_answers ADD COLUMN IF NOT EXISTS conference_id INTEGER REFERENCES conferences(id);
+ALTER TABLE session_speakers ADD COLUMN IF NOT EXISTS conference_id INTEGER;
+ALTER TABLE session_categories ADD COLUMN IF NOT EXISTS conference_id INTEGER;
+
+UPDATE votes v SET conference_id = cs.conference_id
+FROM conference_sessions cs WHERE cs.id = v.sessionid AND v.conference_id IS NULL;
+UPDATE feedback f SET conference_id = cs.conference_id
+FROM conference_sessions cs WHERE cs.id = f.sessionid AND f.conference_id IS NULL;
+UPDATE feedback_answers fa SET conference_id = cs.conference_id
+FROM conference_sessions cs WHERE cs.id = fa.session_id AND fa.conference_id IS NULL;
+UPDATE session_speakers ss SET conference_id = cs.conference_id
+FROM conference_sessions cs WHERE cs.id = ss.session_id AND ss.conference_id IS NULL;
+UPDATE session_categories sc SET conference_id = cs.conference_id
+FROM conference_sessions cs WHERE cs.id = sc.session_id AND sc.conference_id IS NULL;
+
+-- Votes and feedback were never tied to existing sessions
+UPDATE votes SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE feedback SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+UPDATE feedback_answers SET conference_id = (SELECT id FROM conferences WHERE is_default) WHERE conference_id IS NULL;
+
+ALTER TABLE votes ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE feedback ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE feedback_answers ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE session_speakers ALTER COLUMN conference_id SET NOT NULL;
+ALTER TABLE session_categories ALTER COLUMN conference_id SET NOT NULL;
+
+ALTER TABLE session_speakers DROP CONSTRAINT IF EXISTS session_speakers_session_id_fkey;
+ALTER TABLE session_speakers DROP CONSTRAINT IF EXISTS session_speakers_speaker_id_fkey;
+ALTER TABLE session_categories DROP CONSTRAINT IF EXISTS session_categories_session_id_fkey;
+ALTER TABLE feedback_forms DROP CONSTRAINT IF EXISTS feedback_forms_session_id_fkey;
+ALTER TABLE session_translations DROP CONSTRAINT IF EXISTS session_translations_session_id_fkey;
+ALTER TABLE speaker_translations DROP CONSTRAINT IF EXISTS speaker_translations_speaker_id_fkey;
+ALTER TABLE conference_speakers DROP CONSTRAINT IF EXISTS conference_speakers_merged_into_fkey;
+
+ALTER TABLE conference_sessions DROP CONSTRAINT IF EXISTS conference_sessions_pkey;
+ALTER TABLE conference_sessions ADD CONSTRAINT conference_sessions_pkey PRIMARY KEY (conference_id, id);
+ALTER TABLE conference_speakers DROP CONSTRAINT IF EXISTS conference_speakers_pkey;
+ALTER TABLE conference_speakers ADD CONSTRAINT conference_speakers_pkey PRIMARY KEY (conference_id, id);
+
+ALTER TABLE votes DROP CONSTRAINT IF EXISTS votes_pkey;
+ALTER TABLE votes ADD CONSTRAINT votes_pkey PRIMARY KEY (uuid, conference_id, sessionid);
+ALTER TABLE feedback DROP CONSTRAINT IF EXISTS feedback_pkey;
+ALTER TABLE feedback ADD CONSTRAINT feedback_pkey PRIMARY KEY (uuid, conference_id, sessionid);
+ALTER TABLE feedback_answers DROP CONSTRAINT IF EXISTS feedback_answers_pkey;
+ALTER TABLE feedback_answers ADD CONSTRAINT feedback_answers_pkey PRIMARY KEY (uuid, conference_id, session_id, question_id);
+ALTER TABLE session_speakers DROP CONSTRAINT IF EXISTS session_speakers_pkey;
+ALTER TABLE session_speakers ADD CONSTRAINT session_speakers_pkey PRIMARY KEY (conference_id, session_id, speaker_id);
+ALTER TABLE session_categories DROP CONSTRAINT IF EXISTS session_categories_pkey;
+ALTER TABLE session_categories ADD CONSTRAINT session_categories_pkey PRIMARY KEY (conference_id, session_id, category_item_id);
+ALTER TABLE session_translations DROP CONSTRAINT IF EXISTS session_translations_pkey;
+ALTER TABLE session_translations ADD CONSTRAINT session_translations_pkey PRIMARY KEY (conference_id, session_id, locale);
+ALTER TABLE speaker_translations DROP CONSTRAINT IF EXISTS speaker_translations_pkey;
+ALTER TABLE speaker_translations ADD CONSTRAINT speaker_translations_pkey PRIMARY KEY (conference_id, speaker_id, locale);
+
+ALTER TABLE session_speakers ADD CONSTRAINT session_speakers_session_id_fkey
+    FOREIGN KEY (conference_id, session_id) REFERENCES conference_sessions(conference_id, id);
+ALTER TABLE session_speakers ADD CONSTRAINT session_speakers_speaker_id_fkey
+    FOREIGN KEY (conference_id, speaker_id) REFERENCES conference_speakers(conference_id, id);
+ALTER TABLE session_categories ADD CONSTRAINT session_categories_session_id_fkey
+    FOREIGN KEY (conference_id, session_id) REFERENCES conference_sessions(conference_id, id);
+ALTER TABLE feedback_forms ADD CONSTRAINT feedback_forms_session_id_fkey
+    FOREIGN KEY (conference_id, session_id) REFERENCES conference_sessions(conference_id, id) ON DELETE CASCADE;
+ALTER TABLE session_translations ADD CONSTRAINT session_translations_session_id_fkey
+    FOREIGN KEY (conference_id, session_id) REFERENCES conference_sessions(conference_id, id) ON DELETE CASCADE;
+ALTER TABLE speaker_translations ADD CONSTRAINT speaker_translations_speaker_id_fkey
+    FOREIGN KEY (conference_id, speaker_id) REFERENCES conference_speakers(conference_id, id) ON DELETE CASCADE;
+ALTER TABLE conference_speakers ADD CONSTRAINT conference_speakers_merged_into_fkey
+    FOREIGN KEY (conference_id, merged_into) REFERENCES conference_speakers(conference_id, id);
+
+DROP INDEX IF EXISTS idx_votes_sessionid;
+CREATE INDEX IF NOT EXISTS idx_votes_session ON votes(conference_id, sessionid);
+DROP INDEX IF EXISTS idx_feedback_sessionid;
+CREATE INDEX IF NOT EXISTS idx_feedback_session ON feedback(conference_id, sessionid);
+DROP INDEX IF EXISTS idx_feedback_answers_session;
+CREATE INDEX IF NOT EXISTS idx_feedback_answers_conference_session ON feedback_answers(conference_id, session_id);
+DROP INDEX IF EXISTS idx_session_speakers_speaker;
+CREATE INDEX IF NOT EXISTS idx_session_speakers_conference_speaker ON session_speakers(conference_id, speaker_id);
+DROP INDEX IF EXISTS idx_session_speakers_session;
+DROP INDEX IF EXISTS idx_session_categories_session;
+DROP INDEX IF EXISTS idx_feedback_forms_session;
+CREATE INDEX IF NOT EXISTS idx_feedback_forms_conference_session ON feedback_forms(conference_id, session_id);
+
+-- Outbox keys and audit versions name sessions and speakers too
+ALTER TABLE notification_outbox DROP CONSTRAINT IF EXISTS notification_outbox_dedup_key_key;
+ALTER TABLE notification_outbox ADD CONSTRAINT notification_outbox_dedup_key_key UNIQUE (conference_id, dedup_key);
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS audit_log_entity_entity_id_version_key;
+ALTER TABLE audit_log ADD CONSTRAINT audit_log_entity_entity_id_version_key
+    UNIQUE NULLS NOT DISTINCT (conference_id, entity, entity_id, version);
\ No newline at end of file
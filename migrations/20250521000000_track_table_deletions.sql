@@ -0,0 +1,39 @@
+-- Deleting rows does not move the newest updated_at of a table, so read endpoints could not send
+-- a Last-Modified that changes on deletes. Deletes record when they last happened per table and
+-- conference instead, podcast tables are not scoped and use conference 0.
+CREATE TABLE IF NOT EXISTS table_deletions (
+    table_name VARCHAR(64) NOT NULL,
+    conference_id INTEGER NOT NULL,
+    deleted_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (table_name, conference_id)
+);
+
+CREATE OR REPLACE FUNCTION record_table_deletion() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO table_deletions (table_name, conference_id, deleted_at)
+    SELECT DISTINCT TG_TABLE_NAME, COALESCE((to_jsonb(o) ->> 'conference_id')::INTEGER, 0), CURRENT_TIMESTAMP
+    FROM old_rows o
+    ON CONFLICT (table_name, conference_id) DO UPDATE SET deleted_at = EXCLUDED.deleted_at;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    versioned TEXT;
+BEGIN
+    FOREACH versioned IN ARRAY ARRAY[
+        'conference_sessions', 'conference_speakers', 'conference_rooms', 'conference_categories',
+        'category_types', 'session_speakers', 'session_categories',
+        'podcast_channels', 'podcast_episodes', 'podcast_channel_categories',
+        'podcast_episode_categories', 'channel_category_map', 'episode_category_map'
+    ] LOOP
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', versioned || '_deletions', versioned);
+        EXECUTE format(
+            'CREATE TRIGGER %I AFTER DELETE ON %I REFERENCING OLD TABLE AS old_rows '
+            'FOR EACH STATEMENT EXECUTE FUNCTION record_table_deletion()',
+            versioned || '_deletions', versioned
+        );
+    END LOOP;
+END;
+$$;
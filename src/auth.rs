@@ -1,12 +1,20 @@
 // src/auth.rs
 use actix_web::{
-    dev::Payload, error::ErrorUnauthorized, http::header, Error, FromRequest, HttpRequest,
+    dev::Payload, error::ErrorUnauthorized, http::header, web, Error, FromRequest, HttpMessage,
+    HttpRequest,
 };
 use futures::future::{ready, Ready};
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
+use uuid::Uuid;
 
+use crate::config::AppConfig;
 use crate::db::users;
 use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::request_id::RequestId;
+
+const ACTOR_HEADER: &str = "x-actor";
 
 pub struct KotlinConfPrincipal {
     pub token: String,
@@ -38,6 +46,57 @@ impl FromRequest for KotlinConfPrincipal {
     }
 }
 
+// Who made a request, as recorded in the audit log. It only labels the request, handlers still
+// validate the principal. Organisers sharing an admin secret tell themselves apart with an
+// X-Actor header.
+pub struct Actor {
+    pub name: String,
+    pub request_id: Option<String>,
+}
+
+impl FromRequest for Actor {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+    
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let token = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim);
+        let admin_secret = req
+            .app_data::<web::Data<AppConfig>>()
+            .map(|config| config.service.secret.as_str());
+        let extensions = req.extensions();
+        let conference = extensions.get::<ConferenceEdition>();
+        
+        let role = match token {
+            None => "anonymous".to_string(),
+            Some(token) if admin_secret.is_some_and(|secret| matches_secret(token, secret)) => "admin".to_string(),
+            Some(token) if conference.is_some_and(|conference| is_conference_secret(token, conference)) => {
+                "organizer".to_string()
+            }
+            Some(token) => user_actor(token, admin_secret.unwrap_or_default()),
+        };
+        let label = req
+            .headers()
+            .get(ACTOR_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|label| !label.is_empty() && !role.starts_with("user:"));
+        let name = match label {
+            Some(label) => format!("{} ({})", role, label.chars().take(100).collect::<String>()),
+            None => role,
+        };
+        
+        ready(Ok(Actor {
+            name,
+            request_id: extensions.get::<RequestId>().map(|id| id.0.clone()),
+        }))
+    }
+}
+
 pub async fn validate_user(
     principal: &KotlinConfPrincipal, 
     pool: &PgPool
@@ -49,8 +108,112 @@ pub async fn validate_admin_secret(
     principal: &KotlinConfPrincipal,
     admin_secret: &str,
 ) -> Result<(), ServiceError> {
-    if principal.token != admin_secret {
+    if !matches_secret(&principal.token, admin_secret) {
         return Err(ServiceError::SecretInvalid);  // Use SecretInvalid instead of Unauthorized
     }
     Ok(())
+}
+
+// The service secret manages every conference, a conference's own secret only that conference
+pub async fn validate_conference_admin(
+    principal: &KotlinConfPrincipal,
+    admin_secret: &str,
+    conference: &ConferenceEdition,
+) -> Result<(), ServiceError> {
+    if is_conference_secret(&principal.token, conference) {
+        return Ok(());
+    }
+    validate_admin_secret(principal, admin_secret).await
+}
+
+// Audit entries name users by a digest of their token keyed with the service secret. The token
+// is the user's credential, organisers read the log.
+pub fn user_actor(token: &str, admin_secret: &str) -> String {
+    format!("user:{}", salted_digest(admin_secret, token))
+}
+
+// Conference secrets are stored as "<salt>$<hex SHA-256 of salt and secret>"
+pub fn hash_secret(secret: &str) -> String {
+    let salt = Uuid::new_v4().simple().to_string();
+    format!("{}${}", salt, salted_digest(&salt, secret))
+}
+
+fn is_conference_secret(token: &str, conference: &ConferenceEdition) -> bool {
+    let Some((salt, digest)) = conference.admin_secret_hash.as_deref().and_then(|hash| hash.split_once('$')) else {
+        return false;
+    };
+    constant_time_eq(salted_digest(salt, token).as_bytes(), digest.as_bytes())
+}
+
+// Compares digests so the time taken does not depend on the secret's length or content
+fn matches_secret(token: &str, secret: &str) -> bool {
+    constant_time_eq(&Sha256::digest(token), &Sha256::digest(secret))
+}
+
+fn salted_digest(salt: &str, secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(secret.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conference_with(admin_secret_hash: Option<String>) -> ConferenceEdition {
+        ConferenceEdition {
+            id: 1,
+            slug: "default".to_string(),
+            name: "Default conference".to_string(),
+            time_zone: None,
+            admin_secret_hash,
+            settings: Default::default(),
+            is_default: true,
+        }
+    }
+
+    #[test]
+    fn conference_secret_matches_its_hash() {
+        let conference = conference_with(Some(hash_secret("tokyo-secret")));
+
+        assert!(is_conference_secret("tokyo-secret", &conference));
+        assert!(!is_conference_secret("tokyo-secre", &conference));
+        assert!(!is_conference_secret("", &conference));
+    }
+
+    #[test]
+    fn users_are_labelled_without_their_token() {
+        let label = user_actor("3f1c2a9e-user-token", "admin");
+
+        assert_eq!(label, user_actor("3f1c2a9e-user-token", "admin"));
+        assert_ne!(label, user_actor("3f1c2a9e-user-token", "other"));
+        assert!(!label.contains("3f1c2a9e-user-token"));
+    }
+
+    #[test]
+    fn hashes_are_salted() {
+        let first = hash_secret("tokyo-secret");
+        let second = hash_secret("tokyo-secret");
+
+        assert_ne!(first, second);
+        assert!(!first.contains("tokyo-secret"));
+    }
+
+    #[test]
+    fn conference_without_a_secret_has_no_organizers() {
+        assert!(!is_conference_secret("", &conference_with(None)));
+        assert!(!is_conference_secret("anything", &conference_with(Some("malformed".to_string()))));
+    }
+
+    #[test]
+    fn service_secret_must_match_exactly() {
+        assert!(matches_secret("admin", "admin"));
+        assert!(!matches_secret("admin ", "admin"));
+        assert!(!matches_secret("", "admin"));
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use config::{Config, ConfigError, Environment, File};
 
 #[derive(Debug, Deserialize, Clone)]
@@ -19,11 +19,138 @@ pub struct ServiceConfig {
     pub secret: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConferenceConfig {
+    pub time_zone: String,
+    // Locale of the content stored on sessions, speakers and categories
+    pub default_locale: String,
+}
+
+// Point in time a voting or feedback window boundary is measured from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowAnchor {
+    // No boundary: the window is open from the beginning or never closes
+    None,
+    Start,
+    End,
+    ConferenceEnd,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowConfig {
+    pub open_anchor: WindowAnchor,
+    pub open_offset_minutes: i64,
+    pub close_anchor: WindowAnchor,
+    pub close_offset_minutes: i64,
+}
+
+// Credentials are only needed for the platforms that should be delivered to
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationsConfig {
+    pub enabled: bool,
+    // "mock" logs deliveries instead of sending them, "live" uses FCM and APNs
+    pub provider: String,
+    // Lets the mock provider fail tokens starting with "invalid" or "fail", for local testing only
+    pub mock_failures: bool,
+    pub poll_seconds: u64,
+    pub reminder_minutes: i64,
+    pub max_attempts: i32,
+    pub fcm_project_id: Option<String>,
+    pub fcm_client_email: Option<String>,
+    pub fcm_private_key: Option<String>,
+    pub apns_team_id: Option<String>,
+    pub apns_key_id: Option<String>,
+    pub apns_private_key: Option<String>,
+    pub apns_topic: Option<String>,
+    pub apns_sandbox: bool,
+}
+
+// S3 settings are only needed with the "s3" backend, the endpoint defaults to AWS
+#[derive(Debug, Deserialize, Clone)]
+pub struct MediaConfig {
+    // "local" stores files under `local_path`, "s3" in an S3-compatible bucket
+    pub backend: String,
+    pub local_path: String,
+    // Prefixed to asset URLs handed to clients, empty for URLs relative to this service
+    pub public_base_url: String,
+    pub max_upload_bytes: usize,
+    pub cache_control: String,
+    pub s3_bucket: Option<String>,
+    pub s3_region: Option<String>,
+    pub s3_endpoint: Option<String>,
+    pub s3_access_key: Option<String>,
+    pub s3_secret_key: Option<String>,
+    // Address objects as endpoint/bucket/key, needed by most self-hosted S3 implementations
+    pub s3_path_style: bool,
+}
+
+// Cache-Control values sent with the cacheable read endpoints. Responses carry
+// validators either way, so clients can always revalidate cheaply.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CacheConfig {
+    pub conference: String,
+    pub sessions: String,
+    pub speakers: String,
+    pub rooms: String,
+    pub categories: String,
+    pub session_speakers: String,
+    pub session_categories: String,
+    pub podcasts: String,
+}
+
+// What happens to the votes and feedback of a user who deletes their account. Aggregates are
+// computed from the rows, anonymised rows keep counting towards them.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionPolicy {
+    Delete,
+    // Keep the rows under a random pseudonym that is not linked to the user
+    Anonymise,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PrivacyConfig {
+    pub deletion_policy: DeletionPolicy,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    // Cron expression with seconds for the retention job
+    pub schedule: String,
+    // Runs only report what they would change unless asked otherwise. On by default, so
+    // nothing is removed before organisers have checked a report.
+    pub dry_run: bool,
+    // Months after the last session of a conference before its votes and feedback are
+    // pseudonymised
+    pub anonymise_after_months: u32,
+    // Days after registering before a user without votes, feedback or devices is removed.
+    // Pseudonymised votes no longer count as the user's.
+    pub stale_user_days: i64,
+    pub podcast_request_days: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ChangesetsConfig {
+    // Cron expression with seconds for the job that applies scheduled changesets
+    pub schedule: String,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
     pub service: ServiceConfig,
+    pub conference: ConferenceConfig,
+    pub voting: WindowConfig,
+    pub feedback: WindowConfig,
+    pub notifications: NotificationsConfig,
+    pub cache: CacheConfig,
+    pub media: MediaConfig,
+    pub changesets: ChangesetsConfig,
+    pub privacy: PrivacyConfig,
+    pub retention: RetentionConfig,
 }
 
 impl AppConfig {
@@ -49,6 +176,50 @@ impl AppConfig {
             .set_default("database.max_connections", 5)?
             .set_default("service.environment", "production")?
             .set_default("service.secret", "admin")?
+            .set_default("conference.time_zone", "Europe/Amsterdam")?
+            .set_default("conference.default_locale", "en")?
+            // Voting opens when the session starts and never closes, feedback is always open
+            .set_default("voting.open_anchor", "start")?
+            .set_default("voting.open_offset_minutes", 0)?
+            .set_default("voting.close_anchor", "none")?
+            .set_default("voting.close_offset_minutes", 0)?
+            .set_default("feedback.open_anchor", "none")?
+            .set_default("feedback.open_offset_minutes", 0)?
+            .set_default("feedback.close_anchor", "none")?
+            .set_default("feedback.close_offset_minutes", 0)?
+            // Off until a deployment picks a provider, the mock one marks notices as sent for good
+            .set_default("notifications.enabled", false)?
+            .set_default("notifications.provider", "mock")?
+            .set_default("notifications.mock_failures", false)?
+            .set_default("notifications.poll_seconds", 30)?
+            .set_default("notifications.reminder_minutes", 15)?
+            .set_default("notifications.max_attempts", 5)?
+            .set_default("notifications.apns_sandbox", false)?
+            // The /get/* endpoints are per user, the public ones may be kept by shared caches
+            .set_default("cache.conference", "public, max-age=60")?
+            .set_default("cache.sessions", "private, no-cache")?
+            .set_default("cache.speakers", "private, no-cache")?
+            .set_default("cache.rooms", "private, no-cache")?
+            .set_default("cache.categories", "private, no-cache")?
+            .set_default("cache.session_speakers", "private, no-cache")?
+            .set_default("cache.session_categories", "private, no-cache")?
+            .set_default("cache.podcasts", "public, max-age=300")?
+            .set_default("media.backend", "local")?
+            .set_default("media.local_path", "media")?
+            .set_default("media.public_base_url", "")?
+            .set_default("media.max_upload_bytes", 10 * 1024 * 1024)?
+            // Asset URLs never change content, a new upload gets a new id
+            .set_default("media.cache_control", "public, max-age=31536000, immutable")?
+            .set_default("media.s3_path_style", false)?
+            .set_default("changesets.schedule", "0 * * * * *")?
+            .set_default("privacy.deletion_policy", "anonymise")?
+            // Daily at 03:00 UTC
+            .set_default("retention.enabled", true)?
+            .set_default("retention.schedule", "0 0 3 * * *")?
+            .set_default("retention.dry_run", true)?
+            .set_default("retention.anonymise_after_months", 6)?
+            .set_default("retention.stale_user_days", 365)?
+            .set_default("retention.podcast_request_days", 180)?
             // Try to load from a file if it exists
             .add_source(File::with_name("config").required(false))
             // Add environment variables (with prefix)
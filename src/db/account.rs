@@ -0,0 +1,215 @@
+// src/db/account.rs
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::DeletionPolicy;
+use crate::db::audit;
+use crate::error::ServiceError;
+use crate::models::account::{
+    AccountExport, ExportedAnswer, ExportedCohort, ExportedDevice, ExportedFeedback, ExportedNotification,
+    ExportedPodcastRequest, ExportedUser, ExportedVote,
+};
+
+// Stands in for a user id in rows that are kept after the user can no longer be identified
+pub(crate) const PSEUDONYM_PREFIX: &str = "anon-";
+
+pub(crate) fn new_pseudonym() -> String {
+    format!("{}{}", PSEUDONYM_PREFIX, Uuid::new_v4())
+}
+
+// What audit entries of an erased user are relabelled to, a pseudonym is no credential
+fn erased_actor(replacement: &str) -> String {
+    format!("user:{}", replacement)
+}
+
+// None if there is no such user. `actor` is the user's label in the audit log.
+pub async fn get_export(
+    pool: &PgPool,
+    user_id: &str,
+    actor: &str,
+) -> Result<Option<AccountExport>, ServiceError> {
+    let user = sqlx::query_as!(
+        ExportedUser,
+        r#"
+        SELECT uuid, timestamp, notifications_enabled, created_at
+        FROM users
+        WHERE uuid = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(user) = user else {
+        return Ok(None);
+    };
+
+    let cohorts = sqlx::query_as!(
+        ExportedCohort,
+        r#"
+        SELECT c.slug as conference, tc.cohort
+        FROM test_user_cohorts tc
+        JOIN conferences c ON c.id = tc.conference_id
+        WHERE tc.uuid = $1
+        ORDER BY c.id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let devices = sqlx::query_as!(
+        ExportedDevice,
+        r#"
+        SELECT token, platform, created_at
+        FROM device_tokens
+        WHERE uuid = $1
+        ORDER BY created_at
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let votes = sqlx::query_as!(
+        ExportedVote,
+        r#"
+        SELECT c.slug as conference, v.sessionId as "session_id!", v.rating, v.timestamp
+        FROM votes v
+        JOIN conferences c ON c.id = v.conference_id
+        WHERE v.uuid = $1
+        ORDER BY v.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let feedback = sqlx::query_as!(
+        ExportedFeedback,
+        r#"
+        SELECT c.slug as conference, f.sessionId as "session_id!", f.feedback, f.timestamp
+        FROM feedback f
+        JOIN conferences c ON c.id = f.conference_id
+        WHERE f.uuid = $1
+        ORDER BY f.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let feedback_answers = sqlx::query_as!(
+        ExportedAnswer,
+        r#"
+        SELECT c.slug as conference, fa.session_id, fa.question_id, fa.rating, fa.choice,
+            fa.text_value, fa.created_at
+        FROM feedback_answers fa
+        JOIN conferences c ON c.id = fa.conference_id
+        WHERE fa.uuid = $1
+        ORDER BY fa.created_at, fa.question_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let podcast_requests = sqlx::query_as!(
+        ExportedPodcastRequest,
+        r#"
+        SELECT title, author, rssUrl as "rss_url!", created_at
+        FROM podcast_request_table
+        WHERE uuid = $1
+        ORDER BY id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let notifications = sqlx::query_as!(
+        ExportedNotification,
+        r#"
+        SELECT c.slug as conference, n.kind, n.session_id, n.title, n.body, n.status, n.created_at
+        FROM notification_outbox n
+        JOIN conferences c ON c.id = n.conference_id
+        WHERE n.uuid = $1
+        ORDER BY n.id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let changes = audit::get_by_actor(pool, actor).await?;
+
+    Ok(Some(AccountExport {
+        user,
+        devices,
+        votes,
+        feedback,
+        feedback_answers,
+        podcast_requests,
+        notifications,
+        cohorts,
+        changes,
+    }))
+}
+
+// Removes the user with their devices, cohort and queued notifications. Votes, feedback and
+// podcast requests are deleted or moved to a fresh pseudonym depending on the policy, the
+// user's audit entries are relabelled either way. Returns false if there is no such user.
+pub async fn delete_account(
+    pool: &PgPool,
+    user_id: &str,
+    actor: &str,
+    policy: DeletionPolicy,
+) -> Result<bool, ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    // Rows that reference the user are removed by the cascade
+    let deleted = sqlx::query!("DELETE FROM users WHERE uuid = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    let replacement = match policy {
+        DeletionPolicy::Delete => {
+            sqlx::query!("DELETE FROM votes WHERE uuid = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("DELETE FROM feedback WHERE uuid = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("DELETE FROM feedback_answers WHERE uuid = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("DELETE FROM podcast_request_table WHERE uuid = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+            "deleted".to_string()
+        }
+        DeletionPolicy::Anonymise => {
+            let pseudonym = new_pseudonym();
+            sqlx::query!("UPDATE votes SET uuid = $2 WHERE uuid = $1", user_id, pseudonym)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("UPDATE feedback SET uuid = $2 WHERE uuid = $1", user_id, pseudonym)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("UPDATE feedback_answers SET uuid = $2 WHERE uuid = $1", user_id, pseudonym)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query!("UPDATE podcast_request_table SET uuid = $2 WHERE uuid = $1", user_id, pseudonym)
+                .execute(&mut *tx)
+                .await?;
+            pseudonym
+        }
+    };
+    audit::rename_actor(&mut tx, actor, &erased_actor(&replacement)).await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
\ No newline at end of file
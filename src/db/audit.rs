@@ -0,0 +1,787 @@
+// src/db/audit.rs
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+
+use crate::db::categories::{check_category, check_single_choice};
+use crate::error::ServiceError;
+use crate::models::audit::{AuditAction, AuditEntity, AuditEntry};
+use crate::models::session::SessionStatus;
+
+struct AuditRow {
+    id: i64,
+    entity: String,
+    entity_id: String,
+    version: i32,
+    action: String,
+    actor: String,
+    request_id: Option<String>,
+    before: Option<Value>,
+    after: Option<Value>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditRow {
+    fn into_entry(self) -> Option<AuditEntry> {
+        Some(AuditEntry {
+            id: self.id,
+            entity: AuditEntity::from_name(&self.entity)?,
+            entity_id: self.entity_id,
+            version: self.version,
+            action: AuditAction::from_name(&self.action)?,
+            actor: self.actor,
+            request_id: self.request_id,
+            before: self.before,
+            after: self.after,
+            created_at: self.created_at,
+        })
+    }
+}
+
+// Current state of an entity as stored, None if it does not exist. Timestamps are left out
+// so that writes which change nothing compare equal.
+pub async fn snapshot(
+    conn: &mut PgConnection,
+    conference_id: Option<i32>,
+    entity: AuditEntity,
+    entity_id: &str,
+) -> Result<Option<Value>, ServiceError> {
+    let numeric_id = entity_id.parse::<i32>().ok();
+    let state = match entity {
+        AuditEntity::Session => snapshot_sessions(conn, conference_id, &[entity_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .map(|(_, state)| state),
+        AuditEntity::Speaker => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(cs) - 'created_at' - 'updated_at' || jsonb_build_object(
+                'translations', COALESCE((
+                    SELECT jsonb_agg(
+                        to_jsonb(st) - 'conference_id' - 'speaker_id' - 'created_at' - 'updated_at'
+                        ORDER BY st.locale
+                    )
+                    FROM speaker_translations st
+                    WHERE st.conference_id = cs.conference_id AND st.speaker_id = cs.id
+                ), '[]'::jsonb)
+            ) as "state!"
+            FROM conference_speakers cs
+            WHERE cs.id = $1 AND cs.conference_id = $2
+            "#,
+            entity_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        AuditEntity::Room => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(cr) - 'created_at' - 'updated_at' as "state!"
+            FROM conference_rooms cr
+            WHERE cr.id = $1 AND cr.conference_id = $2
+            "#,
+            numeric_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        AuditEntity::Category => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(cc) - 'created_at' - 'updated_at' || jsonb_build_object(
+                'translations', COALESCE((
+                    SELECT jsonb_agg(
+                        to_jsonb(ct) - 'conference_id' - 'category_id' - 'created_at' - 'updated_at'
+                        ORDER BY ct.locale
+                    )
+                    FROM category_translations ct WHERE ct.category_id = cc.id
+                ), '[]'::jsonb)
+            ) as "state!"
+            FROM conference_categories cc
+            WHERE cc.id = $1 AND cc.conference_id = $2
+            "#,
+            numeric_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        AuditEntity::CategoryType => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(ct) - 'created_at' - 'updated_at' as "state!"
+            FROM category_types ct
+            WHERE ct.id = $1 AND ct.conference_id = $2
+            "#,
+            numeric_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        AuditEntity::Podcast => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(pc) - 'created_at' - 'updated_at' || jsonb_build_object(
+                'episodes', COALESCE((
+                    SELECT jsonb_agg(to_jsonb(pe) - 'created_at' - 'updated_at' ORDER BY pe.id)
+                    FROM podcast_episodes pe WHERE pe.channel_id = pc.id
+                ), '[]'::jsonb)
+            ) as "state!"
+            FROM podcast_channels pc
+            WHERE pc.id = $1
+            "#,
+            numeric_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        // Assets are shared by all conferences, like podcasts
+        AuditEntity::MediaAsset => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(ma) - 'created_at' - 'updated_at' || jsonb_build_object(
+                'variants', COALESCE((
+                    SELECT jsonb_agg(
+                        to_jsonb(mv) - 'asset_id' - 'created_at' ORDER BY mv.variant, mv.format
+                    )
+                    FROM media_variants mv WHERE mv.asset_id = ma.id
+                ), '[]'::jsonb)
+            ) as "state!"
+            FROM media_assets ma
+            WHERE ma.id = $1
+            "#,
+            entity_id
+        )
+        .fetch_optional(conn)
+        .await?,
+        AuditEntity::FeedbackForm => sqlx::query_scalar!(
+            r#"
+            SELECT to_jsonb(ff) - 'created_at' - 'updated_at' || jsonb_build_object(
+                'questions', COALESCE((
+                    SELECT jsonb_agg(
+                        to_jsonb(fq) - 'form_id' - 'created_at' - 'updated_at'
+                        ORDER BY fq.sort, fq.id
+                    )
+                    FROM feedback_questions fq WHERE fq.form_id = ff.id
+                ), '[]'::jsonb)
+            ) as "state!"
+            FROM feedback_forms ff
+            WHERE ff.id = $1 AND ff.conference_id = $2
+            "#,
+            numeric_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?,
+    };
+
+    Ok(state)
+}
+
+// Snapshots of several sessions in one query, keyed by session id. Sessions that do not
+// exist are left out.
+pub async fn snapshot_sessions(
+    conn: &mut PgConnection,
+    conference_id: Option<i32>,
+    session_ids: &[String],
+) -> Result<Vec<(String, Value)>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT cs.id, to_jsonb(cs) - 'created_at' - 'updated_at' || jsonb_build_object(
+            'speaker_ids', COALESCE((
+                SELECT jsonb_agg(speaker_id ORDER BY speaker_id)
+                FROM session_speakers
+                WHERE conference_id = cs.conference_id AND session_id = cs.id
+            ), '[]'::jsonb),
+            'category_ids', COALESCE((
+                SELECT jsonb_agg(category_item_id ORDER BY category_item_id)
+                FROM session_categories
+                WHERE conference_id = cs.conference_id AND session_id = cs.id
+            ), '[]'::jsonb),
+            'translations', COALESCE((
+                SELECT jsonb_agg(
+                    to_jsonb(st) - 'conference_id' - 'session_id' - 'created_at' - 'updated_at'
+                    ORDER BY st.locale
+                )
+                FROM session_translations st
+                WHERE st.conference_id = cs.conference_id AND st.session_id = cs.id
+            ), '[]'::jsonb)
+        ) as "state!"
+        FROM conference_sessions cs
+        WHERE cs.id = ANY($1) AND cs.conference_id = $2
+        "#,
+        session_ids,
+        conference_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.state)).collect())
+}
+
+// Serializes concurrent changes of the same entities until the transaction ends, so that
+// snapshots stay accurate and versions stay gapless. Locks are taken in id order.
+pub async fn lock(
+    conn: &mut PgConnection,
+    conference_id: Option<i32>,
+    entity: AuditEntity,
+    entity_ids: &[String],
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        SELECT pg_advisory_xact_lock(
+            hashtext(COALESCE($1::INTEGER::TEXT, '') || ':' || $2 || ':' || ids.id)
+        )
+        FROM (SELECT id FROM UNNEST($3::TEXT[]) id ORDER BY id) ids
+        "#,
+        conference_id,
+        entity.name(),
+        entity_ids
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+// Sessions that change along with the entity, e.g. when a room they are held in is deleted
+pub async fn get_related_sessions(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: &str,
+) -> Result<Vec<String>, ServiceError> {
+    let session_ids = match entity {
+        AuditEntity::Room => sqlx::query_scalar!(
+            "SELECT id FROM conference_sessions WHERE room_id = $1 AND conference_id = $2",
+            entity_id.parse::<i32>().ok(),
+            conference_id
+        )
+        .fetch_all(&mut *conn)
+        .await?,
+        AuditEntity::Speaker => sqlx::query_scalar!(
+            r#"
+            SELECT session_id FROM session_speakers
+            WHERE speaker_id = $1 AND conference_id = $2
+            "#,
+            entity_id,
+            conference_id
+        )
+        .fetch_all(&mut *conn)
+        .await?,
+        _ => Vec::new(),
+    };
+
+    Ok(session_ids)
+}
+
+// Adds an entry with the next version of the entity, within the transaction of the change
+#[allow(clippy::too_many_arguments)]
+pub async fn append(
+    conn: &mut PgConnection,
+    conference_id: Option<i32>,
+    entity: AuditEntity,
+    entity_id: &str,
+    action: AuditAction,
+    actor: &str,
+    request_id: Option<&str>,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) -> Result<i32, ServiceError> {
+    lock(conn, conference_id, entity, &[entity_id.to_string()]).await?;
+
+    let version = sqlx::query_scalar!(
+        r#"
+        INSERT INTO audit_log
+        (conference_id, entity, entity_id, version, action, actor, request_id, before, after)
+        SELECT $1, $2::VARCHAR, $3::VARCHAR, COALESCE(MAX(version), 0) + 1, $4, $5, $6, $7, $8
+        FROM audit_log
+        WHERE conference_id IS NOT DISTINCT FROM $1 AND entity = $2 AND entity_id = $3
+        RETURNING version
+        "#,
+        conference_id,
+        entity.name(),
+        entity_id,
+        action.name(),
+        actor,
+        request_id,
+        before,
+        after
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(version)
+}
+
+// Newest first. Podcasts are shared, their history shows up in every conference.
+pub async fn get_history(
+    pool: &PgPool,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: &str,
+) -> Result<Vec<AuditEntry>, ServiceError> {
+    let rows = sqlx::query_as!(
+        AuditRow,
+        r#"
+        SELECT id, entity, entity_id, version, action, actor, request_id, before, after, created_at
+        FROM audit_log
+        WHERE entity = $1 AND entity_id = $2 AND (conference_id = $3 OR conference_id IS NULL)
+        ORDER BY version DESC
+        "#,
+        entity.name(),
+        entity_id,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(AuditRow::into_entry).collect())
+}
+
+pub async fn get_version(
+    pool: &PgPool,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: &str,
+    version: i32,
+) -> Result<Option<AuditEntry>, ServiceError> {
+    let row = sqlx::query_as!(
+        AuditRow,
+        r#"
+        SELECT id, entity, entity_id, version, action, actor, request_id, before, after, created_at
+        FROM audit_log
+        WHERE entity = $1 AND entity_id = $2 AND version = $3
+            AND (conference_id = $4 OR conference_id IS NULL)
+        "#,
+        entity.name(),
+        entity_id,
+        version,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(AuditRow::into_entry))
+}
+
+// Latest changes of the conference, newest first. `before_id` pages backwards.
+pub async fn get_recent(
+    pool: &PgPool,
+    conference_id: i32,
+    before_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<AuditEntry>, ServiceError> {
+    let rows = sqlx::query_as!(
+        AuditRow,
+        r#"
+        SELECT id, entity, entity_id, version, action, actor, request_id, before, after, created_at
+        FROM audit_log
+        WHERE conference_id = $1 AND ($2::BIGINT IS NULL OR id < $2)
+        ORDER BY id DESC
+        LIMIT $3
+        "#,
+        conference_id,
+        before_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(AuditRow::into_entry).collect())
+}
+
+// Entries recorded for one actor across all conferences, oldest first
+pub async fn get_by_actor(pool: &PgPool, actor: &str) -> Result<Vec<AuditEntry>, ServiceError> {
+    let rows = sqlx::query_as!(
+        AuditRow,
+        r#"
+        SELECT id, entity, entity_id, version, action, actor, request_id, before, after, created_at
+        FROM audit_log
+        WHERE actor = $1
+        ORDER BY id
+        "#,
+        actor
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(AuditRow::into_entry).collect())
+}
+
+// Users still named by their token, from before entries were labelled with a digest. Erased
+// users and digests are left alone.
+pub async fn get_token_actors(conn: &mut PgConnection) -> Result<Vec<String>, ServiceError> {
+    let actors = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT actor AS "actor!"
+        FROM audit_log
+        WHERE actor LIKE 'user:%'
+          AND actor !~ '^user:([0-9a-f]{64}|anon-.*|deleted)$'
+        "#
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(actors)
+}
+
+// The only change the log allows, for erasing who made a change
+pub async fn rename_actor(conn: &mut PgConnection, actor: &str, replacement: &str) -> Result<u64, ServiceError> {
+    let result = sqlx::query!(
+        "UPDATE audit_log SET actor = $2 WHERE actor = $1",
+        actor,
+        replacement
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Writes a snapshot taken by `snapshot` back. Session links are restored to speakers and
+// categories that still exist, a restored speaker is not linked to sessions again.
+pub async fn restore(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    match entity {
+        AuditEntity::Session => restore_session(conn, conference_id, entity_id, state).await,
+        AuditEntity::Speaker => restore_speaker(conn, conference_id, entity_id, state).await,
+        AuditEntity::Room => restore_room(conn, conference_id, entity_id, state).await,
+        AuditEntity::Category => restore_category(conn, conference_id, entity_id, state).await,
+        AuditEntity::CategoryType => {
+            restore_category_type(conn, conference_id, entity_id, state).await
+        }
+        AuditEntity::Podcast => Err(ServiceError::BadRequest(
+            "Podcasts cannot be restored, import the feed again".to_string(),
+        )),
+        AuditEntity::MediaAsset => Err(ServiceError::BadRequest(
+            "Media assets cannot be restored, upload the file again".to_string(),
+        )),
+        AuditEntity::FeedbackForm => Err(ServiceError::BadRequest(
+            "Feedback forms cannot be restored, create the form again".to_string(),
+        )),
+    }
+}
+
+async fn restore_session(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    let current = sqlx::query_scalar!(
+        "SELECT status FROM conference_sessions WHERE id = $1 AND conference_id = $2 FOR UPDATE",
+        session_id,
+        conference_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(ServiceError::NotFound)?;
+
+    // Restoring goes through the same lifecycle as any other status change
+    let current = SessionStatus::from_name(&current).unwrap_or(SessionStatus::Draft);
+    let status = state
+        .get("status")
+        .and_then(Value::as_str)
+        .and_then(SessionStatus::from_name)
+        .unwrap_or(current);
+    if status != current && !current.can_become(status) {
+        return Err(ServiceError::BadRequest(format!(
+            "Session status cannot change from {} to {}",
+            current.name(),
+            status.name()
+        )));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE conference_sessions cs
+        SET title = r.title, description = r.description, starts_at = r.starts_at,
+            ends_at = r.ends_at, room_id = r.room_id, is_service_session = r.is_service_session,
+            is_plenum_session = r.is_plenum_session, status = $4,
+            vote_opens_at = r.vote_opens_at, vote_closes_at = r.vote_closes_at,
+            feedback_opens_at = r.feedback_opens_at, feedback_closes_at = r.feedback_closes_at,
+            published_at = COALESCE(cs.published_at, CASE WHEN $5 THEN NOW() END),
+            updated_at = NOW()
+        FROM jsonb_populate_record(NULL::conference_sessions, $3) r
+        WHERE cs.id = $1 AND cs.conference_id = $2
+        "#,
+        session_id,
+        conference_id,
+        state,
+        status.name(),
+        status.is_public()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    sqlx::query!(
+        "DELETE FROM session_speakers WHERE session_id = $1 AND conference_id = $2",
+        session_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+    sqlx::query!(
+        r#"
+        INSERT INTO session_speakers (conference_id, session_id, speaker_id)
+        SELECT $2, $1, cs.id FROM conference_speakers cs
+        WHERE cs.conference_id = $2 AND cs.deleted_at IS NULL
+            AND cs.id IN (SELECT jsonb_array_elements_text($3::JSONB -> 'speaker_ids'))
+        "#,
+        session_id,
+        conference_id,
+        state
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    sqlx::query!(
+        "DELETE FROM session_categories WHERE session_id = $1 AND conference_id = $2",
+        session_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+    sqlx::query!(
+        r#"
+        INSERT INTO session_categories (conference_id, session_id, category_item_id)
+        SELECT $2, $1, cc.id FROM conference_categories cc
+        WHERE cc.conference_id = $2
+            AND cc.id::TEXT IN (SELECT jsonb_array_elements_text($3::JSONB -> 'category_ids'))
+        "#,
+        session_id,
+        conference_id,
+        state
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    // Snapshots taken before translations were audited leave them as they are
+    if let Some(translations) = state.get("translations") {
+        sqlx::query!(
+            "DELETE FROM session_translations WHERE session_id = $1 AND conference_id = $2",
+            session_id,
+            conference_id
+        )
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO session_translations (session_id, locale, conference_id, title, description)
+            SELECT $1, t.locale, $2, t.title, t.description
+            FROM jsonb_populate_recordset(NULL::session_translations, $3) t
+            "#,
+            session_id,
+            conference_id,
+            translations
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(restore_error)?;
+    }
+
+    check_single_choice(conn, conference_id, Some(session_id)).await
+}
+
+async fn restore_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_speakers cs
+        SET first_name = r.first_name, last_name = r.last_name, bio = r.bio,
+            tag_line = r.tag_line, profile_picture = r.profile_picture,
+            is_top_speaker = r.is_top_speaker, company = r.company, job_title = r.job_title,
+            pronouns = r.pronouns, country = r.country, links = r.links, photos = r.photos,
+            photo_asset_id = r.photo_asset_id, deleted_at = r.deleted_at,
+            merged_into = r.merged_into, updated_at = NOW()
+        FROM jsonb_populate_record(NULL::conference_speakers, $3) r
+        WHERE cs.id = $1 AND cs.conference_id = $2
+        "#,
+        speaker_id,
+        conference_id,
+        state
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ServiceError::NotFound);
+    }
+
+    if let Some(translations) = state.get("translations") {
+        sqlx::query!(
+            "DELETE FROM speaker_translations WHERE speaker_id = $1 AND conference_id = $2",
+            speaker_id,
+            conference_id
+        )
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO speaker_translations (speaker_id, locale, conference_id, bio, tag_line)
+            SELECT $1, t.locale, $2, t.bio, t.tag_line
+            FROM jsonb_populate_recordset(NULL::speaker_translations, $3) t
+            "#,
+            speaker_id,
+            conference_id,
+            translations
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(restore_error)?;
+    }
+    Ok(())
+}
+
+// Deleted rooms come back with their old id
+async fn restore_room(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    room_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    let room_id = room_id.parse::<i32>().map_err(|_| ServiceError::NotFound)?;
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO conference_rooms
+        (id, name, sort, capacity, floor, building, accessibility_notes, map_x, map_y, deleted_at,
+            conference_id)
+        SELECT $1, r.name, r.sort, r.capacity, r.floor, r.building, r.accessibility_notes,
+            r.map_x, r.map_y, r.deleted_at, $2
+        FROM jsonb_populate_record(NULL::conference_rooms, $3) r
+        ON CONFLICT (id) DO UPDATE
+        SET name = EXCLUDED.name, sort = EXCLUDED.sort, capacity = EXCLUDED.capacity,
+            floor = EXCLUDED.floor, building = EXCLUDED.building,
+            accessibility_notes = EXCLUDED.accessibility_notes, map_x = EXCLUDED.map_x,
+            map_y = EXCLUDED.map_y, deleted_at = EXCLUDED.deleted_at, updated_at = NOW()
+        WHERE conference_rooms.conference_id = EXCLUDED.conference_id
+        "#,
+        room_id,
+        conference_id,
+        state
+    )
+    .execute(conn)
+    .await
+    .map_err(restore_error)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(())
+}
+
+async fn restore_category(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    let category_id = category_id.parse::<i32>().map_err(|_| ServiceError::NotFound)?;
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO conference_categories (id, title, sort, type, type_id, parent_id, conference_id)
+        SELECT $1, r.title, r.sort, r.type, r.type_id, r.parent_id, $2
+        FROM jsonb_populate_record(NULL::conference_categories, $3) r
+        ON CONFLICT (id) DO UPDATE
+        SET title = EXCLUDED.title, sort = EXCLUDED.sort, type = EXCLUDED.type,
+            type_id = EXCLUDED.type_id, parent_id = EXCLUDED.parent_id, updated_at = NOW()
+        WHERE conference_categories.conference_id = EXCLUDED.conference_id
+        "#,
+        category_id,
+        conference_id,
+        state
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ServiceError::NotFound);
+    }
+
+    if let Some(translations) = state.get("translations") {
+        sqlx::query!(
+            "DELETE FROM category_translations WHERE category_id = $1",
+            category_id
+        )
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO category_translations (category_id, locale, conference_id, title)
+            SELECT $1, t.locale, $2, t.title
+            FROM jsonb_populate_recordset(NULL::category_translations, $3) t
+            "#,
+            category_id,
+            conference_id,
+            translations
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(restore_error)?;
+    }
+    check_category(conn, conference_id, category_id).await
+}
+
+async fn restore_category_type(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    type_id: &str,
+    state: &Value,
+) -> Result<(), ServiceError> {
+    let type_id = type_id.parse::<i32>().map_err(|_| ServiceError::NotFound)?;
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO category_types (id, name, sort, multiple, conference_id)
+        SELECT $1, r.name, r.sort, r.multiple, $2
+        FROM jsonb_populate_record(NULL::category_types, $3) r
+        ON CONFLICT (id) DO UPDATE
+        SET name = EXCLUDED.name, sort = EXCLUDED.sort, multiple = EXCLUDED.multiple,
+            updated_at = NOW()
+        WHERE category_types.conference_id = EXCLUDED.conference_id
+        "#,
+        type_id,
+        conference_id,
+        state
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ServiceError::NotFound);
+    }
+
+    // The type name is part of every category of the type
+    sqlx::query!(
+        "UPDATE conference_categories SET updated_at = NOW() WHERE type_id = $1",
+        type_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(restore_error)?;
+
+    check_single_choice(conn, conference_id, None).await
+}
+
+// A snapshot can refer to rows that have been removed since, or clash with rows added since
+fn restore_error(error: sqlx::Error) -> ServiceError {
+    let code = error.as_database_error().and_then(|e| e.code());
+    match code.as_deref() {
+        Some("23503") => ServiceError::BadRequest(
+            "The version refers to something that no longer exists, restore that first".to_string(),
+        ),
+        Some("23505") => ServiceError::BadRequest(
+            "The version clashes with a newer change, e.g. a name that is taken".to_string(),
+        ),
+        _ => error.into(),
+    }
+}
\ No newline at end of file
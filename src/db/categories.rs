@@ -1,69 +1,475 @@
 // src/db/categories.rs
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::error::ServiceError;
-use crate::models::category::{CategoriesTable, ConferenceCategoriesRequest};
+use crate::models::category::{
+    CategoriesTable, CategoryType, CategoryTypeRequest, ConferenceCategoriesRequest,
+};
 
-pub async fn get_all_categories(pool: &PgPool) -> Result<Vec<CategoriesTable>, ServiceError> {
-    let categories = sqlx::query!(
-        r#"
-        SELECT id, title, sort, type as "type_name"
-        FROM conference_categories
-        "#
-    )
-    .fetch_all(pool)
-    .await?;
+pub(crate) struct CategoryRow {
+    pub id: i32,
+    pub title: String,
+    pub sort: Option<i32>,
+    pub type_name: Option<String>,
+    pub type_id: Option<i32>,
+    pub parent_id: Option<i32>,
+}
 
-    Ok(categories
-        .into_iter()
-        .map(|row| CategoriesTable {
+impl From<CategoryRow> for CategoriesTable {
+    fn from(row: CategoryRow) -> Self {
+        CategoriesTable {
             id: row.id as i64,
             title: row.title,
             sort: row.sort,
             type_name: row.type_name,
-        })
-        .collect())
+            type_id: row.type_id,
+            parent_id: row.parent_id,
+        }
+    }
+}
+
+pub async fn get_all_categories(
+    pool: &PgPool,
+    conference_id: i32,
+    locale: Option<&str>,
+) -> Result<Vec<CategoriesTable>, ServiceError> {
+    let categories = sqlx::query_as!(
+        CategoryRow,
+        r#"
+        SELECT cc.id, COALESCE(tr.title, cc.title) as "title!", cc.sort,
+            COALESCE(ct.name, cc.type) as type_name, cc.type_id, cc.parent_id
+        FROM conference_categories cc
+        LEFT JOIN category_types ct ON ct.id = cc.type_id
+        LEFT JOIN category_translations tr ON tr.category_id = cc.id AND tr.locale = $2
+        WHERE cc.conference_id = $1
+        "#,
+        conference_id,
+        locale
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(categories.into_iter().map(CategoriesTable::from).collect())
 }
 
 pub async fn get_category_by_id(
     pool: &PgPool,
+    conference_id: i32,
     category_id: i32,
 ) -> Result<Option<CategoriesTable>, ServiceError> {
-    let category = sqlx::query!(
+    let category = sqlx::query_as!(
+        CategoryRow,
         r#"
-        SELECT id, title, sort, type as "type_name"
-        FROM conference_categories
-        WHERE id = $1
+        SELECT cc.id, cc.title, cc.sort,
+            COALESCE(ct.name, cc.type) as type_name, cc.type_id, cc.parent_id
+        FROM conference_categories cc
+        LEFT JOIN category_types ct ON ct.id = cc.type_id
+        WHERE cc.id = $1 AND cc.conference_id = $2
         "#,
-        category_id
+        category_id,
+        conference_id
     )
     .fetch_optional(pool)
     .await?;
 
-    Ok(category.map(|row| CategoriesTable {
-        id: row.id as i64,
-        title: row.title,
-        sort: row.sort,
-        type_name: row.type_name,
-    }))
+    Ok(category.map(CategoriesTable::from))
 }
 
 pub async fn add_category(
-    pool: &PgPool,
+    conn: &mut PgConnection,
+    conference_id: i32,
     category: &ConferenceCategoriesRequest,
 ) -> Result<i32, ServiceError> {
+    let type_id = resolve_type(&mut *conn, conference_id, category).await?;
+    if let Some(parent_id) = category.parent_id {
+        check_parent(&mut *conn, conference_id, None, parent_id, type_id).await?;
+    }
+
     let row = sqlx::query!(
         r#"
-        INSERT INTO conference_categories (title, sort, type)
-        VALUES ($1, $2, $3)
+        INSERT INTO conference_categories (title, sort, type, conference_id, type_id, parent_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
         RETURNING id
         "#,
         category.title,
         category.sort,
-        category.type_name
+        category.type_name,
+        conference_id,
+        type_id,
+        category.parent_id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await?;
-
     Ok(row.id)
+}
+
+pub async fn update_category(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: i32,
+    category: &ConferenceCategoriesRequest,
+) -> Result<bool, ServiceError> {
+    let existing = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM conference_categories
+        WHERE id = $1 AND conference_id = $2
+        FOR UPDATE
+        "#,
+        category_id,
+        conference_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if existing.is_none() {
+        return Ok(false);
+    }
+
+    let type_id = resolve_type(&mut *conn, conference_id, category).await?;
+    if let Some(parent_id) = category.parent_id {
+        check_parent(&mut *conn, conference_id, Some(category_id), parent_id, type_id).await?;
+    }
+    check_children(&mut *conn, category_id, type_id).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE conference_categories
+        SET title = $2, sort = $3, type = $4, type_id = $5, parent_id = $6, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        category_id,
+        category.title,
+        category.sort,
+        category.type_name,
+        type_id,
+        category.parent_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Moving the category into a single-choice type must not leave sessions with two of them
+    check_single_choice(&mut *conn, conference_id, None).await?;
+    Ok(true)
+}
+
+pub async fn get_category_types(
+    pool: &PgPool,
+    conference_id: i32,
+) -> Result<Vec<CategoryType>, ServiceError> {
+    let types = sqlx::query_as!(
+        CategoryType,
+        r#"
+        SELECT id, name, sort, multiple
+        FROM category_types
+        WHERE conference_id = $1
+        ORDER BY sort NULLS LAST, id
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(types)
+}
+
+pub async fn add_category_type(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_type: &CategoryTypeRequest,
+) -> Result<i32, ServiceError> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO category_types (conference_id, name, sort, multiple)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (conference_id, name) DO NOTHING
+        RETURNING id
+        "#,
+        conference_id,
+        category_type.name,
+        category_type.sort,
+        category_type.multiple
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    id.ok_or_else(|| {
+        ServiceError::BadRequest(format!("Category type '{}' already exists", category_type.name))
+    })
+}
+
+pub async fn update_category_type(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    type_id: i32,
+    category_type: &CategoryTypeRequest,
+) -> Result<bool, ServiceError> {
+    let taken = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM category_types
+            WHERE conference_id = $1 AND name = $2 AND id <> $3
+        ) as "exists!"
+        "#,
+        conference_id,
+        category_type.name,
+        type_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if taken {
+        return Err(ServiceError::BadRequest(format!(
+            "Category type '{}' already exists",
+            category_type.name
+        )));
+    }
+
+    let updated = sqlx::query!(
+        r#"
+        UPDATE category_types
+        SET name = $3, sort = $4, multiple = $5, updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        type_id,
+        conference_id,
+        category_type.name,
+        category_type.sort,
+        category_type.multiple
+    )
+    .execute(&mut *conn)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    // The type name is part of every category of the type, /sync/categories has to resend them
+    sqlx::query!(
+        "UPDATE conference_categories SET updated_at = NOW() WHERE type_id = $1",
+        type_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if !category_type.multiple {
+        check_single_choice(&mut *conn, conference_id, None).await?;
+    }
+    Ok(true)
+}
+
+// Types still used by categories cannot be deleted, returns false if the type does not exist
+pub async fn delete_category_type(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    type_id: i32,
+) -> Result<bool, ServiceError> {
+    let exists = sqlx::query_scalar!(
+        "SELECT id FROM category_types WHERE id = $1 AND conference_id = $2 FOR UPDATE",
+        type_id,
+        conference_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if exists.is_none() {
+        return Ok(false);
+    }
+
+    let used_by = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM conference_categories WHERE type_id = $1"#,
+        type_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if used_by > 0 {
+        return Err(ServiceError::BadRequest(format!(
+            "The category type is used by {} categories",
+            used_by
+        )));
+    }
+
+    sqlx::query!("DELETE FROM category_types WHERE id = $1", type_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(true)
+}
+
+// Validates a category that was written without add_category or update_category, e.g. when
+// it was restored from the audit log
+pub(crate) async fn check_category(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: i32,
+) -> Result<(), ServiceError> {
+    let category = sqlx::query!(
+        "SELECT type_id, parent_id FROM conference_categories WHERE id = $1",
+        category_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if let Some(parent_id) = category.parent_id {
+        check_parent(&mut *conn, conference_id, Some(category_id), parent_id, category.type_id)
+            .await?;
+    }
+
+    check_children(&mut *conn, category_id, category.type_id).await?;
+    check_single_choice(conn, conference_id, None).await
+}
+
+// Fails if a session, or any session of the conference when `session_id` is None, has more
+// than one category of a single-choice type. Callers run it after writing, inside the
+// transaction, so the write is rolled back.
+pub(crate) async fn check_single_choice(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: Option<&str>,
+) -> Result<(), ServiceError> {
+    let conflict = sqlx::query!(
+        r#"
+        SELECT sc.session_id, ct.name, array_agg(cc.title ORDER BY cc.title) as "titles!"
+        FROM session_categories sc
+        JOIN conference_categories cc ON cc.id = sc.category_item_id
+        JOIN category_types ct ON ct.id = cc.type_id
+        WHERE sc.conference_id = $1 AND ct.conference_id = $1 AND NOT ct.multiple
+            AND ($2::VARCHAR IS NULL OR sc.session_id = $2)
+        GROUP BY sc.session_id, ct.id, ct.name
+        HAVING COUNT(*) > 1
+        LIMIT 1
+        "#,
+        conference_id,
+        session_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    match conflict {
+        Some(conflict) => Err(ServiceError::BadRequest(format!(
+            "Sessions can only have one {} category, session {} would have {}",
+            conflict.name,
+            conflict.session_id,
+            conflict.titles.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+// The type given by id, or by name for clients that only send the free-form type
+async fn resolve_type(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category: &ConferenceCategoriesRequest,
+) -> Result<Option<i32>, ServiceError> {
+    if let Some(type_id) = category.type_id {
+        let known = sqlx::query_scalar!(
+            "SELECT id FROM category_types WHERE id = $1 AND conference_id = $2 FOR KEY SHARE",
+            type_id,
+            conference_id
+        )
+        .fetch_optional(conn)
+        .await?;
+        return match known {
+            Some(_) => Ok(Some(type_id)),
+            None => Err(ServiceError::BadRequest(format!("Unknown category type {}", type_id))),
+        };
+    }
+
+    let Some(type_name) = &category.type_name else {
+        return Ok(None);
+    };
+    let type_id = sqlx::query_scalar!(
+        "SELECT id FROM category_types WHERE name = $1 AND conference_id = $2 FOR KEY SHARE",
+        type_name,
+        conference_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(type_id)
+}
+
+// The parent has to be a category of the same type that is not the category itself or one
+// of its descendants
+async fn check_parent(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: Option<i32>,
+    parent_id: i32,
+    type_id: Option<i32>,
+) -> Result<(), ServiceError> {
+    let parent = sqlx::query!(
+        r#"
+        SELECT type_id FROM conference_categories
+        WHERE id = $1 AND conference_id = $2
+        FOR KEY SHARE
+        "#,
+        parent_id,
+        conference_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| ServiceError::BadRequest(format!("Unknown parent category {}", parent_id)))?;
+
+    if parent.type_id != type_id {
+        return Err(ServiceError::BadRequest(
+            "A category must have the same type as its parent".to_string(),
+        ));
+    }
+
+    let Some(category_id) = category_id else {
+        return Ok(());
+    };
+    // Two moves that each look fine on their own can close a loop together, re-parenting
+    // is serialised per conference until the transaction ends
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext('category_tree:' || $1::INTEGER::TEXT))",
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    let cycle = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE ancestors AS (
+            SELECT id, parent_id FROM conference_categories WHERE id = $1
+            UNION
+            SELECT cc.id, cc.parent_id
+            FROM conference_categories cc
+            JOIN ancestors a ON cc.id = a.parent_id
+        )
+        SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2) as "exists!"
+        "#,
+        parent_id,
+        category_id
+    )
+    .fetch_one(conn)
+    .await?;
+    if cycle {
+        return Err(ServiceError::BadRequest(
+            "A category cannot be nested inside itself".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn check_children(
+    conn: &mut PgConnection,
+    category_id: i32,
+    type_id: Option<i32>,
+) -> Result<(), ServiceError> {
+    let mismatched = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM conference_categories
+            WHERE parent_id = $1 AND type_id IS DISTINCT FROM $2
+        ) as "exists!"
+        "#,
+        category_id,
+        type_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    if mismatched {
+        return Err(ServiceError::BadRequest(
+            "The category has children of another type, move them first".to_string(),
+        ));
+    }
+    Ok(())
 }
\ No newline at end of file
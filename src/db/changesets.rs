@@ -0,0 +1,407 @@
+// src/db/changesets.rs
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgConnection, PgPool};
+
+use crate::error::ServiceError;
+use crate::models::changeset::{ChangeOperation, Changeset, ChangesetOperation, ChangesetStatus};
+use crate::models::time::{self, TimeSettings};
+
+struct ChangesetRow {
+    id: i32,
+    title: String,
+    status: String,
+    apply_at: Option<DateTime<Utc>>,
+    applied_at: Option<DateTime<Utc>>,
+    error: Option<String>,
+    created_by: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+struct OperationRow {
+    id: i32,
+    changeset_id: i32,
+    operation: Json<ChangeOperation>,
+}
+
+fn into_changeset(row: ChangesetRow, operations: &mut Vec<OperationRow>) -> Changeset {
+    let (own, rest) = std::mem::take(operations)
+        .into_iter()
+        .partition(|operation| operation.changeset_id == row.id);
+    *operations = rest;
+
+    Changeset {
+        id: row.id,
+        title: row.title,
+        status: ChangesetStatus::from_name(&row.status).unwrap_or(ChangesetStatus::Draft),
+        apply_at: row.apply_at,
+        applied_at: row.applied_at,
+        error: row.error,
+        created_by: row.created_by,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        operations: own
+            .into_iter()
+            .map(|operation: OperationRow| ChangesetOperation {
+                id: operation.id,
+                operation: operation.operation.0,
+            })
+            .collect(),
+    }
+}
+
+// Newest first
+pub async fn get_changesets(pool: &PgPool, conference_id: i32) -> Result<Vec<Changeset>, ServiceError> {
+    let rows = sqlx::query_as!(
+        ChangesetRow,
+        r#"
+        SELECT id, title, status, apply_at, applied_at, error, created_by, created_at, updated_at
+        FROM changesets
+        WHERE conference_id = $1
+        ORDER BY id DESC
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let query = sqlx::query_as!(
+        OperationRow,
+        r#"
+        SELECT co.id, co.changeset_id, co.operation as "operation: Json<ChangeOperation>"
+        FROM changeset_operations co
+        JOIN changesets c ON c.id = co.changeset_id
+        WHERE c.conference_id = $1
+        ORDER BY co.id
+        "#,
+        conference_id
+    );
+    let mut operations = time::scope(TimeSettings::STORED, query.fetch_all(pool)).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| into_changeset(row, &mut operations))
+        .collect())
+}
+
+pub async fn get_changeset(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<Option<Changeset>, ServiceError> {
+    let row = sqlx::query_as!(
+        ChangesetRow,
+        r#"
+        SELECT id, title, status, apply_at, applied_at, error, created_by, created_at, updated_at
+        FROM changesets
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        changeset_id,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let mut operations = get_operation_rows(pool, changeset_id).await?;
+    Ok(Some(into_changeset(row, &mut operations)))
+}
+
+async fn get_operation_rows<'e, E>(executor: E, changeset_id: i32) -> Result<Vec<OperationRow>, ServiceError>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let query = sqlx::query_as!(
+        OperationRow,
+        r#"
+        SELECT id, changeset_id, operation as "operation: Json<ChangeOperation>"
+        FROM changeset_operations
+        WHERE changeset_id = $1
+        ORDER BY id
+        "#,
+        changeset_id
+    );
+    let operations = time::scope(TimeSettings::STORED, query.fetch_all(executor)).await?;
+
+    Ok(operations)
+}
+
+pub async fn add_changeset(
+    pool: &PgPool,
+    conference_id: i32,
+    title: &str,
+    created_by: &str,
+) -> Result<i32, ServiceError> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO changesets (conference_id, title, created_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        conference_id,
+        title,
+        created_by
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+// Locks the changeset and returns its status, None if it does not exist
+async fn lock_changeset(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<Option<ChangesetStatus>, ServiceError> {
+    let status = sqlx::query_scalar!(
+        r#"
+        SELECT status FROM changesets
+        WHERE id = $1 AND conference_id = $2
+        FOR UPDATE
+        "#,
+        changeset_id,
+        conference_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(status.map(|status| ChangesetStatus::from_name(&status).unwrap_or(ChangesetStatus::Draft)))
+}
+
+// For the scheduler, false if the changeset was applied, unscheduled or postponed since it was
+// listed as due
+pub async fn lock_due_changeset(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    changeset_id: i32,
+    now: DateTime<Utc>,
+) -> Result<bool, ServiceError> {
+    let locked = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM changesets
+        WHERE id = $1 AND conference_id = $2 AND status = 'scheduled' AND apply_at <= $3
+        FOR UPDATE
+        "#,
+        changeset_id,
+        conference_id,
+        now
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(locked.is_some())
+}
+
+// Like lock_changeset, for changes that are only allowed until the changeset has been applied
+async fn lock_open_changeset(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<bool, ServiceError> {
+    match lock_changeset(conn, conference_id, changeset_id).await? {
+        Some(ChangesetStatus::Applied) => Err(ServiceError::BadRequest(
+            "The changeset has already been applied".to_string(),
+        )),
+        Some(_) => Ok(true),
+        None => Ok(false),
+    }
+}
+
+// Applied changesets are kept as a record of what went live
+pub async fn delete_changeset(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<bool, ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    if !lock_open_changeset(&mut tx, conference_id, changeset_id).await? {
+        return Ok(false);
+    }
+    sqlx::query!("DELETE FROM changesets WHERE id = $1", changeset_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+// Returns the id of the queued operation, None if the changeset does not exist
+pub async fn add_operation(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+    operation: &ChangeOperation,
+) -> Result<Option<i32>, ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    if !lock_open_changeset(&mut tx, conference_id, changeset_id).await? {
+        return Ok(None);
+    }
+    // Operations keep their times as stored, whatever format the client that adds them uses
+    let id = time::scope(TimeSettings::STORED, async {
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO changeset_operations (changeset_id, operation)
+            VALUES ($1, $2)
+            RETURNING id
+            "#,
+            changeset_id,
+            Json(operation) as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+    })
+    .await?;
+    touch_changeset(&mut tx, changeset_id).await?;
+
+    tx.commit().await?;
+    Ok(Some(id))
+}
+
+pub async fn delete_operation(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+    operation_id: i32,
+) -> Result<bool, ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    if !lock_open_changeset(&mut tx, conference_id, changeset_id).await? {
+        return Ok(false);
+    }
+    let result = sqlx::query!(
+        "DELETE FROM changeset_operations WHERE id = $1 AND changeset_id = $2",
+        operation_id,
+        changeset_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    touch_changeset(&mut tx, changeset_id).await?;
+
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Scheduling with None takes the changeset back to a draft
+pub async fn set_schedule(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+    apply_at: Option<DateTime<Utc>>,
+) -> Result<bool, ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    if !lock_open_changeset(&mut tx, conference_id, changeset_id).await? {
+        return Ok(false);
+    }
+    let status = match apply_at {
+        Some(_) => ChangesetStatus::Scheduled,
+        None => ChangesetStatus::Draft,
+    };
+    sqlx::query!(
+        r#"
+        UPDATE changesets
+        SET status = $2, apply_at = $3, error = NULL, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        changeset_id,
+        status.name(),
+        apply_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+// Locks the changeset for applying it and returns its operations in order. None if it does
+// not exist, an error if it has been applied already.
+pub async fn lock_for_apply(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<Option<Vec<ChangesetOperation>>, ServiceError> {
+    if !lock_open_changeset(conn, conference_id, changeset_id).await? {
+        return Ok(None);
+    }
+    let operations = get_operation_rows(&mut *conn, changeset_id).await?;
+
+    Ok(Some(
+        operations
+            .into_iter()
+            .map(|row| ChangesetOperation {
+                id: row.id,
+                operation: row.operation.0,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn mark_applied(conn: &mut PgConnection, changeset_id: i32) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE changesets
+        SET status = 'applied', applied_at = NOW(), error = NULL, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        changeset_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+// Only a changeset that is still scheduled fails, it may have been changed in the meantime
+pub async fn mark_failed(pool: &PgPool, changeset_id: i32, error: &str) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE changesets
+        SET status = 'failed', error = $2, updated_at = NOW()
+        WHERE id = $1 AND status = 'scheduled'
+        "#,
+        changeset_id,
+        error
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Ids of the scheduled changesets of the conference whose time has come, oldest first
+pub async fn get_due_changesets(
+    pool: &PgPool,
+    conference_id: i32,
+    now: DateTime<Utc>,
+) -> Result<Vec<(i32, String)>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, created_by FROM changesets
+        WHERE conference_id = $1 AND status = 'scheduled' AND apply_at <= $2
+        ORDER BY apply_at, id
+        "#,
+        conference_id,
+        now
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.created_by)).collect())
+}
+
+async fn touch_changeset(conn: &mut PgConnection, changeset_id: i32) -> Result<(), ServiceError> {
+    sqlx::query!(
+        "UPDATE changesets SET updated_at = NOW() WHERE id = $1",
+        changeset_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
\ No newline at end of file
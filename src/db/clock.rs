@@ -0,0 +1,127 @@
+// src/db/clock.rs
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+
+// The empty cohort name stands for the global simulation
+pub const GLOBAL_COHORT: &str = "";
+
+pub struct SimulatedTime {
+    pub simulated_at: DateTime<Utc>,
+    pub set_at: DateTime<Utc>,
+}
+
+pub async fn get_simulated_time(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: Option<&str>,
+) -> Result<Option<SimulatedTime>, ServiceError> {
+    // A cohort simulation the user belongs to wins over the global one
+    let row = sqlx::query!(
+        r#"
+        SELECT st.simulated_at, st.set_at
+        FROM simulated_time st
+        LEFT JOIN test_user_cohorts tc
+            ON tc.conference_id = st.conference_id AND tc.cohort = st.cohort AND tc.uuid = $1
+        WHERE st.conference_id = $3 AND (st.cohort = $2 OR tc.uuid IS NOT NULL)
+        ORDER BY (st.cohort = $2)
+        LIMIT 1
+        "#,
+        user_id,
+        GLOBAL_COHORT,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| SimulatedTime {
+        simulated_at: row.simulated_at,
+        set_at: row.set_at,
+    }))
+}
+
+pub async fn set_simulated_time(
+    pool: &PgPool,
+    conference_id: i32,
+    cohort: &str,
+    simulated_at: Option<DateTime<Utc>>,
+    set_at: DateTime<Utc>,
+) -> Result<(), ServiceError> {
+    match simulated_at {
+        Some(simulated_at) => {
+            sqlx::query!(
+                r#"
+                INSERT INTO simulated_time (conference_id, cohort, simulated_at, set_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (conference_id, cohort) DO UPDATE
+                SET simulated_at = EXCLUDED.simulated_at, set_at = EXCLUDED.set_at, updated_at = NOW()
+                "#,
+                conference_id,
+                cohort,
+                simulated_at,
+                set_at
+            )
+            .execute(pool)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                r#"
+                DELETE FROM simulated_time
+                WHERE conference_id = $1 AND cohort = $2
+                "#,
+                conference_id,
+                cohort
+            )
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn add_cohort_member(
+    pool: &PgPool,
+    conference_id: i32,
+    cohort: &str,
+    user_id: &str,
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO test_user_cohorts (conference_id, uuid, cohort)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (conference_id, uuid) DO UPDATE
+        SET cohort = EXCLUDED.cohort, updated_at = NOW()
+        "#,
+        conference_id,
+        user_id,
+        cohort
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_cohort_member(
+    pool: &PgPool,
+    conference_id: i32,
+    cohort: &str,
+    user_id: &str,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM test_user_cohorts
+        WHERE uuid = $1 AND cohort = $2 AND conference_id = $3
+        "#,
+        user_id,
+        cohort,
+        conference_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+// src/db/conferences.rs
+use sqlx::{types::Json, PgPool};
+
+use crate::error::ServiceError;
+use crate::models::edition::{ConferenceEdition, ConferenceEditionRequest, ConferenceSettings};
+
+struct ConferenceRow {
+    id: i32,
+    slug: String,
+    name: String,
+    time_zone: Option<String>,
+    admin_secret_hash: Option<String>,
+    settings: Json<ConferenceSettings>,
+    is_default: bool,
+}
+
+impl From<ConferenceRow> for ConferenceEdition {
+    fn from(row: ConferenceRow) -> Self {
+        ConferenceEdition {
+            id: row.id,
+            slug: row.slug,
+            name: row.name,
+            time_zone: row.time_zone,
+            admin_secret_hash: row.admin_secret_hash,
+            settings: row.settings.0,
+            is_default: row.is_default,
+        }
+    }
+}
+
+pub async fn get_conference_by_slug(
+    pool: &PgPool,
+    slug: &str,
+) -> Result<Option<ConferenceEdition>, ServiceError> {
+    let conference = sqlx::query_as!(
+        ConferenceRow,
+        r#"
+        SELECT id, slug, name, time_zone, admin_secret_hash,
+               settings as "settings: Json<ConferenceSettings>", is_default
+        FROM conferences
+        WHERE slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(conference.map(Into::into))
+}
+
+pub async fn get_all_conferences(pool: &PgPool) -> Result<Vec<ConferenceEdition>, ServiceError> {
+    let conferences = sqlx::query_as!(
+        ConferenceRow,
+        r#"
+        SELECT id, slug, name, time_zone, admin_secret_hash,
+               settings as "settings: Json<ConferenceSettings>", is_default
+        FROM conferences
+        ORDER BY id
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(conferences.into_iter().map(Into::into).collect())
+}
+
+pub async fn add_conference(
+    pool: &PgPool,
+    conference: &ConferenceEditionRequest,
+    admin_secret_hash: Option<String>,
+) -> Result<i32, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO conferences (slug, name, time_zone, admin_secret_hash, settings)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+        conference.slug,
+        conference.name,
+        conference.time_zone,
+        admin_secret_hash,
+        Json(&conference.settings) as _
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.id)
+}
+
+pub async fn update_conference(
+    pool: &PgPool,
+    slug: &str,
+    conference: &ConferenceEditionRequest,
+    admin_secret_hash: Option<String>,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE conferences
+        SET slug = $2, name = $3, time_zone = $4, admin_secret_hash = $5, settings = $6, updated_at = NOW()
+        WHERE slug = $1
+        "#,
+        slug,
+        conference.slug,
+        conference.name,
+        conference.time_zone,
+        admin_secret_hash,
+        Json(&conference.settings) as _
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+// src/db/events.rs
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+use crate::models::event::{ChangeEntity, ChangeEvent};
+
+pub const CHANGES_CHANNEL: &str = "conference_changes";
+
+// pg_notify rejects payloads of 8000 bytes or more, a few hundred ids
+const MAX_PAYLOAD_BYTES: usize = 7000;
+
+// Delivered to every listening backend instance, including this one. Events with too many ids
+// to fit are sent without them, which tells listeners to re-sync the whole entity.
+pub async fn notify_change(pool: &PgPool, event: &ChangeEvent) -> Result<(), ServiceError> {
+    let mut payload = serde_json::to_string(event)?;
+    if payload.len() > MAX_PAYLOAD_BYTES {
+        payload = serde_json::to_string(&ChangeEvent { ids: Vec::new(), ..event.clone() })?;
+    }
+
+    sqlx::query!("SELECT pg_notify($1, $2)", CHANGES_CHANNEL, payload)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// Earliest committed updated_at of the changed rows, None for rows that no longer exist.
+// Podcasts are shared, their ids are not scoped by the conference.
+pub async fn get_changed_at(
+    pool: &PgPool,
+    conference_id: i32,
+    entity: ChangeEntity,
+    ids: &[String],
+) -> Result<Option<DateTime<Utc>>, ServiceError> {
+    let changed_at = match entity {
+        ChangeEntity::Sessions => sqlx::query_scalar!(
+            "SELECT MIN(updated_at) FROM conference_sessions WHERE conference_id = $1 AND id = ANY($2)",
+            conference_id,
+            ids
+        )
+        .fetch_one(pool)
+        .await?,
+        ChangeEntity::Speakers => sqlx::query_scalar!(
+            "SELECT MIN(updated_at) FROM conference_speakers WHERE conference_id = $1 AND id = ANY($2)",
+            conference_id,
+            ids
+        )
+        .fetch_one(pool)
+        .await?,
+        ChangeEntity::Rooms => sqlx::query_scalar!(
+            "SELECT MIN(updated_at) FROM conference_rooms WHERE conference_id = $1 AND id::TEXT = ANY($2)",
+            conference_id,
+            ids
+        )
+        .fetch_one(pool)
+        .await?,
+        ChangeEntity::Categories => sqlx::query_scalar!(
+            "SELECT MIN(updated_at) FROM conference_categories WHERE conference_id = $1 AND id::TEXT = ANY($2)",
+            conference_id,
+            ids
+        )
+        .fetch_one(pool)
+        .await?,
+        ChangeEntity::Podcasts => sqlx::query_scalar!(
+            "SELECT MIN(updated_at) FROM podcast_channels WHERE id::TEXT = ANY($1)",
+            ids
+        )
+        .fetch_one(pool)
+        .await?,
+    };
+
+    Ok(changed_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::postgres::PgListener;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::models::event::ChangeAction;
+
+    fn event(ids: Vec<String>) -> ChangeEvent {
+        ChangeEvent {
+            conference_id: 1,
+            entity: ChangeEntity::Sessions,
+            action: ChangeAction::Updated,
+            ids,
+            at: 1_700_000_000_000,
+        }
+    }
+
+    async fn notified(pool: &PgPool, event: &ChangeEvent) -> ChangeEvent {
+        let mut listener = PgListener::connect_with(pool).await.unwrap();
+        listener.listen(CHANGES_CHANNEL).await.unwrap();
+
+        notify_change(pool, event).await.unwrap();
+
+        let notification = listener.recv().await.unwrap();
+        serde_json::from_str(notification.payload()).unwrap()
+    }
+
+    #[sqlx::test]
+    async fn listeners_receive_the_changed_ids(pool: PgPool) {
+        let ids = vec!["session-1".to_string(), "session-2".to_string()];
+
+        let received = notified(&pool, &event(ids.clone())).await;
+
+        assert_eq!(received.ids, ids);
+        assert_eq!(received.at, 1_700_000_000_000);
+    }
+
+    #[sqlx::test]
+    async fn too_many_ids_ask_listeners_to_resync_the_entity(pool: PgPool) {
+        let ids: Vec<String> = (0..500).map(|_| Uuid::new_v4().to_string()).collect();
+
+        let received = notified(&pool, &event(ids)).await;
+
+        assert!(received.ids.is_empty());
+        assert_eq!(received.conference_id, 1);
+        assert_eq!(received.entity, ChangeEntity::Sessions);
+        assert_eq!(received.action, ChangeAction::Updated);
+    }
+}
\ No newline at end of file
@@ -3,10 +3,11 @@ use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
 use crate::error::ServiceError;
-use crate::models::feedback::FeedbackInfo;
+use crate::models::feedback::{FeedbackEntry, FeedbackInfo, FeedbackSummaryPage, SessionFeedback};
 
 pub async fn set_feedback(
     pool: &PgPool,
+    conference_id: i32,
     user_id: &str,
     session_id: &str,
     feedback_value: &str,
@@ -14,15 +15,19 @@ pub async fn set_feedback(
 ) -> Result<bool, ServiceError> {
     let timestamp_str = timestamp.to_rfc3339();
     
+    // A second submission for the same session replaces the previous one
     let result = sqlx::query!(
         r#"
-        INSERT INTO feedback (uuid, sessionId, feedback, timestamp)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO feedback (uuid, sessionId, feedback, timestamp, conference_id)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (uuid, conference_id, sessionId) DO UPDATE
+        SET feedback = EXCLUDED.feedback, timestamp = EXCLUDED.timestamp, updated_at = NOW()
         "#,
         user_id,
         session_id,
         feedback_value,
-        timestamp_str
+        timestamp_str,
+        conference_id
     )
     .execute(pool)
     .await?;
@@ -30,12 +35,21 @@ pub async fn set_feedback(
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn get_feedback_summary(pool: &PgPool) -> Result<Vec<FeedbackInfo>, ServiceError> {
+pub async fn get_feedback(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: &str,
+) -> Result<Vec<FeedbackInfo>, ServiceError> {
     let feedback = sqlx::query!(
         r#"
-        SELECT sessionId, feedback
-        FROM feedback
-        "#
+        SELECT f.sessionId, f.feedback
+        FROM feedback f
+        JOIN conference_sessions cs ON cs.conference_id = f.conference_id AND cs.id = f.sessionId
+        WHERE f.uuid = $1 AND f.conference_id = $2
+        ORDER BY f.updated_at DESC
+        "#,
+        user_id,
+        conference_id
     )
     .fetch_all(pool)
     .await?;
@@ -47,4 +61,112 @@ pub async fn get_feedback_summary(pool: &PgPool) -> Result<Vec<FeedbackInfo>, Se
             value: row.feedback,
         })
         .collect())
+}
+
+pub async fn delete_feedback(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: &str,
+    session_id: &str,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM feedback
+        WHERE uuid = $1 AND sessionId = $2 AND conference_id = $3
+        "#,
+        user_id,
+        session_id,
+        conference_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_feedback_summary(
+    pool: &PgPool,
+    conference_id: i32,
+    page: i64,
+    page_size: i64,
+) -> Result<FeedbackSummaryPage, ServiceError> {
+    let offset = (page - 1)
+        .checked_mul(page_size)
+        .ok_or_else(|| ServiceError::BadRequest("Page is out of range".to_string()))?;
+
+    let total_sessions = sqlx::query!(
+        r#"
+        SELECT COUNT(DISTINCT f.sessionId) as count
+        FROM feedback f
+        JOIN conference_sessions cs ON cs.conference_id = f.conference_id AND cs.id = f.sessionId
+        WHERE f.conference_id = $1
+        "#,
+        conference_id
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0);
+
+    // Pick the sessions on this page first so a session's entries are never split across pages
+    let sessions = sqlx::query!(
+        r#"
+        SELECT f.sessionId as "session_id!", cs.title as "title?", COUNT(*) as "count!"
+        FROM feedback f
+        JOIN conference_sessions cs ON cs.conference_id = f.conference_id AND cs.id = f.sessionId
+        WHERE f.conference_id = $3
+        GROUP BY f.sessionId, cs.title
+        ORDER BY f.sessionId
+        LIMIT $1 OFFSET $2
+        "#,
+        page_size,
+        offset,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let session_ids: Vec<String> = sessions.iter().map(|row| row.session_id.clone()).collect();
+
+    let mut entries_by_session = std::collections::HashMap::<String, Vec<FeedbackEntry>>::new();
+    if !session_ids.is_empty() {
+        let entries = sqlx::query!(
+            r#"
+            SELECT sessionId, feedback, created_at, updated_at
+            FROM feedback
+            WHERE conference_id = $1 AND sessionId = ANY($2)
+            ORDER BY created_at
+            "#,
+            conference_id,
+            &session_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for row in entries {
+            entries_by_session
+                .entry(row.sessionid)
+                .or_default()
+                .push(FeedbackEntry {
+                    value: row.feedback,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                });
+        }
+    }
+
+    Ok(FeedbackSummaryPage {
+        page,
+        page_size,
+        total_sessions,
+        sessions: sessions
+            .into_iter()
+            .map(|row| SessionFeedback {
+                entries: entries_by_session.remove(&row.session_id).unwrap_or_default(),
+                session_id: row.session_id,
+                title: row.title,
+                count: row.count,
+            })
+            .collect(),
+    })
 }
\ No newline at end of file
@@ -0,0 +1,330 @@
+// src/db/feedback_forms.rs
+use std::collections::{BTreeMap, HashMap};
+
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::ServiceError;
+use crate::models::feedback_form::{
+    FeedbackAnswer, FeedbackForm, FeedbackFormRequest, FeedbackFormSummary, FeedbackQuestion,
+    QuestionSummary, QuestionType,
+};
+
+async fn get_questions_for_forms(
+    pool: &PgPool,
+    form_ids: &[i32],
+) -> Result<HashMap<i32, Vec<FeedbackQuestion>>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, form_id, prompt, question_type, scale_min, scale_max, options, required, sort
+        FROM feedback_questions
+        WHERE form_id = ANY($1)
+        ORDER BY sort, id
+        "#,
+        form_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut questions: HashMap<i32, Vec<FeedbackQuestion>> = HashMap::new();
+    for row in rows {
+        if let Some(question_type) = QuestionType::from_name(&row.question_type) {
+            questions.entry(row.form_id).or_default().push(FeedbackQuestion {
+                id: row.id,
+                prompt: row.prompt,
+                question_type,
+                scale_min: row.scale_min,
+                scale_max: row.scale_max,
+                options: row.options,
+                required: row.required,
+                sort: row.sort,
+            });
+        }
+    }
+
+    Ok(questions)
+}
+
+pub async fn get_all_forms(
+    pool: &PgPool,
+    conference_id: i32,
+) -> Result<Vec<FeedbackForm>, ServiceError> {
+    let forms = sqlx::query!(
+        r#"
+        SELECT id, title, session_id, category_id
+        FROM feedback_forms
+        WHERE conference_id = $1
+        ORDER BY id
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let form_ids: Vec<i32> = forms.iter().map(|row| row.id).collect();
+    let mut questions = get_questions_for_forms(pool, &form_ids).await?;
+
+    Ok(forms
+        .into_iter()
+        .map(|row| FeedbackForm {
+            questions: questions.remove(&row.id).unwrap_or_default(),
+            id: row.id,
+            title: row.title,
+            session_id: row.session_id,
+            category_id: row.category_id,
+        })
+        .collect())
+}
+
+pub async fn get_form_by_id(
+    pool: &PgPool,
+    conference_id: i32,
+    form_id: i32,
+) -> Result<Option<FeedbackForm>, ServiceError> {
+    let form = sqlx::query!(
+        r#"
+        SELECT id, title, session_id, category_id
+        FROM feedback_forms
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        form_id,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match form {
+        Some(row) => {
+            let mut questions = get_questions_for_forms(pool, &[row.id]).await?;
+            Ok(Some(FeedbackForm {
+                questions: questions.remove(&row.id).unwrap_or_default(),
+                id: row.id,
+                title: row.title,
+                session_id: row.session_id,
+                category_id: row.category_id,
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+// A session-specific form wins over a form for one of the session's categories,
+// which in turn wins over a global form without scope
+pub async fn get_form_for_session(
+    pool: &PgPool,
+    conference_id: i32,
+    session_id: &str,
+) -> Result<Option<FeedbackForm>, ServiceError> {
+    let form = sqlx::query!(
+        r#"
+        SELECT ff.id
+        FROM feedback_forms ff
+        WHERE ff.conference_id = $2
+          AND (ff.session_id = $1
+            OR ff.category_id IN (
+                SELECT category_item_id FROM session_categories
+                WHERE conference_id = $2 AND session_id = $1
+            )
+            OR (ff.session_id IS NULL AND ff.category_id IS NULL))
+        ORDER BY
+            CASE
+                WHEN ff.session_id IS NOT NULL THEN 0
+                WHEN ff.category_id IS NOT NULL THEN 1
+                ELSE 2
+            END,
+            ff.id DESC
+        LIMIT 1
+        "#,
+        session_id,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match form {
+        Some(row) => get_form_by_id(pool, conference_id, row.id).await,
+        None => Ok(None),
+    }
+}
+
+pub async fn add_form(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    form: &FeedbackFormRequest,
+) -> Result<i32, ServiceError> {
+    let form_row = sqlx::query!(
+        r#"
+        INSERT INTO feedback_forms (title, session_id, category_id, conference_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        form.title,
+        form.session_id,
+        form.category_id,
+        conference_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    for (index, question) in form.questions.iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO feedback_questions
+            (form_id, prompt, question_type, scale_min, scale_max, options, required, sort)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            form_row.id,
+            question.prompt,
+            question.question_type.name(),
+            question.scale_min,
+            question.scale_max,
+            &question.options,
+            question.required,
+            index as i32
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(form_row.id)
+}
+
+pub async fn delete_form(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    form_id: i32,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM feedback_forms
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        form_id,
+        conference_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn set_answers(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: &str,
+    session_id: &str,
+    answers: &[FeedbackAnswer],
+) -> Result<(), ServiceError> {
+    let mut tx = pool.begin().await?;
+
+    for answer in answers {
+        sqlx::query!(
+            r#"
+            INSERT INTO feedback_answers
+            (uuid, session_id, question_id, rating, choice, text_value, conference_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (uuid, conference_id, session_id, question_id) DO UPDATE
+            SET rating = EXCLUDED.rating, choice = EXCLUDED.choice,
+                text_value = EXCLUDED.text_value, updated_at = NOW()
+            "#,
+            user_id,
+            session_id,
+            answer.question_id,
+            answer.rating,
+            answer.choice,
+            answer.text,
+            conference_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn get_form_summary(
+    pool: &PgPool,
+    conference_id: i32,
+    form_id: i32,
+    session_id: Option<&str>,
+) -> Result<Option<FeedbackFormSummary>, ServiceError> {
+    let form = match get_form_by_id(pool, conference_id, form_id).await? {
+        Some(form) => form,
+        None => return Ok(None),
+    };
+
+    let answers = sqlx::query!(
+        r#"
+        SELECT fa.question_id, fa.rating, fa.choice, fa.text_value
+        FROM feedback_answers fa
+        JOIN feedback_questions fq ON fq.id = fa.question_id
+        WHERE fq.form_id = $1 AND ($2::VARCHAR IS NULL OR fa.session_id = $2)
+        ORDER BY fa.created_at
+        "#,
+        form_id,
+        session_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut answers_by_question: HashMap<i32, Vec<_>> = HashMap::new();
+    for row in answers {
+        answers_by_question.entry(row.question_id).or_default().push(row);
+    }
+
+    let questions = form
+        .questions
+        .into_iter()
+        .map(|question| {
+            let rows = answers_by_question.remove(&question.id).unwrap_or_default();
+            let mut distribution = BTreeMap::new();
+            let mut texts = Vec::new();
+            let mut rating_sum = 0i64;
+            let mut rating_count = 0i64;
+
+            for row in &rows {
+                match question.question_type {
+                    QuestionType::Rating => {
+                        if let Some(rating) = row.rating {
+                            rating_sum += rating as i64;
+                            rating_count += 1;
+                            *distribution.entry(rating.to_string()).or_insert(0) += 1;
+                        }
+                    }
+                    QuestionType::Choice => {
+                        if let Some(choice) = &row.choice {
+                            *distribution.entry(choice.clone()).or_insert(0) += 1;
+                        }
+                    }
+                    QuestionType::Text => {
+                        if let Some(text) = &row.text_value {
+                            texts.push(text.clone());
+                        }
+                    }
+                }
+            }
+
+            QuestionSummary {
+                question_id: question.id,
+                prompt: question.prompt,
+                question_type: question.question_type,
+                responses: rows.len() as i64,
+                average_rating: if rating_count > 0 {
+                    Some(rating_sum as f64 / rating_count as f64)
+                } else {
+                    None
+                },
+                distribution,
+                texts,
+            }
+        })
+        .collect();
+
+    Ok(Some(FeedbackFormSummary {
+        form_id: form.id,
+        title: form.title,
+        session_id: session_id.map(|id| id.to_string()),
+        questions,
+    }))
+}
\ No newline at end of file
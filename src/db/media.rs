@@ -0,0 +1,120 @@
+// src/db/media.rs
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::ServiceError;
+use crate::models::media::{MediaFormat, StoredAsset, StoredVariant};
+use crate::models::session::PhotoVariant;
+
+pub async fn get_asset(pool: &PgPool, asset_id: &str) -> Result<Option<StoredAsset>, ServiceError> {
+    let Some(asset) = sqlx::query!(
+        r#"
+        SELECT id, content_type, width, height, byte_size
+        FROM media_assets
+        WHERE id = $1
+        "#,
+        asset_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let variants = sqlx::query!(
+        r#"
+        SELECT variant, format, storage_key, width, height, byte_size
+        FROM media_variants
+        WHERE asset_id = $1
+        ORDER BY CASE variant WHEN 'original' THEN 0 WHEN 'square' THEN 1 ELSE 2 END, format
+        "#,
+        asset_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(StoredAsset {
+        id: asset.id,
+        content_type: asset.content_type,
+        width: asset.width,
+        height: asset.height,
+        byte_size: asset.byte_size,
+        variants: variants
+            .into_iter()
+            .filter_map(|row| {
+                Some(StoredVariant {
+                    variant: PhotoVariant::from_name(&row.variant)?,
+                    format: MediaFormat::from_name(&row.format)?,
+                    storage_key: row.storage_key,
+                    width: row.width,
+                    height: row.height,
+                    byte_size: row.byte_size,
+                })
+            })
+            .collect(),
+    }))
+}
+
+pub async fn get_storage_key(
+    pool: &PgPool,
+    asset_id: &str,
+    variant: PhotoVariant,
+    format: MediaFormat,
+) -> Result<Option<String>, ServiceError> {
+    let storage_key = sqlx::query_scalar!(
+        r#"
+        SELECT storage_key FROM media_variants
+        WHERE asset_id = $1 AND variant = $2 AND format = $3
+        "#,
+        asset_id,
+        variant.name(),
+        format.name()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(storage_key)
+}
+
+// Records an asset whose variants are already stored. Uploading the same file concurrently
+// stores identical variants under the same keys, so the second insert is simply dropped.
+// Returns whether the asset was new.
+pub async fn add_asset(conn: &mut PgConnection, asset: &StoredAsset) -> Result<bool, ServiceError> {
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO media_assets (id, content_type, width, height, byte_size)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+        asset.id,
+        asset.content_type,
+        asset.width,
+        asset.height,
+        asset.byte_size
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if inserted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    for variant in &asset.variants {
+        sqlx::query!(
+            r#"
+            INSERT INTO media_variants
+            (asset_id, variant, format, storage_key, width, height, byte_size)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            asset.id,
+            variant.variant.name(),
+            variant.format.name(),
+            variant.storage_key,
+            variant.width,
+            variant.height,
+            variant.byte_size
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(true)
+}
\ No newline at end of file
@@ -2,14 +2,27 @@ use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::time::Duration;
 
 pub mod users;
+pub mod clock;
+pub mod conferences;
+pub mod events;
+pub mod notifications;
 pub mod sessions;
 pub mod votes;
 pub mod feedback;
+pub mod feedback_forms;
 pub mod speakers;
 pub mod rooms;
 pub mod categories;
 pub mod podcast;
 pub mod sync;
+pub mod reports;
+pub mod versions;
+pub mod media;
+pub mod translations;
+pub mod audit;
+pub mod changesets;
+pub mod account;
+pub mod retention;
 
 pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
     PgPoolOptions::new()
@@ -0,0 +1,255 @@
+// src/db/notifications.rs
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+use crate::models::notification::{
+    DeviceToken, NotificationKind, OutboxMessage, Platform, SessionNotice,
+};
+
+pub async fn register_device(
+    pool: &PgPool,
+    user_id: &str,
+    token: &str,
+    platform: Platform,
+) -> Result<(), ServiceError> {
+    // A token moves to whoever registered it last, e.g. after reinstalling the app
+    sqlx::query!(
+        r#"
+        INSERT INTO device_tokens (token, uuid, platform)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (token) DO UPDATE
+        SET uuid = EXCLUDED.uuid, platform = EXCLUDED.platform, updated_at = NOW()
+        "#,
+        token,
+        user_id,
+        platform.name()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_device(pool: &PgPool, user_id: &str, token: &str) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM device_tokens
+        WHERE uuid = $1 AND token = $2
+        "#,
+        user_id,
+        token
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_token(pool: &PgPool, token: &str) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        DELETE FROM device_tokens
+        WHERE token = $1
+        "#,
+        token
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_device_tokens(pool: &PgPool, user_id: &str) -> Result<Vec<DeviceToken>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT token, platform
+        FROM device_tokens
+        WHERE uuid = $1
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Platform::from_name(&row.platform).map(|platform| DeviceToken {
+                token: row.token,
+                platform,
+            })
+        })
+        .collect())
+}
+
+pub async fn get_notifications_enabled(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<bool>, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT notifications_enabled
+        FROM users
+        WHERE uuid = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| row.notifications_enabled))
+}
+
+pub async fn set_notifications_enabled(
+    pool: &PgPool,
+    user_id: &str,
+    enabled: bool,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET notifications_enabled = $2, updated_at = NOW()
+        WHERE uuid = $1
+        "#,
+        user_id,
+        enabled
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Queues the notice for everyone who voted on the session and has not opted out.
+// Returns the number of new outbox rows, duplicates of an earlier notice are skipped.
+pub async fn enqueue_session_notice(
+    pool: &PgPool,
+    conference_id: i32,
+    notice: &SessionNotice,
+) -> Result<u64, ServiceError> {
+    let dedup_prefix = format!("{}:{}:{}:", notice.kind.name(), notice.session_id, notice.revision);
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO notification_outbox (conference_id, uuid, kind, session_id, title, body, dedup_key)
+        SELECT $1, v.uuid, $2, $3::VARCHAR, $4, $5, $6 || v.uuid
+        FROM votes v
+        JOIN users u ON u.uuid = v.uuid
+        WHERE v.conference_id = $1 AND v.sessionId = $3 AND u.notifications_enabled
+        ON CONFLICT (conference_id, dedup_key) DO NOTHING
+        "#,
+        conference_id,
+        notice.kind.name(),
+        notice.session_id,
+        notice.title,
+        notice.body,
+        dedup_prefix
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Claims due messages by pushing their next attempt out by the lease. A worker that dies
+// mid-delivery leaves them to be picked up again once the lease runs out.
+pub async fn claim_due_messages(
+    pool: &PgPool,
+    limit: i64,
+    lease_until: DateTime<Utc>,
+) -> Result<Vec<OutboxMessage>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        UPDATE notification_outbox
+        SET attempts = attempts + 1, next_attempt_at = $2, updated_at = NOW()
+        WHERE id IN (
+            SELECT id
+            FROM notification_outbox
+            WHERE status = 'pending' AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, uuid, kind, session_id, title, body, attempts
+        "#,
+        limit,
+        lease_until
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut messages = Vec::new();
+    for row in rows {
+        match NotificationKind::from_name(&row.kind) {
+            Some(kind) => messages.push(OutboxMessage {
+                id: row.id,
+                uuid: row.uuid,
+                kind,
+                session_id: row.session_id,
+                title: row.title,
+                body: row.body,
+                attempts: row.attempts,
+            }),
+            None => mark_failed(pool, row.id, &format!("Unknown kind '{}'", row.kind)).await?,
+        }
+    }
+
+    Ok(messages)
+}
+
+pub async fn mark_sent(pool: &PgPool, id: i64) -> Result<(), ServiceError> {
+    set_status(pool, id, "sent", None).await
+}
+
+pub async fn mark_skipped(pool: &PgPool, id: i64, reason: &str) -> Result<(), ServiceError> {
+    set_status(pool, id, "skipped", Some(reason)).await
+}
+
+pub async fn mark_failed(pool: &PgPool, id: i64, error: &str) -> Result<(), ServiceError> {
+    set_status(pool, id, "failed", Some(error)).await
+}
+
+pub async fn schedule_retry(
+    pool: &PgPool,
+    id: i64,
+    next_attempt_at: DateTime<Utc>,
+    error: &str,
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE notification_outbox
+        SET next_attempt_at = $2, last_error = $3, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        id,
+        next_attempt_at,
+        error
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_status(
+    pool: &PgPool,
+    id: i64,
+    status: &str,
+    error: Option<&str>,
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE notification_outbox
+        SET status = $2, last_error = COALESCE($3, last_error), updated_at = NOW()
+        WHERE id = $1
+        "#,
+        id,
+        status,
+        error
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 // src/db/podcast.rs
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::error::ServiceError;
 use crate::models::podcast::{ChannelFullData, EpisodeData, PodcastImportRequest};
@@ -29,12 +29,9 @@ pub async fn store_podcast_query(
 }
 
 pub async fn store_podcast_data(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     import_request: &PodcastImportRequest,
 ) -> Result<i32, ServiceError> {
-    // Start a transaction
-    let mut tx = pool.begin().await?;
-
     // 1. Insert the channel
     let last_build_date = match &import_request.channel.last_build_date {
         Some(date_str) => {
@@ -48,8 +45,9 @@ pub async fn store_podcast_data(
     let channel_row = sqlx::query!(
         r#"
         INSERT INTO podcast_channels 
-        (title, link, description, copyright, language, author, owner_email, owner_name, image_url, last_build_date)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        (title, link, description, copyright, language, author, owner_email, owner_name, image_url, last_build_date,
+         image_asset_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         RETURNING id
         "#,
         import_request.channel.title,
@@ -61,9 +59,10 @@ pub async fn store_podcast_data(
         import_request.channel.owner_email.clone().unwrap_or_default(),
         import_request.channel.owner_name.clone().unwrap_or_default(),
         import_request.channel.image_url.clone().unwrap_or_default(),
-        last_build_date
+        last_build_date,
+        import_request.channel.image_asset_id
     )
-    .fetch_one(&mut *tx)
+    .fetch_one(&mut *conn)
     .await?;
 
     let channel_id = channel_row.id;
@@ -78,7 +77,7 @@ pub async fn store_podcast_data(
             "#,
             category_name
         )
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut *conn)
         .await?;
 
         let category_id = match category_row {
@@ -93,7 +92,7 @@ pub async fn store_podcast_data(
                     "#,
                     category_name
                 )
-                .fetch_one(&mut *tx)
+                .fetch_one(&mut *conn)
                 .await?;
                 new_category.id
             }
@@ -109,7 +108,7 @@ pub async fn store_podcast_data(
             channel_id,
             category_id
         )
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await?;
     }
 
@@ -136,7 +135,7 @@ pub async fn store_podcast_data(
             episode.media_type.clone().unwrap_or_else(|| "audio/mpeg".to_string()),
             episode.media_length.unwrap_or(0)
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(&mut *conn)
         .await?;
 
         let episode_id = episode_row.id;
@@ -151,7 +150,7 @@ pub async fn store_podcast_data(
                 "#,
                 category_name
             )
-            .fetch_optional(&mut *tx)
+            .fetch_optional(&mut *conn)
             .await?;
 
             let category_id = match category_row {
@@ -166,7 +165,7 @@ pub async fn store_podcast_data(
                         "#,
                         category_name
                     )
-                    .fetch_one(&mut *tx)
+                    .fetch_one(&mut *conn)
                     .await?;
                     new_category.id
                 }
@@ -182,14 +181,11 @@ pub async fn store_podcast_data(
                 episode_id,
                 category_id
             )
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         }
     }
 
-    // Commit the transaction
-    tx.commit().await?;
-
     Ok(channel_id)
 }
 
@@ -239,7 +235,7 @@ pub async fn get_all_podcast_data(pool: &PgPool) -> Result<Vec<ChannelFullData>,
         r#"
         SELECT 
             id, title, link, description, copyright, language,
-            author, owner_email, owner_name, image_url, last_build_date
+            author, owner_email, owner_name, image_url, image_asset_id, last_build_date
         FROM podcast_channels
         ORDER BY id ASC
         "#
@@ -247,29 +243,26 @@ pub async fn get_all_podcast_data(pool: &PgPool) -> Result<Vec<ChannelFullData>,
     .fetch_all(pool)
     .await?;
 
-    let mut result = Vec::new();
-
-    // Process each channel
-    for channel in channels {
-        // Get episodes for this channel
-        let episodes = sqlx::query!(
-            r#"
-            SELECT 
-                id, guid, title, description, link, pub_date, 
-                duration, explicit, image_url, media_url, media_type, media_length
-            FROM podcast_episodes
-            WHERE channel_id = $1
-            ORDER BY pub_date DESC
-            "#,
-            channel.id
-        )
-        .fetch_all(pool)
-        .await?;
+    // Get the episodes of all channels at once, newest first within each channel
+    let episodes = sqlx::query!(
+        r#"
+        SELECT 
+            id, channel_id, guid, title, description, link, pub_date, 
+            duration, explicit, image_url, media_url, media_type, media_length
+        FROM podcast_episodes
+        ORDER BY channel_id, pub_date DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
 
-        // Map episodes
-        let episode_data = episodes
-            .into_iter()
-            .map(|ep| EpisodeData {
+    // Group episodes by channel_id
+    let mut episodes_map: std::collections::HashMap<i32, Vec<EpisodeData>> = std::collections::HashMap::new();
+    for ep in episodes {
+        episodes_map
+            .entry(ep.channel_id)
+            .or_default()
+            .push(EpisodeData {
                 id: Some(ep.id),
                 guid: ep.guid,
                 title: ep.title,
@@ -283,9 +276,13 @@ pub async fn get_all_podcast_data(pool: &PgPool) -> Result<Vec<ChannelFullData>,
                 media_type: Some(ep.media_type),
                 media_length: Some(ep.media_length),
                 episode_category: episode_categories_map.get(&ep.id).cloned().unwrap_or_default(),
-            })
-            .collect();
+            });
+    }
+
+    let mut result = Vec::new();
 
+    // Process each channel
+    for channel in channels {
         // Add channel with its episodes
         result.push(ChannelFullData {
             id: channel.id,
@@ -298,9 +295,10 @@ pub async fn get_all_podcast_data(pool: &PgPool) -> Result<Vec<ChannelFullData>,
             owner_email: Some(channel.owner_email),
             owner_name: Some(channel.owner_name),
             image_url: Some(channel.image_url),
+            image_asset_id: channel.image_asset_id,
             last_build_date: Some(channel.last_build_date.to_rfc3339()),
             categories: channel_categories_map.get(&channel.id).cloned().unwrap_or_default(),
-            episodes: episode_data,
+            episodes: episodes_map.remove(&channel.id).unwrap_or_default(),
         });
     }
 
@@ -0,0 +1,259 @@
+// src/db/reports.rs
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+use crate::models::report::{RankingDimension, RatingGroup, RatingReport, SessionRating};
+
+// Users who took part in the conference by voting, leaving feedback or answering a feedback
+// form. Users are shared by all conferences, counting them all would dilute participation rates.
+pub async fn get_signed_user_count(pool: &PgPool, conference_id: i32) -> Result<i64, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as count
+        FROM (
+            SELECT uuid FROM votes WHERE conference_id = $1
+            UNION
+            SELECT uuid FROM feedback WHERE conference_id = $1
+            UNION
+            SELECT uuid FROM feedback_answers WHERE conference_id = $1
+        ) participants
+        "#,
+        conference_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.count.unwrap_or(0))
+}
+
+pub async fn get_session_ratings(
+    pool: &PgPool,
+    conference_id: i32,
+) -> Result<RatingReport, ServiceError> {
+    let signed_users = get_signed_user_count(pool, conference_id).await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            cs.id as "id!", cs.title as "title!", cs.starts_at as "starts_at!",
+            cs.room_id, cr.name as "room_name?", cr.capacity as "room_capacity?",
+            COUNT(v.rating) FILTER (WHERE v.rating = 1) as "good!",
+            COUNT(v.rating) FILTER (WHERE v.rating = 0) as "ok!",
+            COUNT(v.rating) FILTER (WHERE v.rating = -1) as "bad!"
+        FROM conference_sessions cs
+        LEFT JOIN conference_rooms cr ON cs.room_id = cr.id
+        LEFT JOIN votes v ON v.conference_id = cs.conference_id AND v.sessionId = cs.id
+        WHERE cs.conference_id = $1
+        GROUP BY cs.conference_id, cs.id, cr.id
+        ORDER BY cs.starts_at, cs.id
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let sessions = rows
+        .into_iter()
+        .map(|row| {
+            let total = row.good + row.ok + row.bad;
+            SessionRating {
+                session_id: row.id,
+                title: row.title,
+                starts_at: row.starts_at,
+                room_id: row.room_id,
+                room_name: row.room_name,
+                room_capacity: row.room_capacity,
+                good: row.good,
+                ok: row.ok,
+                bad: row.bad,
+                total,
+                net_score: row.good - row.bad,
+                participation_rate: if signed_users > 0 {
+                    total as f64 / signed_users as f64
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+
+    Ok(RatingReport {
+        signed_users,
+        sessions,
+    })
+}
+
+pub async fn get_rankings(
+    pool: &PgPool,
+    conference_id: i32,
+    dimension: RankingDimension,
+) -> Result<Vec<RatingGroup>, ServiceError> {
+    // Every dimension yields the same shape: a group key and label plus summed vote counts
+    let rows = match dimension {
+        RankingDimension::Category => {
+            sqlx::query_as!(
+                RankingRow,
+                r#"
+                WITH session_votes AS (
+                    SELECT sessionId,
+                        COUNT(*) FILTER (WHERE rating = 1) as good,
+                        COUNT(*) FILTER (WHERE rating = 0) as ok,
+                        COUNT(*) FILTER (WHERE rating = -1) as bad
+                    FROM votes
+                    WHERE conference_id = $1
+                    GROUP BY sessionId
+                )
+                SELECT
+                    cc.id::TEXT as "key!", cc.title as "label!",
+                    COUNT(DISTINCT sc.session_id) as "sessions!",
+                    COALESCE(SUM(sv.good), 0)::BIGINT as "good!",
+                    COALESCE(SUM(sv.ok), 0)::BIGINT as "ok!",
+                    COALESCE(SUM(sv.bad), 0)::BIGINT as "bad!"
+                FROM conference_categories cc
+                JOIN session_categories sc ON sc.category_item_id = cc.id
+                LEFT JOIN session_votes sv ON sv.sessionId = sc.session_id
+                WHERE cc.conference_id = $1
+                GROUP BY cc.id
+                "#,
+                conference_id
+            )
+            .fetch_all(pool)
+            .await?
+        }
+        RankingDimension::Room => {
+            sqlx::query_as!(
+                RankingRow,
+                r#"
+                WITH session_votes AS (
+                    SELECT sessionId,
+                        COUNT(*) FILTER (WHERE rating = 1) as good,
+                        COUNT(*) FILTER (WHERE rating = 0) as ok,
+                        COUNT(*) FILTER (WHERE rating = -1) as bad
+                    FROM votes
+                    WHERE conference_id = $1
+                    GROUP BY sessionId
+                )
+                SELECT
+                    cr.id::TEXT as "key!", cr.name as "label!",
+                    COUNT(DISTINCT cs.id) as "sessions!",
+                    COALESCE(SUM(sv.good), 0)::BIGINT as "good!",
+                    COALESCE(SUM(sv.ok), 0)::BIGINT as "ok!",
+                    COALESCE(SUM(sv.bad), 0)::BIGINT as "bad!"
+                FROM conference_rooms cr
+                JOIN conference_sessions cs ON cs.room_id = cr.id
+                LEFT JOIN session_votes sv ON sv.sessionId = cs.id
+                WHERE cr.conference_id = $1
+                GROUP BY cr.id
+                "#,
+                conference_id
+            )
+            .fetch_all(pool)
+            .await?
+        }
+        RankingDimension::Speaker => {
+            sqlx::query_as!(
+                RankingRow,
+                r#"
+                WITH session_votes AS (
+                    SELECT sessionId,
+                        COUNT(*) FILTER (WHERE rating = 1) as good,
+                        COUNT(*) FILTER (WHERE rating = 0) as ok,
+                        COUNT(*) FILTER (WHERE rating = -1) as bad
+                    FROM votes
+                    WHERE conference_id = $1
+                    GROUP BY sessionId
+                )
+                SELECT
+                    sp.id as "key!", sp.first_name || ' ' || sp.last_name as "label!",
+                    COUNT(DISTINCT ss.session_id) as "sessions!",
+                    COALESCE(SUM(sv.good), 0)::BIGINT as "good!",
+                    COALESCE(SUM(sv.ok), 0)::BIGINT as "ok!",
+                    COALESCE(SUM(sv.bad), 0)::BIGINT as "bad!"
+                FROM conference_speakers sp
+                JOIN session_speakers ss ON ss.conference_id = sp.conference_id AND ss.speaker_id = sp.id
+                LEFT JOIN session_votes sv ON sv.sessionId = ss.session_id
+                WHERE sp.conference_id = $1
+                GROUP BY sp.conference_id, sp.id
+                "#,
+                conference_id
+            )
+            .fetch_all(pool)
+            .await?
+        }
+        RankingDimension::Day => {
+            sqlx::query_as!(
+                RankingRow,
+                r#"
+                WITH session_votes AS (
+                    SELECT sessionId,
+                        COUNT(*) FILTER (WHERE rating = 1) as good,
+                        COUNT(*) FILTER (WHERE rating = 0) as ok,
+                        COUNT(*) FILTER (WHERE rating = -1) as bad
+                    FROM votes
+                    WHERE conference_id = $1
+                    GROUP BY sessionId
+                )
+                -- Session times are conference wall-clock time stored as UTC, reading them in
+                -- UTC keeps the day independent of the database session time zone
+                SELECT
+                    to_char(cs.starts_at AT TIME ZONE 'UTC', 'YYYY-MM-DD') as "key!",
+                    to_char(cs.starts_at AT TIME ZONE 'UTC', 'FMDay, DD Mon YYYY') as "label!",
+                    COUNT(DISTINCT cs.id) as "sessions!",
+                    COALESCE(SUM(sv.good), 0)::BIGINT as "good!",
+                    COALESCE(SUM(sv.ok), 0)::BIGINT as "ok!",
+                    COALESCE(SUM(sv.bad), 0)::BIGINT as "bad!"
+                FROM conference_sessions cs
+                LEFT JOIN session_votes sv ON sv.sessionId = cs.id
+                WHERE cs.conference_id = $1
+                GROUP BY 1, 2
+                "#,
+                conference_id
+            )
+            .fetch_all(pool)
+            .await?
+        }
+    };
+
+    let mut groups: Vec<RatingGroup> = rows
+        .into_iter()
+        .map(|row| {
+            let total = row.good + row.ok + row.bad;
+            let net_score = row.good - row.bad;
+            RatingGroup {
+                key: row.key,
+                label: row.label,
+                sessions: row.sessions,
+                good: row.good,
+                ok: row.ok,
+                bad: row.bad,
+                total,
+                net_score,
+                average_score: if total > 0 {
+                    Some(net_score as f64 / total as f64)
+                } else {
+                    None
+                },
+            }
+        })
+        .collect();
+
+    // Best rated first, groups without votes at the bottom
+    groups.sort_by(|a, b| {
+        b.average_score
+            .unwrap_or(f64::MIN)
+            .total_cmp(&a.average_score.unwrap_or(f64::MIN))
+            .then(b.total.cmp(&a.total))
+            .then(a.key.cmp(&b.key))
+    });
+
+    Ok(groups)
+}
+
+struct RankingRow {
+    key: String,
+    label: String,
+    sessions: i64,
+    good: i64,
+    ok: i64,
+    bad: i64,
+}
\ No newline at end of file
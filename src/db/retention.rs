@@ -0,0 +1,231 @@
+// src/db/retention.rs
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgConnection, PgPool};
+
+use crate::db::account::{new_pseudonym, PSEUDONYM_PREFIX};
+use crate::error::ServiceError;
+use crate::models::retention::{ConferenceRetention, RetentionReport, RetentionRun};
+
+struct RunRow {
+    id: i32,
+    started_by: String,
+    dry_run: bool,
+    report: Option<Json<RetentionReport>>,
+    error: Option<String>,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+}
+
+impl From<RunRow> for RetentionRun {
+    fn from(row: RunRow) -> Self {
+        RetentionRun {
+            id: row.id,
+            started_by: row.started_by,
+            dry_run: row.dry_run,
+            report: row.report.map(|report| report.0),
+            error: row.error,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+        }
+    }
+}
+
+// Held until the transaction ends, so runs of several instances do not overlap
+pub async fn try_lock(conn: &mut PgConnection) -> Result<bool, ServiceError> {
+    let locked = sqlx::query_scalar!(r#"SELECT pg_try_advisory_xact_lock(hashtext('retention')) as "locked!""#)
+        .fetch_one(conn)
+        .await?;
+
+    Ok(locked)
+}
+
+// Conferences whose last session ended before the cutoff, with their end
+pub async fn get_ended_conferences(
+    conn: &mut PgConnection,
+    before: DateTime<Utc>,
+) -> Result<Vec<(i32, String, DateTime<Utc>)>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT c.id, c.slug, MAX(cs.ends_at) as "conference_end!"
+        FROM conferences c
+        JOIN conference_sessions cs ON cs.conference_id = c.id
+        GROUP BY c.id, c.slug
+        HAVING MAX(cs.ends_at) < $1
+        ORDER BY c.id
+        "#,
+        before
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.id, row.slug, row.conference_end))
+        .collect())
+}
+
+// Gives every user with votes or feedback on the conference's sessions a pseudonym of their
+// own that is used for this conference only. Rows that carry a pseudonym already are left alone.
+// The conference's notifications are deleted, they cannot be pseudonymised.
+pub async fn pseudonymise_conference(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    slug: String,
+    conference_end: DateTime<Utc>,
+) -> Result<ConferenceRetention, ServiceError> {
+    let user_ids = sqlx::query_scalar!(
+        r#"
+        SELECT uuid as "uuid!" FROM votes
+        WHERE conference_id = $1 AND uuid NOT LIKE $2 || '%'
+        UNION
+        SELECT uuid FROM feedback
+        WHERE conference_id = $1 AND uuid NOT LIKE $2 || '%'
+        UNION
+        SELECT uuid FROM feedback_answers
+        WHERE conference_id = $1 AND uuid NOT LIKE $2 || '%'
+        "#,
+        conference_id,
+        PSEUDONYM_PREFIX
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let pseudonyms: Vec<String> = user_ids.iter().map(|_| new_pseudonym()).collect();
+
+    let votes = sqlx::query!(
+        r#"
+        UPDATE votes v SET uuid = m.pseudonym
+        FROM UNNEST($2::VARCHAR[], $3::VARCHAR[]) AS m(uuid, pseudonym)
+        WHERE v.uuid = m.uuid AND v.conference_id = $1
+        "#,
+        conference_id,
+        &user_ids,
+        &pseudonyms
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let feedback = sqlx::query!(
+        r#"
+        UPDATE feedback f SET uuid = m.pseudonym
+        FROM UNNEST($2::VARCHAR[], $3::VARCHAR[]) AS m(uuid, pseudonym)
+        WHERE f.uuid = m.uuid AND f.conference_id = $1
+        "#,
+        conference_id,
+        &user_ids,
+        &pseudonyms
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let feedback_answers = sqlx::query!(
+        r#"
+        UPDATE feedback_answers fa SET uuid = m.pseudonym
+        FROM UNNEST($2::VARCHAR[], $3::VARCHAR[]) AS m(uuid, pseudonym)
+        WHERE fa.uuid = m.uuid AND fa.conference_id = $1
+        "#,
+        conference_id,
+        &user_ids,
+        &pseudonyms
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Notices name the user and the session they voted for in their recipient, session and
+    // dedup key. Nothing is sent for a conference that is long over, so they go entirely.
+    let notifications = sqlx::query!(
+        "DELETE FROM notification_outbox WHERE conference_id = $1",
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(ConferenceRetention {
+        conference_id,
+        slug,
+        conference_end,
+        users: user_ids.len() as u64,
+        votes: votes.rows_affected(),
+        feedback: feedback.rows_affected(),
+        feedback_answers: feedback_answers.rows_affected(),
+        notifications: notifications.rows_affected(),
+    })
+}
+
+// Users that registered before the cutoff and left nothing behind. Test users stay, they are
+// assigned by organisers.
+pub async fn purge_stale_users(conn: &mut PgConnection, before: DateTime<Utc>) -> Result<u64, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM users u
+        WHERE u.created_at < $1
+            AND NOT EXISTS (SELECT 1 FROM votes v WHERE v.uuid = u.uuid)
+            AND NOT EXISTS (SELECT 1 FROM feedback f WHERE f.uuid = u.uuid)
+            AND NOT EXISTS (SELECT 1 FROM feedback_answers fa WHERE fa.uuid = u.uuid)
+            AND NOT EXISTS (SELECT 1 FROM device_tokens d WHERE d.uuid = u.uuid)
+            AND NOT EXISTS (SELECT 1 FROM test_user_cohorts c WHERE c.uuid = u.uuid)
+        "#,
+        before
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn delete_podcast_requests(conn: &mut PgConnection, before: DateTime<Utc>) -> Result<u64, ServiceError> {
+    let result = sqlx::query!(
+        "DELETE FROM podcast_request_table WHERE created_at < $1",
+        before
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn add_run(
+    pool: &PgPool,
+    started_by: &str,
+    dry_run: bool,
+    started_at: DateTime<Utc>,
+    report: Option<&RetentionReport>,
+    error: Option<&str>,
+) -> Result<RetentionRun, ServiceError> {
+    let row = sqlx::query_as!(
+        RunRow,
+        r#"
+        INSERT INTO retention_runs (started_by, dry_run, report, error, started_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, started_by, dry_run, report as "report: Json<RetentionReport>", error,
+            started_at, finished_at
+        "#,
+        started_by,
+        dry_run,
+        report.map(Json) as _,
+        error,
+        started_at
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.into())
+}
+
+// Newest first
+pub async fn get_runs(pool: &PgPool, limit: i64) -> Result<Vec<RetentionRun>, ServiceError> {
+    let rows = sqlx::query_as!(
+        RunRow,
+        r#"
+        SELECT id, started_by, dry_run, report as "report: Json<RetentionReport>", error,
+            started_at, finished_at
+        FROM retention_runs
+        ORDER BY id DESC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(RetentionRun::from).collect())
+}
\ No newline at end of file
@@ -1,15 +1,17 @@
 // src/db/rooms.rs
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::error::ServiceError;
 use crate::models::room::{ConferenceRoomRequest, RoomTable};
 
-pub async fn get_all_rooms(pool: &PgPool) -> Result<Vec<RoomTable>, ServiceError> {
+pub async fn get_all_rooms(pool: &PgPool, conference_id: i32) -> Result<Vec<RoomTable>, ServiceError> {
     let rooms = sqlx::query!(
         r#"
-        SELECT id, name, sort
+        SELECT id, name, sort, capacity, floor, building, accessibility_notes, map_x, map_y
         FROM conference_rooms
-        "#
+        WHERE conference_id = $1 AND deleted_at IS NULL
+        "#,
+        conference_id
     )
     .fetch_all(pool)
     .await?;
@@ -20,25 +22,112 @@ pub async fn get_all_rooms(pool: &PgPool) -> Result<Vec<RoomTable>, ServiceError
             id: Some(row.id as i64),
             name: row.name,
             sort: row.sort,
+            capacity: row.capacity,
+            floor: row.floor,
+            building: row.building,
+            accessibility_notes: row.accessibility_notes,
+            map_x: row.map_x,
+            map_y: row.map_y,
+            deleted_at: None,
         })
         .collect())
 }
 
 pub async fn add_room(
-    pool: &PgPool,
+    conn: &mut PgConnection,
+    conference_id: i32,
     room: &ConferenceRoomRequest,
 ) -> Result<i32, ServiceError> {
     let row = sqlx::query!(
         r#"
-        INSERT INTO conference_rooms (name, sort)
-        VALUES ($1, $2)
+        INSERT INTO conference_rooms
+        (name, sort, capacity, floor, building, accessibility_notes, map_x, map_y, conference_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING id
         "#,
         room.name,
-        room.sort
+        room.sort,
+        room.capacity,
+        room.floor,
+        room.building,
+        room.accessibility_notes,
+        room.map_x,
+        room.map_y,
+        conference_id
     )
-    .fetch_one(pool)
+    .fetch_one(conn)
     .await?;
 
     Ok(row.id)
+}
+
+pub async fn update_room(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    room_id: i32,
+    room: &ConferenceRoomRequest,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_rooms
+        SET name = $2, sort = $3, capacity = $4, floor = $5, building = $6,
+            accessibility_notes = $7, map_x = $8, map_y = $9, updated_at = NOW()
+        WHERE id = $1 AND conference_id = $10 AND deleted_at IS NULL
+        "#,
+        room_id,
+        room.name,
+        room.sort,
+        room.capacity,
+        room.floor,
+        room.building,
+        room.accessibility_notes,
+        room.map_x,
+        room.map_y,
+        conference_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Returns the id of every session that was held in the room, None if there was no such room
+pub async fn delete_room(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    room_id: i32,
+) -> Result<Option<Vec<String>>, ServiceError> {
+
+    // Detach sessions from the room and bump them so /sync/sessions picks up the change
+    let detached = sqlx::query_scalar!(
+        r#"
+        UPDATE conference_sessions
+        SET room_id = NULL, updated_at = NOW()
+        WHERE room_id = $1 AND conference_id = $2
+        RETURNING id
+        "#,
+        room_id,
+        conference_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // The row stays as a tombstone for /sync/rooms
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_rooms
+        SET deleted_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+        "#,
+        room_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(detached))
 }
\ No newline at end of file
@@ -1,93 +1,128 @@
 // src/db/sessions.rs
-use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgConnection, PgPool};
 use uuid::Uuid;
 
+use crate::db::categories::check_single_choice;
 use crate::error::ServiceError;
-use crate::models::conference::{Conference, Session, Speaker};
-use crate::models::session::{ConferenceSessionRequest, SessionInfo};
+use crate::models::conference::{
+    CategoryGroup, CategoryItem, Conference, Room, Session, Speaker,
+};
+use crate::models::session::{
+    ConferenceSessionRequest, InvalidReferences, SessionInfo, SessionStatus, SessionWindows,
+    SessionPlacement, SpeakerLink, SpeakerPhoto, UpcomingSession,
+};
+
+// Session row with its speaker and category ids aggregated in the same query
+pub(crate) struct SessionInfoRow {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub room_id: Option<i32>,
+    pub is_service_session: Option<bool>,
+    pub is_plenum_session: Option<bool>,
+    pub status: String,
+    pub speaker_ids: Vec<String>,
+    pub category_ids: Vec<i32>,
+}
+
+impl From<SessionInfoRow> for SessionInfo {
+    fn from(row: SessionInfoRow) -> Self {
+        SessionInfo {
+            id: row.id,
+            title: row.title,
+            description: row.description,
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+            room_id: row.room_id,
+            is_service_session: row.is_service_session.unwrap_or(false),
+            is_plenum_session: row.is_plenum_session.unwrap_or(false),
+            status: SessionStatus::from_name(&row.status).unwrap_or(SessionStatus::Draft),
+            speaker_ids: row.speaker_ids,
+            category_ids: row.category_ids,
+        }
+    }
+}
 
-pub async fn get_conference_data(pool: &PgPool) -> Result<Conference, ServiceError> {
-    // Query sessions
-    let sessions = sqlx::query!(
+// `locale` selects translations, None serves the content as stored
+// `preview` includes the sessions attendees cannot see yet
+pub async fn get_conference_data(
+    pool: &PgPool,
+    conference_id: i32,
+    locale: Option<&str>,
+    preview: bool,
+) -> Result<Conference, ServiceError> {
+    // Speakers and tags are aggregated per session so the whole programme takes one query
+    let result_sessions = sqlx::query!(
         r#"
         SELECT 
-            cs.id, cs.title, cs.description, cs.starts_at, cs.ends_at,
-            cr.name as room_name
+            cs.id as "id!", COALESCE(st.title, cs.title) as "title!",
+            COALESCE(st.description, cs.description) as description,
+            cs.starts_at as "starts_at!", cs.ends_at as "ends_at!",
+            cs.room_id, cr.name as "room_name?", cs.status,
+            COALESCE(sp.speaker_ids, '{}') as "speaker_ids!",
+            COALESCE(tg.titles, '{}') as "tags!",
+            COALESCE(tg.category_ids, '{}') as "category_ids!"
         FROM conference_sessions cs
         LEFT JOIN conference_rooms cr ON cs.room_id = cr.id
-        "#
-    )
-    .fetch_all(pool)
-    .await?;
-    
-    // Map sessions and fetch related data
-    let mut result_sessions = Vec::new();
-    for session_row in sessions {
-        // Get speaker IDs for this session
-        let speaker_ids = sqlx::query!(
-            r#"
-            SELECT speaker_id
-            FROM session_speakers
-            WHERE session_id = $1
-            "#,
-            session_row.id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|row| row.speaker_id)
-        .collect::<Vec<String>>();
-        
-        // Get tags (categories) for this session
-        let tags = match sqlx::query!(
-            r#"
-            SELECT cc.title
+        LEFT JOIN session_translations st
+            ON st.conference_id = cs.conference_id AND st.session_id = cs.id AND st.locale = $2
+        LEFT JOIN LATERAL (
+            SELECT array_agg(ss.speaker_id) AS speaker_ids
+            FROM session_speakers ss
+            WHERE ss.conference_id = cs.conference_id AND ss.session_id = cs.id
+        ) sp ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT array_agg(COALESCE(ct.title, cc.title)) AS titles,
+                array_agg(cc.id) AS category_ids
             FROM session_categories sc
             JOIN conference_categories cc ON sc.category_item_id = cc.id
-            WHERE sc.session_id = $1
-            "#,
-            session_row.id
-        )
-        .fetch_all(pool)
-        .await {
-            Ok(results) => results,
-            Err(e) => {
-                log::error!("Error fetching tags for session {}: {}", session_row.id, e);
-                Vec::new() // Return empty vector on error
-            }
-        };
-        
-        // Debug log the category data
-        log::info!("Session {}: Found {} categories", session_row.id, tags.len());
-        if !tags.is_empty() {
-            log::info!("First category title: {:?}", tags.first().map(|t| &t.title));
-        }
-        
-        let tag_titles = tags
-            .into_iter()
-            .map(|row| row.title)
-            .collect::<Vec<String>>();
-        
-        // Create session object with optional tags that may be null instead of empty
-        result_sessions.push(Session {
-            id: session_row.id,
-            title: session_row.title,
-            description: session_row.description.unwrap_or_default(),
-            speaker_ids,
-            location: session_row.room_name.unwrap_or_else(|| "Unknown Room".to_string()),
-            starts_at: session_row.starts_at,
-            ends_at: session_row.ends_at,
-            tags: tag_titles,
-        });
-    }
+            LEFT JOIN category_translations ct ON ct.category_id = cc.id AND ct.locale = $2
+            WHERE sc.conference_id = cs.conference_id AND sc.session_id = cs.id
+        ) tg ON TRUE
+        WHERE cs.conference_id = $1 AND cs.status = ANY($3)
+        "#,
+        conference_id,
+        locale,
+        &SessionStatus::visible_names(preview)
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| Session {
+        id: row.id,
+        title: row.title,
+        description: row.description.unwrap_or_default(),
+        speaker_ids: row.speaker_ids,
+        location: row.room_name.unwrap_or_else(|| "Unknown Room".to_string()),
+        room_id: row.room_id,
+        starts_at: row.starts_at,
+        ends_at: row.ends_at,
+        tags: row.tags,
+        category_ids: row.category_ids,
+        status: SessionStatus::from_name(&row.status).unwrap_or(SessionStatus::Draft),
+    })
+    .collect();
     
     // Query speakers
     let speakers = sqlx::query!(
         r#"
         SELECT 
-            id, first_name, last_name, bio, tag_line, profile_picture
-        FROM conference_speakers
-        "#
+            cs.id, cs.first_name, cs.last_name,
+            COALESCE(st.bio, cs.bio) as bio, COALESCE(st.tag_line, cs.tag_line) as tag_line,
+            cs.profile_picture, cs.company, cs.job_title, cs.pronouns, cs.country,
+            cs.links as "links: Json<Vec<SpeakerLink>>", cs.photos as "photos: Json<Vec<SpeakerPhoto>>"
+        FROM conference_speakers cs
+        LEFT JOIN speaker_translations st
+            ON st.conference_id = cs.conference_id AND st.speaker_id = cs.id AND st.locale = $2
+        WHERE cs.conference_id = $1 AND cs.deleted_at IS NULL
+        "#,
+        conference_id,
+        locale
     )
     .fetch_all(pool)
     .await?
@@ -98,29 +133,163 @@ pub async fn get_conference_data(pool: &PgPool) -> Result<Conference, ServiceErr
         position: row.tag_line.unwrap_or_default(),
         description: row.bio.unwrap_or_default(),
         photo_url: row.profile_picture.unwrap_or_default(),
+        company: row.company,
+        job_title: row.job_title,
+        pronouns: row.pronouns,
+        country: row.country,
+        links: row.links.0,
+        photos: row.photos.0,
     })
     .collect();
     
+    // Query rooms
+    let rooms = sqlx::query!(
+        r#"
+        SELECT 
+            id, name, capacity, floor, building, accessibility_notes, map_x, map_y
+        FROM conference_rooms
+        WHERE conference_id = $1 AND deleted_at IS NULL
+        ORDER BY sort NULLS LAST, id
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| Room {
+        id: row.id,
+        name: row.name,
+        capacity: row.capacity,
+        floor: row.floor,
+        building: row.building,
+        accessibility_notes: row.accessibility_notes,
+        map_x: row.map_x,
+        map_y: row.map_y,
+    })
+    .collect();
+    
+    let categories = get_category_groups(pool, conference_id, locale).await?;
+
     Ok(Conference {
         sessions: result_sessions,
         speakers,
+        rooms,
+        categories,
     })
 }
 
-pub async fn add_session(
+// One group per category type in type order, followed by the categories without a type
+async fn get_category_groups(
     pool: &PgPool,
+    conference_id: i32,
+    locale: Option<&str>,
+) -> Result<Vec<CategoryGroup>, ServiceError> {
+    let types = sqlx::query!(
+        r#"
+        SELECT id, name, multiple
+        FROM category_types
+        WHERE conference_id = $1
+        ORDER BY sort NULLS LAST, id
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let categories = sqlx::query!(
+        r#"
+        SELECT cc.id, COALESCE(tr.title, cc.title) as "title!", cc.type_id, cc.parent_id
+        FROM conference_categories cc
+        LEFT JOIN category_translations tr ON tr.category_id = cc.id AND tr.locale = $2
+        WHERE cc.conference_id = $1
+        ORDER BY cc.sort NULLS LAST, cc.id
+        "#,
+        conference_id,
+        locale
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let ids: HashSet<i32> = categories.iter().map(|row| row.id).collect();
+    let mut children: HashMap<Option<i32>, Vec<(i32, String)>> = HashMap::new();
+    let mut roots: HashMap<Option<i32>, Vec<(i32, String)>> = HashMap::new();
+    for row in categories {
+        match row.parent_id.filter(|parent_id| ids.contains(parent_id)) {
+            Some(parent_id) => children.entry(Some(parent_id)).or_default(),
+            None => roots.entry(row.type_id).or_default(),
+        }
+        .push((row.id, row.title));
+    }
+
+    let mut groups: Vec<CategoryGroup> = types
+        .into_iter()
+        .map(|row| CategoryGroup {
+            items: category_tree(roots.remove(&Some(row.id)).unwrap_or_default(), &mut children),
+            id: Some(row.id),
+            name: Some(row.name),
+            multiple: row.multiple,
+        })
+        .collect();
+
+    if let Some(untyped) = roots.remove(&None) {
+        groups.push(CategoryGroup {
+            id: None,
+            name: None,
+            multiple: true,
+            items: category_tree(untyped, &mut children),
+        });
+    }
+
+    Ok(groups)
+}
+
+fn category_tree(
+    items: Vec<(i32, String)>,
+    children: &mut HashMap<Option<i32>, Vec<(i32, String)>>,
+) -> Vec<CategoryItem> {
+    items
+        .into_iter()
+        .map(|(id, title)| {
+            let nested = children.remove(&Some(id)).unwrap_or_default();
+            CategoryItem {
+                id,
+                title,
+                children: category_tree(nested, children),
+            }
+        })
+        .collect()
+}
+
+pub async fn add_session(
+    conn: &mut PgConnection,
+    conference_id: i32,
     session: &ConferenceSessionRequest,
 ) -> Result<String, ServiceError> {
+    // Generate a new UUID for the session
+    let generated_id = Uuid::new_v4().to_string();
+    add_session_in(conn, conference_id, &generated_id, session).await?;
+
+    Ok(generated_id)
+}
+
+// Like add_session, with an id chosen by the caller
+pub(crate) async fn add_session_in(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    session: &ConferenceSessionRequest,
+) -> Result<(), ServiceError> {
     // Check for duplicate session
     let duplicate = sqlx::query!(
         r#"
         SELECT id FROM conference_sessions 
-        WHERE title = $1 AND description = $2
+        WHERE title = $1 AND description = $2 AND conference_id = $3
         "#,
         session.title,
-        session.description
+        session.description,
+        conference_id
     )
-    .fetch_optional(pool)
+    .fetch_optional(&mut *conn)
     .await?;
 
     if duplicate.is_some() {
@@ -129,17 +298,50 @@ pub async fn add_session(
         ));
     }
 
-    // Generate a new UUID for the session
-    let generated_id = Uuid::new_v4().to_string();
+    if !session.status.is_initial() {
+        return Err(ServiceError::BadRequest(format!(
+            "A new session cannot be {}",
+            session.status.name()
+        )));
+    }
+
+    let speaker_ids = unique(&session.speaker_ids);
+    let category_ids = unique(&session.category_ids);
+
+    let room_known = match session.room_id {
+        Some(room_id) => sqlx::query_scalar!(
+            r#"
+            SELECT id FROM conference_rooms
+            WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+            FOR KEY SHARE
+            "#,
+            room_id,
+            conference_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .is_some(),
+        None => true,
+    };
+
+    let invalid = InvalidReferences {
+        speaker_ids: unknown_speakers(conn, conference_id, &speaker_ids).await?,
+        category_ids: unknown_categories(conn, conference_id, &category_ids).await?,
+        room_id: session.room_id.filter(|_| !room_known),
+    };
+    if !invalid.is_empty() {
+        return Err(ServiceError::InvalidReferences(invalid));
+    }
 
     // Insert the session
     sqlx::query!(
         r#"
         INSERT INTO conference_sessions 
-        (id, title, description, starts_at, ends_at, room_id, is_service_session, is_plenum_session, status)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        (id, title, description, starts_at, ends_at, room_id, is_service_session, is_plenum_session, status,
+         conference_id, published_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, CASE WHEN $11 THEN NOW() END)
         "#,
-        generated_id,
+        session_id,
         session.title,
         session.description,
         session.starts_at,
@@ -147,241 +349,891 @@ pub async fn add_session(
         session.room_id,
         session.is_service_session,
         session.is_plenum_session,
-        session.status
+        session.status.name(),
+        conference_id,
+        session.status.is_public()
     )
-    .execute(pool)
+    .execute(&mut *conn)
     .await?;
 
     // Insert speaker associations
-    for speaker_id in &session.speaker_ids {
-        sqlx::query!(
-            r#"
-            INSERT INTO session_speakers (session_id, speaker_id)
-            VALUES ($1, $2)
-            "#,
-            generated_id,
-            speaker_id
-        )
-        .execute(pool)
-        .await?;
-    }
+    sqlx::query!(
+        r#"
+        INSERT INTO session_speakers (conference_id, session_id, speaker_id)
+        SELECT $1, $2, UNNEST($3::VARCHAR[])
+        "#,
+        conference_id,
+        session_id,
+        &speaker_ids
+    )
+    .execute(&mut *conn)
+    .await?;
 
     // Insert category associations
-    for category_id in &session.category_ids {
-        sqlx::query!(
-            r#"
-            INSERT INTO session_categories (session_id, category_item_id)
-            VALUES ($1, $2)
-            "#,
-            generated_id,
-            category_id
-        )
-        .execute(pool)
-        .await?;
-    }
+    sqlx::query!(
+        r#"
+        INSERT INTO session_categories (conference_id, session_id, category_item_id)
+        SELECT $1, $2, UNNEST($3::INTEGER[])
+        "#,
+        conference_id,
+        session_id,
+        &category_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+    check_single_choice(conn, conference_id, Some(session_id)).await
+}
 
-    Ok(generated_id)
+// Returns the ids that are not speakers of the conference. The others are locked so they
+// cannot be deleted before the caller has linked them.
+async fn unknown_speakers(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_ids: &[String],
+) -> Result<Vec<String>, ServiceError> {
+    let known = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM conference_speakers
+        WHERE id = ANY($1) AND conference_id = $2 AND deleted_at IS NULL
+        FOR KEY SHARE
+        "#,
+        speaker_ids,
+        conference_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(speaker_ids.iter().filter(|id| !known.contains(id)).cloned().collect())
+}
+
+// Returns the ids that are not categories of the conference, locking the others
+async fn unknown_categories(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_ids: &[i32],
+) -> Result<Vec<i32>, ServiceError> {
+    let known = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM conference_categories
+        WHERE id = ANY($1) AND conference_id = $2
+        FOR KEY SHARE
+        "#,
+        category_ids,
+        conference_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(category_ids.iter().filter(|id| !known.contains(id)).copied().collect())
 }
 
+// Drops repeated ids, keeping the first occurrence
+fn unique<T: Clone + PartialEq>(ids: &[T]) -> Vec<T> {
+    let mut result: Vec<T> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !result.contains(id) {
+            result.push(id.clone());
+        }
+    }
+    result
+}
+
+// Without preview only sessions attendees can see are found
 pub async fn get_session_by_id(
     pool: &PgPool,
+    conference_id: i32,
     session_id: &str,
+    preview: bool,
 ) -> Result<Option<SessionInfo>, ServiceError> {
-    let session = sqlx::query!(
+    let session = sqlx::query_as!(
+        SessionInfoRow,
         r#"
         SELECT 
-            id, title, description, starts_at, ends_at, room_id, 
-            is_service_session, is_plenum_session, status
-        FROM conference_sessions
-        WHERE id = $1
+            cs.id, cs.title, cs.description, cs.starts_at, cs.ends_at, cs.room_id, 
+            cs.is_service_session, cs.is_plenum_session, cs.status,
+            COALESCE(sp.speaker_ids, '{}') as "speaker_ids!",
+            COALESCE(sc.category_ids, '{}') as "category_ids!"
+        FROM conference_sessions cs
+        LEFT JOIN LATERAL (
+            SELECT array_agg(speaker_id) AS speaker_ids
+            FROM session_speakers
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sp ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT array_agg(category_item_id) AS category_ids
+            FROM session_categories
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sc ON TRUE
+        WHERE cs.id = $1 AND cs.conference_id = $2 AND cs.status = ANY($3)
         "#,
-        session_id
+        session_id,
+        conference_id,
+        &SessionStatus::visible_names(preview)
     )
     .fetch_optional(pool)
     .await?;
 
-    if let Some(row) = session {
-        // Get speaker IDs for this session
-        let speaker_ids = sqlx::query!(
-            r#"
-            SELECT speaker_id
-            FROM session_speakers
-            WHERE session_id = $1
-            "#,
-            session_id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.speaker_id)
-        .collect();
-
-        // Get category IDs for this session
-        let category_ids = sqlx::query!(
-            r#"
-            SELECT category_item_id
-            FROM session_categories
-            WHERE session_id = $1
-            "#,
-            session_id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.category_item_id)
-        .collect();
-
-        Ok(Some(SessionInfo {
-            id: row.id,
-            title: row.title,
-            description: row.description,
-            starts_at: row.starts_at,
-            ends_at: row.ends_at,
-            room_id: row.room_id,
-            is_service_session: row.is_service_session.unwrap_or(false),
-            is_plenum_session: row.is_plenum_session.unwrap_or(false),
-            status: row.status.unwrap_or_else(|| "draft".to_string()),
-            speaker_ids,
-            category_ids,
-        }))
-    } else {
-        Ok(None)
-    }
+    Ok(session.map(SessionInfo::from))
 }
 
-pub async fn get_all_sessions(pool: &PgPool) -> Result<Vec<SessionInfo>, ServiceError> {
-    let sessions = sqlx::query!(
+pub async fn get_all_sessions(
+    pool: &PgPool,
+    conference_id: i32,
+    locale: Option<&str>,
+    preview: bool,
+) -> Result<Vec<SessionInfo>, ServiceError> {
+    let sessions = sqlx::query_as!(
+        SessionInfoRow,
         r#"
         SELECT 
-            id, title, description, starts_at, ends_at, room_id, 
-            is_service_session, is_plenum_session, status
-        FROM conference_sessions
-        "#
+            cs.id, COALESCE(st.title, cs.title) as "title!",
+            COALESCE(st.description, cs.description) as description,
+            cs.starts_at, cs.ends_at, cs.room_id, 
+            cs.is_service_session, cs.is_plenum_session, cs.status,
+            COALESCE(sp.speaker_ids, '{}') as "speaker_ids!",
+            COALESCE(sc.category_ids, '{}') as "category_ids!"
+        FROM conference_sessions cs
+        LEFT JOIN session_translations st
+            ON st.conference_id = cs.conference_id AND st.session_id = cs.id AND st.locale = $2
+        LEFT JOIN LATERAL (
+            SELECT array_agg(speaker_id) AS speaker_ids
+            FROM session_speakers
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sp ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT array_agg(category_item_id) AS category_ids
+            FROM session_categories
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sc ON TRUE
+        WHERE cs.conference_id = $1 AND cs.status = ANY($3)
+        "#,
+        conference_id,
+        locale,
+        &SessionStatus::visible_names(preview)
     )
     .fetch_all(pool)
     .await?;
 
-    let mut result = Vec::new();
+    Ok(sessions.into_iter().map(SessionInfo::from).collect())
+}
 
-    for row in sessions {
-        // Get speaker IDs
-        let speaker_ids = sqlx::query!(
-            r#"
-            SELECT speaker_id
+pub async fn get_sessions_for_speaker(
+    pool: &PgPool,
+    conference_id: i32,
+    speaker_id: &str,
+) -> Result<Vec<SessionInfo>, ServiceError> {
+    let sessions = sqlx::query_as!(
+        SessionInfoRow,
+        r#"
+        SELECT 
+            cs.id, cs.title, cs.description, cs.starts_at, cs.ends_at, cs.room_id, 
+            cs.is_service_session, cs.is_plenum_session, cs.status,
+            COALESCE(sp.speaker_ids, '{}') as "speaker_ids!",
+            COALESCE(sc.category_ids, '{}') as "category_ids!"
+        FROM conference_sessions cs
+        LEFT JOIN LATERAL (
+            SELECT array_agg(speaker_id) AS speaker_ids
             FROM session_speakers
-            WHERE session_id = $1
-            "#,
-            row.id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.speaker_id)
-        .collect();
-
-        // Get category IDs
-        let category_ids = sqlx::query!(
-            r#"
-            SELECT category_item_id
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sp ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT array_agg(category_item_id) AS category_ids
             FROM session_categories
-            WHERE session_id = $1
-            "#,
-            row.id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.category_item_id)
-        .collect();
-
-        result.push(SessionInfo {
-            id: row.id,
-            title: row.title,
-            description: row.description,
-            starts_at: row.starts_at,
-            ends_at: row.ends_at,
-            room_id: row.room_id,
-            is_service_session: row.is_service_session.unwrap_or(false),
-            is_plenum_session: row.is_plenum_session.unwrap_or(false),
-            status: row.status.unwrap_or_else(|| "draft".to_string()),
-            speaker_ids,
-            category_ids,
-        });
-    }
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sc ON TRUE
+        WHERE cs.conference_id = $1 AND cs.status = ANY($3)
+            AND EXISTS (
+                SELECT 1 FROM session_speakers
+                WHERE conference_id = cs.conference_id AND session_id = cs.id AND speaker_id = $2
+            )
+        ORDER BY cs.starts_at, cs.id
+        "#,
+        conference_id,
+        speaker_id,
+        &SessionStatus::visible_names(false)
+    )
+    .fetch_all(pool)
+    .await?;
 
-    Ok(result)
+    Ok(sessions.into_iter().map(SessionInfo::from).collect())
 }
 
 pub async fn add_session_speaker(
-    pool: &PgPool,
+    conn: &mut PgConnection,
+    conference_id: i32,
     session_id: &str,
     speaker_id: &str,
 ) -> Result<(), ServiceError> {
-    // Check if relationship already exists
-    let exists = sqlx::query!(
+    if !lock_session(&mut *conn, conference_id, session_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    let speaker_ids = vec![speaker_id.to_string()];
+    let unknown = unknown_speakers(&mut *conn, conference_id, &speaker_ids).await?;
+    if !unknown.is_empty() {
+        return Err(ServiceError::InvalidReferences(InvalidReferences {
+            speaker_ids: unknown,
+            ..Default::default()
+        }));
+    }
+
+    // Adding an existing relationship is a no-op
+    let inserted = sqlx::query!(
         r#"
-        SELECT COUNT(*) as count
-        FROM session_speakers
-        WHERE session_id = $1 AND speaker_id = $2
+        INSERT INTO session_speakers (conference_id, session_id, speaker_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (conference_id, session_id, speaker_id) DO NOTHING
         "#,
+        conference_id,
         session_id,
         speaker_id
     )
-    .fetch_one(pool)
+    .execute(&mut *conn)
     .await?
-    .count
-    .unwrap_or(0)
-        > 0;
+    .rows_affected();
 
-    if !exists {
-        sqlx::query!(
-            r#"
-            INSERT INTO session_speakers (session_id, speaker_id)
-            VALUES ($1, $2)
-            "#,
-            session_id,
-            speaker_id
-        )
-        .execute(pool)
-        .await?;
+    if inserted > 0 {
+        touch_session(&mut *conn, conference_id, session_id).await?;
     }
 
     Ok(())
 }
 
 pub async fn add_session_category(
-    pool: &PgPool,
+    conn: &mut PgConnection,
+    conference_id: i32,
     session_id: &str,
     category_id: i32,
 ) -> Result<(), ServiceError> {
-    // Check if relationship already exists
-    let exists = sqlx::query!(
+    if !lock_session(&mut *conn, conference_id, session_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    let unknown = unknown_categories(&mut *conn, conference_id, &[category_id]).await?;
+    if !unknown.is_empty() {
+        return Err(ServiceError::InvalidReferences(InvalidReferences {
+            category_ids: unknown,
+            ..Default::default()
+        }));
+    }
+
+    // Adding an existing relationship is a no-op
+    let inserted = sqlx::query!(
         r#"
-        SELECT COUNT(*) as count
-        FROM session_categories
-        WHERE session_id = $1 AND category_item_id = $2
+        INSERT INTO session_categories (conference_id, session_id, category_item_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (conference_id, session_id, category_item_id) DO NOTHING
         "#,
+        conference_id,
         session_id,
         category_id
     )
-    .fetch_one(pool)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if inserted > 0 {
+        touch_session(&mut *conn, conference_id, session_id).await?;
+    }
+    // The session row is locked, concurrent additions see each other's categories
+    check_single_choice(&mut *conn, conference_id, Some(session_id)).await?;
+
+    Ok(())
+}
+
+// Returns false if the session had no such speaker
+pub async fn remove_session_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    speaker_id: &str,
+) -> Result<bool, ServiceError> {
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM session_speakers
+        WHERE conference_id = $1 AND session_id = $2 AND speaker_id = $3
+        "#,
+        conference_id,
+        session_id,
+        speaker_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if removed == 0 {
+        return Ok(false);
+    }
+
+    touch_session(&mut *conn, conference_id, session_id).await?;
+
+    Ok(true)
+}
+
+// Returns false if the session had no such category
+pub async fn remove_session_category(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    category_id: i32,
+) -> Result<bool, ServiceError> {
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM session_categories
+        WHERE conference_id = $1 AND session_id = $2 AND category_item_id = $3
+        "#,
+        conference_id,
+        session_id,
+        category_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if removed == 0 {
+        return Ok(false);
+    }
+
+    touch_session(&mut *conn, conference_id, session_id).await?;
+
+    Ok(true)
+}
+
+// Replaces the speakers of a session with the given list, returns false if the session
+// does not exist
+pub async fn set_session_speakers(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    speaker_ids: &[String],
+) -> Result<bool, ServiceError> {
+    if !lock_session(&mut *conn, conference_id, session_id).await? {
+        return Ok(false);
+    }
+
+    let speaker_ids = unique(speaker_ids);
+    let unknown = unknown_speakers(&mut *conn, conference_id, &speaker_ids).await?;
+    if !unknown.is_empty() {
+        return Err(ServiceError::InvalidReferences(InvalidReferences {
+            speaker_ids: unknown,
+            ..Default::default()
+        }));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM session_speakers
+        WHERE conference_id = $1 AND session_id = $2 AND NOT (speaker_id = ANY($3))
+        "#,
+        conference_id,
+        session_id,
+        &speaker_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO session_speakers (conference_id, session_id, speaker_id)
+        SELECT $1, $2, UNNEST($3::VARCHAR[])
+        ON CONFLICT (conference_id, session_id, speaker_id) DO NOTHING
+        "#,
+        conference_id,
+        session_id,
+        &speaker_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    touch_session(&mut *conn, conference_id, session_id).await?;
+
+    Ok(true)
+}
+
+// Replaces the categories of a session with the given list, returns false if the session
+// does not exist
+pub async fn set_session_categories(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    category_ids: &[i32],
+) -> Result<bool, ServiceError> {
+    if !lock_session(&mut *conn, conference_id, session_id).await? {
+        return Ok(false);
+    }
+
+    let category_ids = unique(category_ids);
+    let unknown = unknown_categories(&mut *conn, conference_id, &category_ids).await?;
+    if !unknown.is_empty() {
+        return Err(ServiceError::InvalidReferences(InvalidReferences {
+            category_ids: unknown,
+            ..Default::default()
+        }));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM session_categories
+        WHERE conference_id = $1 AND session_id = $2 AND NOT (category_item_id = ANY($3))
+        "#,
+        conference_id,
+        session_id,
+        &category_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO session_categories (conference_id, session_id, category_item_id)
+        SELECT $1, $2, UNNEST($3::INTEGER[])
+        ON CONFLICT (conference_id, session_id, category_item_id) DO NOTHING
+        "#,
+        conference_id,
+        session_id,
+        &category_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+    check_single_choice(&mut *conn, conference_id, Some(session_id)).await?;
+
+    touch_session(&mut *conn, conference_id, session_id).await?;
+
+    Ok(true)
+}
+
+// Moves the session along its lifecycle. Returns the previous status and the title, or None if
+// there is no such session. Setting the current status again is a no-op.
+pub async fn set_session_status(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    status: SessionStatus,
+) -> Result<Option<(SessionStatus, String)>, ServiceError> {
+
+    let Some(current) = sqlx::query!(
+        r#"
+        SELECT title, status FROM conference_sessions
+        WHERE id = $1 AND conference_id = $2
+        FOR UPDATE
+        "#,
+        session_id,
+        conference_id
+    )
+    .fetch_optional(&mut *conn)
     .await?
-    .count
-    .unwrap_or(0)
-        > 0;
+    else {
+        return Ok(None);
+    };
+
+    let previous = SessionStatus::from_name(&current.status).unwrap_or(SessionStatus::Draft);
+    if previous == status {
+        return Ok(Some((previous, current.title)));
+    }
+    if !previous.can_become(status) {
+        return Err(ServiceError::BadRequest(format!(
+            "Session status cannot change from {} to {}",
+            previous.name(),
+            status.name()
+        )));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE conference_sessions
+        SET status = $2, published_at = COALESCE(published_at, CASE WHEN $3 THEN NOW() END),
+            updated_at = NOW()
+        WHERE id = $1 AND conference_id = $4
+        "#,
+        session_id,
+        status.name(),
+        status.is_public(),
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(Some((previous, current.title)))
+}
 
-    if !exists {
-        sqlx::query!(
+// Changes when and where the session takes place, returns false if there is no such session
+pub(crate) async fn move_session(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    room_id: Option<i32>,
+) -> Result<bool, ServiceError> {
+    if starts_at > ends_at {
+        return Err(ServiceError::BadRequest("A session cannot end before it starts".to_string()));
+    }
+    if let Some(room_id) = room_id {
+        let room = sqlx::query_scalar!(
             r#"
-            INSERT INTO session_categories (session_id, category_item_id)
-            VALUES ($1, $2)
+            SELECT id FROM conference_rooms
+            WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+            FOR KEY SHARE
             "#,
-            session_id,
-            category_id
+            room_id,
+            conference_id
         )
-        .execute(pool)
+        .fetch_optional(&mut *conn)
         .await?;
+
+        if room.is_none() {
+            return Err(ServiceError::InvalidReferences(InvalidReferences {
+                room_id: Some(room_id),
+                ..Default::default()
+            }));
+        }
     }
 
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_sessions
+        SET starts_at = $3, ends_at = $4, room_id = $5, updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        session_id,
+        conference_id,
+        starts_at,
+        ends_at,
+        room_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Serializes concurrent edits of the same session's links
+async fn lock_session(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+) -> Result<bool, ServiceError> {
+    let session = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM conference_sessions
+        WHERE id = $1 AND conference_id = $2
+        FOR UPDATE
+        "#,
+        session_id,
+        conference_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(session.is_some())
+}
+
+// Link rows are not covered by the session's updated_at, bump it so /sync/sessions
+// returns the new speakerIds and categoryIds
+async fn touch_session<'e, E>(
+    executor: E,
+    conference_id: i32,
+    session_id: &str,
+) -> Result<(), ServiceError>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query!(
+        r#"
+        UPDATE conference_sessions
+        SET updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        session_id,
+        conference_id
+    )
+    .execute(executor)
+    .await?;
+
     Ok(())
+}
+
+pub async fn get_session_windows(
+    pool: &PgPool,
+    conference_id: i32,
+    session_id: &str,
+) -> Result<SessionWindows, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT vote_opens_at, vote_closes_at, feedback_opens_at, feedback_closes_at
+        FROM conference_sessions
+        WHERE id = $1 AND conference_id = $2
+        "#,
+        session_id,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row
+        .map(|row| SessionWindows {
+            vote_opens_at: row.vote_opens_at,
+            vote_closes_at: row.vote_closes_at,
+            feedback_opens_at: row.feedback_opens_at,
+            feedback_closes_at: row.feedback_closes_at,
+        })
+        .unwrap_or_default())
+}
+
+pub async fn set_session_windows(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    windows: &SessionWindows,
+) -> Result<bool, ServiceError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_sessions
+        SET vote_opens_at = $2, vote_closes_at = $3,
+            feedback_opens_at = $4, feedback_closes_at = $5, updated_at = NOW()
+        WHERE id = $1 AND conference_id = $6
+        "#,
+        session_id,
+        windows.vote_opens_at,
+        windows.vote_closes_at,
+        windows.feedback_opens_at,
+        windows.feedback_closes_at,
+        conference_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_conference_end(
+    pool: &PgPool,
+    conference_id: i32,
+) -> Result<Option<DateTime<Utc>>, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT MAX(ends_at) as conference_end
+        FROM conference_sessions
+        WHERE conference_id = $1
+        "#,
+        conference_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.conference_end)
+}
+
+// Bounds are conference wall-clock times, the start is exclusive
+pub async fn get_sessions_starting_between(
+    pool: &PgPool,
+    conference_id: i32,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<UpcomingSession>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT cs.id, cs.title, cs.starts_at, cr.name as "room_name?"
+        FROM conference_sessions cs
+        LEFT JOIN conference_rooms cr ON cs.room_id = cr.id
+        WHERE cs.conference_id = $1 AND cs.starts_at > $2 AND cs.starts_at <= $3
+            AND cs.status = 'published'
+        "#,
+        conference_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| UpcomingSession {
+            id: row.id,
+            title: row.title,
+            starts_at: row.starts_at,
+            room_name: row.room_name,
+        })
+        .collect())
+}
+
+pub async fn get_session_placements(
+    pool: &PgPool,
+    conference_id: i32,
+    session_ids: &[String],
+) -> Result<Vec<SessionPlacement>, ServiceError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT cs.id, cs.title, cs.status, cs.starts_at, cs.ends_at, cs.room_id,
+            cr.name as "room_name?", COALESCE(cs.updated_at, NOW()) as "updated_at!"
+        FROM conference_sessions cs
+        LEFT JOIN conference_rooms cr ON cr.id = cs.room_id AND cr.deleted_at IS NULL
+        WHERE cs.conference_id = $1 AND cs.id = ANY($2)
+        "#,
+        conference_id,
+        session_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SessionPlacement {
+            id: row.id,
+            title: row.title,
+            status: row.status,
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+            room_id: row.room_id,
+            room_name: row.room_name,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::cell::Cell;
+
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::*;
+    use crate::db::{conferences, speakers, sync};
+    use crate::models::edition::ConferenceEditionRequest;
+    use crate::models::session::ConferenceSpeakerRequest;
+
+    thread_local! {
+        static STATEMENTS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    // sqlx reports every statement it runs as an event on the "sqlx::query" target. Tests run on
+    // a current-thread runtime, so a thread-local count only sees the statements of its own test.
+    struct StatementCounter;
+
+    impl Subscriber for StatementCounter {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == "sqlx::query"
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {
+            STATEMENTS.with(|count| count.set(count.get() + 1));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    async fn count_statements<T>(read: impl Future<Output = Result<T, ServiceError>>) -> usize {
+        let _guard = tracing::subscriber::set_default(StatementCounter);
+        STATEMENTS.with(|count| count.set(0));
+        read.await.unwrap();
+        STATEMENTS.with(Cell::get)
+    }
+
+    async fn add_edition(pool: &PgPool, slug: &str) -> i32 {
+        let request = ConferenceEditionRequest {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            time_zone: None,
+            admin_secret: None,
+            settings: Default::default(),
+        };
+        conferences::add_conference(pool, &request, None).await.unwrap()
+    }
+
+    async fn import(pool: &PgPool, conference_id: i32, title: &str) {
+        let speaker: ConferenceSpeakerRequest = serde_json::from_value(json!({
+            "firstName": "Ada",
+            "lastName": "Lovelace",
+            "isTopSpeaker": false
+        }))
+        .unwrap();
+        let session: ConferenceSessionRequest = serde_json::from_value(json!({
+            "title": title,
+            "description": "Imported",
+            "startsAt": "2026-05-21T10:00:00",
+            "endsAt": "2026-05-21T11:00:00",
+            "isServiceSession": false,
+            "isPlenumSession": false,
+            "status": "published",
+            "speakerIds": ["speaker-1"],
+            "categoryIds": []
+        }))
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        speakers::add_speaker_in(&mut conn, conference_id, "speaker-1", &speaker).await.unwrap();
+        add_session_in(&mut conn, conference_id, "session-1", &session).await.unwrap();
+    }
+
+    async fn add_more_sessions(pool: &PgPool, conference_id: i32, count: usize) {
+        let mut conn = pool.acquire().await.unwrap();
+        for index in 2..count + 2 {
+            let session: ConferenceSessionRequest = serde_json::from_value(json!({
+                "title": format!("Talk {}", index),
+                "description": "Imported",
+                "startsAt": "2026-05-21T12:00:00",
+                "endsAt": "2026-05-21T13:00:00",
+                "isServiceSession": false,
+                "isPlenumSession": false,
+                "status": "published",
+                "speakerIds": ["speaker-1"],
+                "categoryIds": []
+            }))
+            .unwrap();
+            add_session_in(&mut conn, conference_id, &format!("session-{}", index), &session)
+                .await
+                .unwrap();
+        }
+    }
+
+    // Statements each read path runs, with the same locale and visibility for every call
+    async fn read_path_statements(pool: &PgPool, conference_id: i32) -> [usize; 4] {
+        [
+            count_statements(get_conference_data(pool, conference_id, None, false)).await,
+            count_statements(get_all_sessions(pool, conference_id, None, false)).await,
+            count_statements(get_session_by_id(pool, conference_id, "session-1", true)).await,
+            count_statements(sync::get_sessions_changed_since(pool, conference_id, 0, None, false)).await,
+        ]
+    }
+
+    #[sqlx::test]
+    async fn editions_can_import_the_same_external_ids(pool: PgPool) {
+        let first = add_edition(&pool, "first").await;
+        let second = add_edition(&pool, "second").await;
+        import(&pool, first, "First keynote").await;
+        import(&pool, second, "Second keynote").await;
+
+        let in_first = get_session_by_id(&pool, first, "session-1", true).await.unwrap().unwrap();
+        let in_second = get_session_by_id(&pool, second, "session-1", true).await.unwrap().unwrap();
+        assert_eq!(in_first.title, "First keynote");
+        assert_eq!(in_second.title, "Second keynote");
+
+        // Links of one edition leave the other's alone
+        let mut conn = pool.acquire().await.unwrap();
+        assert!(remove_session_speaker(&mut conn, first, "session-1", "speaker-1").await.unwrap());
+        let in_first = get_session_by_id(&pool, first, "session-1", true).await.unwrap().unwrap();
+        let in_second = get_session_by_id(&pool, second, "session-1", true).await.unwrap().unwrap();
+        assert!(in_first.speaker_ids.is_empty());
+        assert_eq!(in_second.speaker_ids, vec!["speaker-1".to_string()]);
+    }
+
+    #[sqlx::test]
+    async fn read_paths_run_the_same_statements_for_any_programme_size(pool: PgPool) {
+        let conference = add_edition(&pool, "sized").await;
+        import(&pool, conference, "Keynote").await;
+        // Warm the pool so opening a connection does not count towards the first read
+        read_path_statements(&pool, conference).await;
+        let with_one = read_path_statements(&pool, conference).await;
+
+        add_more_sessions(&pool, conference, 20).await;
+        let with_many = read_path_statements(&pool, conference).await;
+
+        assert!(with_one.iter().all(|&statements| statements > 0));
+        assert_eq!(with_one, with_many);
+    }
 }
\ No newline at end of file
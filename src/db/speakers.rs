@@ -1,82 +1,383 @@
 // src/db/speakers.rs
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::error::ServiceError;
-use crate::models::session::{ConferenceSpeakerRequest, SpeakerInfo};
+use crate::models::session::{ConferenceSpeakerRequest, SpeakerInfo, SpeakerLink, SpeakerPhoto};
+
+struct SpeakerRow {
+    id: String,
+    first_name: String,
+    last_name: String,
+    bio: Option<String>,
+    tag_line: Option<String>,
+    profile_picture: Option<String>,
+    is_top_speaker: bool,
+    company: Option<String>,
+    job_title: Option<String>,
+    pronouns: Option<String>,
+    country: Option<String>,
+    links: Json<Vec<SpeakerLink>>,
+    photos: Json<Vec<SpeakerPhoto>>,
+    photo_asset_id: Option<String>,
+    deleted_at: Option<DateTime<Utc>>,
+    merged_into: Option<String>,
+}
+
+impl From<SpeakerRow> for SpeakerInfo {
+    fn from(row: SpeakerRow) -> Self {
+        SpeakerInfo {
+            id: row.id,
+            first_name: row.first_name,
+            last_name: row.last_name,
+            bio: row.bio,
+            tag_line: row.tag_line,
+            profile_picture: row.profile_picture,
+            is_top_speaker: row.is_top_speaker,
+            company: row.company,
+            job_title: row.job_title,
+            pronouns: row.pronouns,
+            country: row.country,
+            links: row.links.0,
+            photos: row.photos.0,
+            photo_asset_id: row.photo_asset_id,
+            deleted_at: row.deleted_at,
+            merged_into: row.merged_into,
+        }
+    }
+}
 
 pub async fn get_speaker_by_id(
     pool: &PgPool,
+    conference_id: i32,
     speaker_id: &str,
 ) -> Result<Option<SpeakerInfo>, ServiceError> {
-    let speaker = sqlx::query!(
+    let speaker = sqlx::query_as!(
+        SpeakerRow,
         r#"
-        SELECT id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker
+        SELECT id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker,
+            company, job_title, pronouns, country,
+            links as "links: Json<Vec<SpeakerLink>>", photos as "photos: Json<Vec<SpeakerPhoto>>",
+            photo_asset_id, deleted_at, merged_into
         FROM conference_speakers
-        WHERE id = $1
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
         "#,
-        speaker_id
+        speaker_id,
+        conference_id
     )
     .fetch_optional(pool)
     .await?;
 
-    Ok(speaker.map(|row| SpeakerInfo {
-        id: row.id,
-        first_name: row.first_name,
-        last_name: row.last_name,
-        bio: row.bio,
-        tag_line: row.tag_line,
-        profile_picture: row.profile_picture,
-        is_top_speaker: row.is_top_speaker,
-    }))
+    Ok(speaker.map(SpeakerInfo::from))
 }
 
-pub async fn get_all_speakers(pool: &PgPool) -> Result<Vec<SpeakerInfo>, ServiceError> {
-    let speakers = sqlx::query!(
+// Speaker a merged duplicate now lives on as
+pub async fn get_merged_into(
+    pool: &PgPool,
+    conference_id: i32,
+    speaker_id: &str,
+) -> Result<Option<String>, ServiceError> {
+    let merged_into = sqlx::query_scalar!(
         r#"
-        SELECT id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker
-        FROM conference_speakers
-        "#
+        SELECT merged_into FROM conference_speakers
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NOT NULL
+        "#,
+        speaker_id,
+        conference_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(merged_into.flatten())
+}
+
+pub async fn get_all_speakers(
+    pool: &PgPool,
+    conference_id: i32,
+    locale: Option<&str>,
+) -> Result<Vec<SpeakerInfo>, ServiceError> {
+    let speakers = sqlx::query_as!(
+        SpeakerRow,
+        r#"
+        SELECT cs.id, cs.first_name, cs.last_name,
+            COALESCE(st.bio, cs.bio) as bio, COALESCE(st.tag_line, cs.tag_line) as tag_line,
+            cs.profile_picture, cs.is_top_speaker, cs.company, cs.job_title, cs.pronouns, cs.country,
+            cs.links as "links: Json<Vec<SpeakerLink>>", cs.photos as "photos: Json<Vec<SpeakerPhoto>>",
+            cs.photo_asset_id, cs.deleted_at, cs.merged_into
+        FROM conference_speakers cs
+        LEFT JOIN speaker_translations st
+            ON st.conference_id = cs.conference_id AND st.speaker_id = cs.id AND st.locale = $2
+        WHERE cs.conference_id = $1 AND cs.deleted_at IS NULL
+        "#,
+        conference_id,
+        locale
     )
     .fetch_all(pool)
     .await?;
 
-    Ok(speakers
-        .into_iter()
-        .map(|row| SpeakerInfo {
-            id: row.id,
-            first_name: row.first_name,
-            last_name: row.last_name,
-            bio: row.bio,
-            tag_line: row.tag_line,
-            profile_picture: row.profile_picture,
-            is_top_speaker: row.is_top_speaker,
-        })
-        .collect())
+    Ok(speakers.into_iter().map(SpeakerInfo::from).collect())
 }
 
 pub async fn add_speaker(
-    pool: &PgPool,
+    conn: &mut PgConnection,
+    conference_id: i32,
     speaker: &ConferenceSpeakerRequest,
 ) -> Result<String, ServiceError> {
     let generated_id = Uuid::new_v4().to_string();
+    add_speaker_in(conn, conference_id, &generated_id, speaker).await?;
 
+    Ok(generated_id)
+}
+
+// Like add_speaker, with an id chosen by the caller
+pub(crate) async fn add_speaker_in(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    speaker: &ConferenceSpeakerRequest,
+) -> Result<(), ServiceError> {
     sqlx::query!(
         r#"
         INSERT INTO conference_speakers 
-        (id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        (id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker, conference_id,
+         company, job_title, pronouns, country, links, photos, photo_asset_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
         "#,
-        generated_id,
+        speaker_id,
         speaker.first_name,
         speaker.last_name,
         speaker.bio,
         speaker.tag_line,
-        speaker.profile_picture,
-        speaker.is_top_speaker
+        speaker.primary_picture(),
+        speaker.is_top_speaker,
+        conference_id,
+        speaker.company,
+        speaker.job_title,
+        speaker.pronouns,
+        speaker.country,
+        Json(speaker.links.as_deref().unwrap_or_default()) as _,
+        Json(speaker.photos.as_deref().unwrap_or_default()) as _,
+        speaker.photo_asset_id
     )
-    .execute(pool)
+    .execute(conn)
     .await?;
 
-    Ok(generated_id)
+    Ok(())
+}
+
+pub async fn update_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    speaker: &ConferenceSpeakerRequest,
+) -> Result<bool, ServiceError> {
+    // Clients that predate the profile fields leave them out, which keeps what is stored.
+    // An empty string clears a text field.
+    let result = sqlx::query!(
+        r#"
+        UPDATE conference_speakers
+        SET first_name = $3, last_name = $4, bio = $5, tag_line = $6, profile_picture = $7,
+            is_top_speaker = $8, company = NULLIF(COALESCE($9, company), ''),
+            job_title = NULLIF(COALESCE($10, job_title), ''),
+            pronouns = NULLIF(COALESCE($11, pronouns), ''),
+            country = NULLIF(COALESCE($12, country), ''),
+            links = COALESCE($13, links), photos = COALESCE($14, photos),
+            photo_asset_id = COALESCE($15, photo_asset_id), updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+        "#,
+        speaker_id,
+        conference_id,
+        speaker.first_name,
+        speaker.last_name,
+        speaker.bio,
+        speaker.tag_line,
+        speaker.primary_picture(),
+        speaker.is_top_speaker,
+        speaker.company,
+        speaker.job_title,
+        speaker.pronouns,
+        speaker.country,
+        speaker.links.as_ref().map(Json) as _,
+        speaker.photos.as_ref().map(Json) as _,
+        speaker.photo_asset_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Unlinks the speaker from their sessions and leaves a tombstone for /sync/speakers.
+// Returns the ids of the affected sessions, None if the speaker does not exist.
+pub async fn delete_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+) -> Result<Option<Vec<String>>, ServiceError> {
+    if !lock_live_speaker(conn, conference_id, speaker_id).await? {
+        return Ok(None);
+    }
+
+    let session_ids = sqlx::query_scalar!(
+        r#"
+        DELETE FROM session_speakers
+        WHERE conference_id = $1 AND speaker_id = $2
+        RETURNING session_id
+        "#,
+        conference_id,
+        speaker_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    touch_sessions(conn, conference_id, &session_ids).await?;
+    bury_speaker(conn, conference_id, speaker_id, None).await?;
+
+    Ok(Some(session_ids))
+}
+
+// Moves the sessions of the duplicate to the canonical speaker and leaves a tombstone
+// pointing at it. Returns the ids of the affected sessions, None if either speaker does
+// not exist.
+pub async fn merge_speakers(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    canonical_id: &str,
+    duplicate_id: &str,
+) -> Result<Option<Vec<String>>, ServiceError> {
+    if canonical_id == duplicate_id {
+        return Err(ServiceError::BadRequest("A speaker cannot be merged into itself".to_string()));
+    }
+
+    // Always lock in the same order so concurrent merges of the same pair cannot deadlock
+    let (first, second) = if canonical_id < duplicate_id {
+        (canonical_id, duplicate_id)
+    } else {
+        (duplicate_id, canonical_id)
+    };
+    if !lock_live_speaker(&mut *conn, conference_id, first).await?
+        || !lock_live_speaker(&mut *conn, conference_id, second).await?
+    {
+        return Ok(None);
+    }
+
+    let session_ids = sqlx::query_scalar!(
+        r#"
+        SELECT session_id FROM session_speakers
+        WHERE conference_id = $1 AND speaker_id = $2
+        "#,
+        conference_id,
+        duplicate_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Sessions both speakers were linked to keep a single link
+    sqlx::query!(
+        r#"
+        INSERT INTO session_speakers (conference_id, session_id, speaker_id)
+        SELECT conference_id, session_id, $3 FROM session_speakers
+        WHERE conference_id = $1 AND speaker_id = $2
+        ON CONFLICT (conference_id, session_id, speaker_id) DO NOTHING
+        "#,
+        conference_id,
+        duplicate_id,
+        canonical_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM session_speakers
+        WHERE conference_id = $1 AND speaker_id = $2
+        "#,
+        conference_id,
+        duplicate_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Earlier duplicates of the duplicate now point at the canonical speaker as well
+    sqlx::query!(
+        r#"
+        UPDATE conference_speakers
+        SET merged_into = $3, updated_at = NOW()
+        WHERE conference_id = $1 AND merged_into = $2
+        "#,
+        conference_id,
+        duplicate_id,
+        canonical_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    touch_sessions(&mut *conn, conference_id, &session_ids).await?;
+    bury_speaker(&mut *conn, conference_id, duplicate_id, Some(canonical_id)).await?;
+
+    Ok(Some(session_ids))
+}
+
+async fn lock_live_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+) -> Result<bool, ServiceError> {
+    let speaker = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM conference_speakers
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        speaker_id,
+        conference_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(speaker.is_some())
+}
+
+async fn bury_speaker(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    merged_into: Option<&str>,
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE conference_speakers
+        SET deleted_at = NOW(), merged_into = $3, updated_at = NOW()
+        WHERE conference_id = $1 AND id = $2
+        "#,
+        conference_id,
+        speaker_id,
+        merged_into
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+// The sessions' speakerIds changed, make /sync/sessions pick them up
+async fn touch_sessions(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_ids: &[String],
+) -> Result<(), ServiceError> {
+    sqlx::query!(
+        r#"
+        UPDATE conference_sessions
+        SET updated_at = NOW()
+        WHERE conference_id = $1 AND id = ANY($2)
+        "#,
+        conference_id,
+        session_ids
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
 }
\ No newline at end of file
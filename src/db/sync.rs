@@ -1,85 +1,68 @@
 use chrono::DateTime;
-use sqlx::PgPool;
+use sqlx::{types::Json, PgPool};
 
+use crate::db::categories::CategoryRow;
+use crate::db::sessions::SessionInfoRow;
 use crate::error::ServiceError;
 use crate::models::category::CategoriesTable;
 use crate::models::podcast::{EpisodeData,ChannelFullData};
 use crate::models::room::RoomTable;
-use crate::models::session::{SessionInfo, SpeakerInfo};
+use crate::models::session::{SessionInfo, SessionStatus, SpeakerInfo, SpeakerLink, SpeakerPhoto};
 
+// Sessions that have been public once keep being delivered, so clients also learn when one
+// is archived. `preview` includes the sessions attendees cannot see yet.
 pub async fn get_sessions_changed_since(
     pool: &PgPool,
+    conference_id: i32,
     timestamp: i64,
+    locale: Option<&str>,
+    preview: bool,
 ) -> Result<Vec<SessionInfo>, ServiceError> {
     let since_time = DateTime::from_timestamp(timestamp / 1000, 0)
         .ok_or_else(|| ServiceError::BadRequest("Invalid timestamp".to_string()))?;
 
-    let sessions = sqlx::query!(
+    let sessions = sqlx::query_as!(
+        SessionInfoRow,
         r#"
         SELECT 
-            id, title, description, starts_at, ends_at, 
-            room_id, is_service_session, is_plenum_session, status
-        FROM conference_sessions
-        WHERE updated_at >= $1
+            cs.id, COALESCE(st.title, cs.title) as "title!",
+            COALESCE(st.description, cs.description) as description,
+            cs.starts_at, cs.ends_at, 
+            cs.room_id, cs.is_service_session, cs.is_plenum_session, cs.status,
+            COALESCE(sp.speaker_ids, '{}') as "speaker_ids!",
+            COALESCE(sc.category_ids, '{}') as "category_ids!"
+        FROM conference_sessions cs
+        LEFT JOIN session_translations st
+            ON st.conference_id = cs.conference_id AND st.session_id = cs.id AND st.locale = $3
+        LEFT JOIN LATERAL (
+            SELECT array_agg(speaker_id) AS speaker_ids
+            FROM session_speakers
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sp ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT array_agg(category_item_id) AS category_ids
+            FROM session_categories
+            WHERE conference_id = cs.conference_id AND session_id = cs.id
+        ) sc ON TRUE
+        WHERE cs.updated_at >= $1 AND cs.conference_id = $2
+            AND (cs.status = ANY($4) OR cs.published_at IS NOT NULL)
         "#,
-        since_time
+        since_time,
+        conference_id,
+        locale,
+        &SessionStatus::visible_names(preview)
     )
     .fetch_all(pool)
     .await?;
 
-    let mut result = Vec::new();
-    for row in sessions {
-        // Get speaker IDs
-        let speaker_ids = sqlx::query!(
-            r#"
-            SELECT speaker_id
-            FROM session_speakers
-            WHERE session_id = $1
-            "#,
-            row.id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.speaker_id)
-        .collect();
-
-        // Get category IDs
-        let category_ids = sqlx::query!(
-            r#"
-            SELECT category_item_id
-            FROM session_categories
-            WHERE session_id = $1
-            "#,
-            row.id
-        )
-        .fetch_all(pool)
-        .await?
-        .into_iter()
-        .map(|r| r.category_item_id)
-        .collect();
-
-        result.push(SessionInfo {
-            id: row.id,
-            title: row.title,
-            description: row.description,
-            starts_at: row.starts_at,
-            ends_at: row.ends_at,
-            room_id: row.room_id,
-            is_service_session: row.is_service_session.unwrap_or(false),
-            is_plenum_session: row.is_plenum_session.unwrap_or(false),
-            status: row.status.unwrap_or_else(|| "draft".to_string()),
-            speaker_ids,
-            category_ids,
-        });
-    }
-
-    Ok(result)
+    Ok(sessions.into_iter().map(SessionInfo::from).collect())
 }
 
 pub async fn get_speakers_changed_since(
     pool: &PgPool,
+    conference_id: i32,
     timestamp: i64,
+    locale: Option<&str>,
 ) -> Result<Vec<SpeakerInfo>, ServiceError> {
     let since_time = DateTime::from_timestamp(timestamp / 1000, 0)
         .ok_or_else(|| ServiceError::BadRequest("Invalid timestamp".to_string()))?;
@@ -87,11 +70,19 @@ pub async fn get_speakers_changed_since(
     let speakers = sqlx::query!(
         r#"
         SELECT 
-            id, first_name, last_name, bio, tag_line, profile_picture, is_top_speaker
-        FROM conference_speakers
-        WHERE updated_at >= $1
+            cs.id, cs.first_name, cs.last_name,
+            COALESCE(st.bio, cs.bio) as bio, COALESCE(st.tag_line, cs.tag_line) as tag_line,
+            cs.profile_picture, cs.is_top_speaker, cs.company, cs.job_title, cs.pronouns, cs.country,
+            cs.links as "links: Json<Vec<SpeakerLink>>", cs.photos as "photos: Json<Vec<SpeakerPhoto>>",
+            cs.photo_asset_id, cs.deleted_at, cs.merged_into
+        FROM conference_speakers cs
+        LEFT JOIN speaker_translations st
+            ON st.conference_id = cs.conference_id AND st.speaker_id = cs.id AND st.locale = $3
+        WHERE cs.updated_at >= $1 AND cs.conference_id = $2
         "#,
-        since_time
+        since_time,
+        conference_id,
+        locale
     )
     .fetch_all(pool)
     .await?;
@@ -106,12 +97,22 @@ pub async fn get_speakers_changed_since(
             tag_line: row.tag_line,
             profile_picture: row.profile_picture,
             is_top_speaker: row.is_top_speaker,
+            company: row.company,
+            job_title: row.job_title,
+            pronouns: row.pronouns,
+            country: row.country,
+            links: row.links.0,
+            photos: row.photos.0,
+            photo_asset_id: row.photo_asset_id,
+            deleted_at: row.deleted_at,
+            merged_into: row.merged_into,
         })
         .collect())
 }
 
 pub async fn get_rooms_changed_since(
     pool: &PgPool,
+    conference_id: i32,
     timestamp: i64,
 ) -> Result<Vec<RoomTable>, ServiceError> {
     let since_time = DateTime::from_timestamp(timestamp / 1000, 0)
@@ -119,11 +120,12 @@ pub async fn get_rooms_changed_since(
 
     let rooms = sqlx::query!(
         r#"
-        SELECT id, name, sort
+        SELECT id, name, sort, capacity, floor, building, accessibility_notes, map_x, map_y, deleted_at
         FROM conference_rooms
-        WHERE updated_at >= $1
+        WHERE updated_at >= $1 AND conference_id = $2
         "#,
-        since_time
+        since_time,
+        conference_id
     )
     .fetch_all(pool)
     .await?;
@@ -134,37 +136,44 @@ pub async fn get_rooms_changed_since(
             id: Some(row.id as i64),
             name: row.name,
             sort: row.sort,
+            capacity: row.capacity,
+            floor: row.floor,
+            building: row.building,
+            accessibility_notes: row.accessibility_notes,
+            map_x: row.map_x,
+            map_y: row.map_y,
+            deleted_at: row.deleted_at,
         })
         .collect())
 }
 
 pub async fn get_categories_changed_since(
     pool: &PgPool,
+    conference_id: i32,
     timestamp: i64,
+    locale: Option<&str>,
 ) -> Result<Vec<CategoriesTable>, ServiceError> {
     let since_time = DateTime::from_timestamp(timestamp / 1000, 0)
         .ok_or_else(|| ServiceError::BadRequest("Invalid timestamp".to_string()))?;
 
-    let categories = sqlx::query!(
+    let categories = sqlx::query_as!(
+        CategoryRow,
         r#"
-        SELECT id, title, sort, type as "type_name"
-        FROM conference_categories
-        WHERE updated_at >= $1
+        SELECT cc.id, COALESCE(tr.title, cc.title) as "title!", cc.sort,
+            COALESCE(ct.name, cc.type) as type_name, cc.type_id, cc.parent_id
+        FROM conference_categories cc
+        LEFT JOIN category_types ct ON ct.id = cc.type_id
+        LEFT JOIN category_translations tr ON tr.category_id = cc.id AND tr.locale = $3
+        WHERE cc.updated_at >= $1 AND cc.conference_id = $2
         "#,
-        since_time
+        since_time,
+        conference_id,
+        locale
     )
     .fetch_all(pool)
     .await?;
 
-    Ok(categories
-        .into_iter()
-        .map(|row| CategoriesTable {
-            id: row.id as i64,
-            title: row.title,
-            sort: row.sort,
-            type_name: row.type_name,
-        })
-        .collect())
+    Ok(categories.into_iter().map(CategoriesTable::from).collect())
 }
 
 pub async fn get_podcasts_changed_since(
@@ -373,7 +382,7 @@ pub async fn get_podcasts_changed_since(
         r#"
         SELECT 
             id, title, link, description, copyright, language,
-            author, owner_email, owner_name, image_url, last_build_date
+            author, owner_email, owner_name, image_url, image_asset_id, last_build_date
         FROM podcast_channels
         WHERE id = ANY($1)
         ORDER BY id ASC
@@ -428,6 +437,7 @@ pub async fn get_podcasts_changed_since(
                 owner_email: Some(channel_row.owner_email),
                 owner_name: Some(channel_row.owner_name),
                 image_url: Some(channel_row.image_url),
+                image_asset_id: channel_row.image_asset_id,
                 last_build_date: Some(channel_row.last_build_date.to_rfc3339()),
                 categories: channel_categories.get(&channel_id).cloned().unwrap_or_default(),
                 episodes,
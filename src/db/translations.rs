@@ -0,0 +1,229 @@
+// src/db/translations.rs
+//
+// Writing a translation also bumps updated_at of the translated record, so ETags change and
+// /sync/* hands the record out again.
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::ServiceError;
+use crate::models::translation::{CategoryTranslation, SessionTranslation, SpeakerTranslation};
+
+// Locales the conference has any translated content in
+pub async fn get_locales(pool: &PgPool, conference_id: i32) -> Result<Vec<String>, ServiceError> {
+    let locales = sqlx::query_scalar!(
+        r#"
+        SELECT locale as "locale!" FROM session_translations WHERE conference_id = $1
+        UNION
+        SELECT locale FROM speaker_translations WHERE conference_id = $1
+        UNION
+        SELECT locale FROM category_translations WHERE conference_id = $1
+        ORDER BY 1
+        "#,
+        conference_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(locales)
+}
+
+pub async fn set_session_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    locale: &str,
+    translation: &SessionTranslation,
+) -> Result<bool, ServiceError> {
+    let touched = sqlx::query!(
+        "UPDATE conference_sessions SET updated_at = NOW() WHERE id = $1 AND conference_id = $2",
+        session_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if touched.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO session_translations (session_id, locale, conference_id, title, description)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (conference_id, session_id, locale)
+        DO UPDATE SET title = $4, description = $5, updated_at = NOW()
+        "#,
+        session_id,
+        locale,
+        conference_id,
+        translation.title,
+        translation.description
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
+
+pub async fn delete_session_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    session_id: &str,
+    locale: &str,
+) -> Result<bool, ServiceError> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM session_translations
+        WHERE session_id = $1 AND locale = $2 AND conference_id = $3
+        "#,
+        session_id,
+        locale,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        "UPDATE conference_sessions SET updated_at = NOW() WHERE id = $1 AND conference_id = $2",
+        session_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
+
+pub async fn set_speaker_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    locale: &str,
+    translation: &SpeakerTranslation,
+) -> Result<bool, ServiceError> {
+    let touched = sqlx::query!(
+        r#"
+        UPDATE conference_speakers SET updated_at = NOW()
+        WHERE id = $1 AND conference_id = $2 AND deleted_at IS NULL
+        "#,
+        speaker_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if touched.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO speaker_translations (speaker_id, locale, conference_id, bio, tag_line)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (conference_id, speaker_id, locale)
+        DO UPDATE SET bio = $4, tag_line = $5, updated_at = NOW()
+        "#,
+        speaker_id,
+        locale,
+        conference_id,
+        translation.bio,
+        translation.tag_line
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
+
+pub async fn delete_speaker_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    speaker_id: &str,
+    locale: &str,
+) -> Result<bool, ServiceError> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM speaker_translations
+        WHERE speaker_id = $1 AND locale = $2 AND conference_id = $3
+        "#,
+        speaker_id,
+        locale,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        "UPDATE conference_speakers SET updated_at = NOW() WHERE id = $1 AND conference_id = $2",
+        speaker_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
+
+pub async fn set_category_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: i32,
+    locale: &str,
+    translation: &CategoryTranslation,
+) -> Result<bool, ServiceError> {
+    let touched = sqlx::query!(
+        "UPDATE conference_categories SET updated_at = NOW() WHERE id = $1 AND conference_id = $2",
+        category_id,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if touched.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO category_translations (category_id, locale, conference_id, title)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (category_id, locale)
+        DO UPDATE SET title = $4, updated_at = NOW()
+        "#,
+        category_id,
+        locale,
+        conference_id,
+        translation.title
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
+
+pub async fn delete_category_translation(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    category_id: i32,
+    locale: &str,
+) -> Result<bool, ServiceError> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM category_translations
+        WHERE category_id = $1 AND locale = $2 AND conference_id = $3
+        "#,
+        category_id,
+        locale,
+        conference_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        "UPDATE conference_categories SET updated_at = NOW() WHERE id = $1",
+        category_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(true)
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+// src/db/versions.rs
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+use crate::models::version::{PodcastVersions, ScheduleVersions, TableVersion};
+
+// Tables record when rows were last deleted from them, so deletes move `last_modified` too
+pub async fn get_schedule_versions(
+    pool: &PgPool,
+    conference_id: i32,
+) -> Result<ScheduleVersions, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            s.last_modified AS sessions_at, s.rows AS "sessions_rows!",
+            sp.last_modified AS speakers_at, sp.rows AS "speakers_rows!",
+            r.last_modified AS rooms_at, r.rows AS "rooms_rows!",
+            c.last_modified AS categories_at, c.rows AS "categories_rows!",
+            ct.last_modified AS category_types_at, ct.rows AS "category_types_rows!",
+            ss.last_modified AS session_speakers_at, ss.rows AS "session_speakers_rows!",
+            sc.last_modified AS session_categories_at, sc.rows AS "session_categories_rows!"
+        FROM
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'conference_sessions' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM conference_sessions
+             WHERE conference_id = $1) s,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'conference_speakers' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM conference_speakers
+             WHERE conference_id = $1) sp,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'conference_rooms' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM conference_rooms
+             WHERE conference_id = $1) r,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'conference_categories' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM conference_categories
+             WHERE conference_id = $1) c,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'category_types' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM category_types
+             WHERE conference_id = $1) ct,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'session_speakers' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM session_speakers
+             WHERE conference_id = $1) ss,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'session_categories' AND conference_id = $1)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM session_categories
+             WHERE conference_id = $1) sc
+        "#,
+        conference_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(ScheduleVersions {
+        sessions: TableVersion { last_modified: row.sessions_at, rows: row.sessions_rows },
+        speakers: TableVersion { last_modified: row.speakers_at, rows: row.speakers_rows },
+        rooms: TableVersion { last_modified: row.rooms_at, rows: row.rooms_rows },
+        categories: TableVersion { last_modified: row.categories_at, rows: row.categories_rows },
+        category_types: TableVersion {
+            last_modified: row.category_types_at,
+            rows: row.category_types_rows,
+        },
+        session_speakers: TableVersion {
+            last_modified: row.session_speakers_at,
+            rows: row.session_speakers_rows,
+        },
+        session_categories: TableVersion {
+            last_modified: row.session_categories_at,
+            rows: row.session_categories_rows,
+        },
+    })
+}
+
+pub async fn get_podcast_versions(pool: &PgPool) -> Result<PodcastVersions, ServiceError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            ch.last_modified AS channels_at, ch.rows AS "channels_rows!",
+            ep.last_modified AS episodes_at, ep.rows AS "episodes_rows!",
+            cc.last_modified AS channel_categories_at, cc.rows AS "channel_categories_rows!",
+            ec.last_modified AS episode_categories_at, ec.rows AS "episode_categories_rows!",
+            cm.last_modified AS channel_map_at, cm.rows AS "channel_map_rows!",
+            em.last_modified AS episode_map_at, em.rows AS "episode_map_rows!"
+        FROM
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'podcast_channels' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM podcast_channels) ch,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'podcast_episodes' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM podcast_episodes) ep,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'podcast_channel_categories' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM podcast_channel_categories) cc,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'podcast_episode_categories' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM podcast_episode_categories) ec,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'channel_category_map' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM channel_category_map) cm,
+            (SELECT GREATEST(MAX(updated_at), (SELECT deleted_at FROM table_deletions
+                     WHERE table_name = 'episode_category_map' AND conference_id = 0)) AS last_modified,
+                    COUNT(*) AS rows
+             FROM episode_category_map) em
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(PodcastVersions {
+        channels: TableVersion { last_modified: row.channels_at, rows: row.channels_rows },
+        episodes: TableVersion { last_modified: row.episodes_at, rows: row.episodes_rows },
+        channel_categories: TableVersion {
+            last_modified: row.channel_categories_at,
+            rows: row.channel_categories_rows,
+        },
+        episode_categories: TableVersion {
+            last_modified: row.episode_categories_at,
+            rows: row.episode_categories_rows,
+        },
+        channel_category_map: TableVersion {
+            last_modified: row.channel_map_at,
+            rows: row.channel_map_rows,
+        },
+        episode_category_map: TableVersion {
+            last_modified: row.episode_map_at,
+            rows: row.episode_map_rows,
+        },
+    })
+}
\ No newline at end of file
@@ -5,14 +5,20 @@ use sqlx::PgPool;
 use crate::error::ServiceError;
 use crate::models::vote::{Score, VoteInfo};
 
-pub async fn get_votes(pool: &PgPool, user_id: &str) -> Result<Vec<VoteInfo>, ServiceError> {
+pub async fn get_votes(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: &str,
+) -> Result<Vec<VoteInfo>, ServiceError> {
     let votes = sqlx::query!(
         r#"
-        SELECT sessionId, rating
-        FROM votes
-        WHERE uuid = $1
+        SELECT v.sessionId, v.rating
+        FROM votes v
+        JOIN conference_sessions cs ON cs.conference_id = v.conference_id AND cs.id = v.sessionId
+        WHERE v.uuid = $1 AND v.conference_id = $2
         "#,
-        user_id
+        user_id,
+        conference_id
     )
     .fetch_all(pool)
     .await?;
@@ -28,12 +34,15 @@ pub async fn get_votes(pool: &PgPool, user_id: &str) -> Result<Vec<VoteInfo>, Se
         .collect())
 }
 
-pub async fn get_all_votes(pool: &PgPool) -> Result<Vec<VoteInfo>, ServiceError> {
+pub async fn get_all_votes(pool: &PgPool, conference_id: i32) -> Result<Vec<VoteInfo>, ServiceError> {
     let votes = sqlx::query!(
         r#"
-        SELECT sessionId, rating
-        FROM votes
-        "#
+        SELECT v.sessionId, v.rating
+        FROM votes v
+        JOIN conference_sessions cs ON cs.conference_id = v.conference_id AND cs.id = v.sessionId
+        WHERE v.conference_id = $1
+        "#,
+        conference_id
     )
     .fetch_all(pool)
     .await?;
@@ -51,6 +60,7 @@ pub async fn get_all_votes(pool: &PgPool) -> Result<Vec<VoteInfo>, ServiceError>
 
 pub async fn change_vote(
     pool: &PgPool,
+    conference_id: i32,
     user_id: &str,
     session_id: &str,
     score: Option<Score>,
@@ -58,7 +68,7 @@ pub async fn change_vote(
 ) -> Result<(), ServiceError> {
     // If score is None, delete the vote
     if score.is_none() {
-        return delete_vote(pool, user_id, session_id).await;
+        return delete_vote(pool, conference_id, user_id, session_id).await;
     }
 
     let score_value = score.unwrap().value();
@@ -69,10 +79,11 @@ pub async fn change_vote(
         r#"
         SELECT COUNT(*) as count
         FROM votes
-        WHERE uuid = $1 AND sessionId = $2
+        WHERE uuid = $1 AND sessionId = $2 AND conference_id = $3
         "#,
         user_id,
-        session_id
+        session_id,
+        conference_id
     )
     .fetch_one(pool)
     .await?
@@ -86,12 +97,13 @@ pub async fn change_vote(
             r#"
             UPDATE votes
             SET rating = $3, timestamp = $4, updated_at = NOW()
-            WHERE uuid = $1 AND sessionId = $2
+            WHERE uuid = $1 AND sessionId = $2 AND conference_id = $5
             "#,
             user_id,
             session_id,
             score_value,
-            timestamp_str
+            timestamp_str,
+            conference_id
         )
         .execute(pool)
         .await?;
@@ -99,13 +111,14 @@ pub async fn change_vote(
         // Insert new vote
         sqlx::query!(
             r#"
-            INSERT INTO votes (uuid, sessionId, rating, timestamp)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO votes (uuid, sessionId, rating, timestamp, conference_id)
+            VALUES ($1, $2, $3, $4, $5)
             "#,
             user_id,
             session_id,
             score_value,
-            timestamp_str
+            timestamp_str,
+            conference_id
         )
         .execute(pool)
         .await?;
@@ -114,14 +127,20 @@ pub async fn change_vote(
     Ok(())
 }
 
-async fn delete_vote(pool: &PgPool, user_id: &str, session_id: &str) -> Result<(), ServiceError> {
+async fn delete_vote(
+    pool: &PgPool,
+    conference_id: i32,
+    user_id: &str,
+    session_id: &str,
+) -> Result<(), ServiceError> {
     sqlx::query!(
         r#"
         DELETE FROM votes
-        WHERE uuid = $1 AND sessionId = $2
+        WHERE uuid = $1 AND sessionId = $2 AND conference_id = $3
         "#,
         user_id,
-        session_id
+        session_id,
+        conference_id
     )
     .execute(pool)
     .await?;
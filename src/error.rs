@@ -2,6 +2,8 @@ use actix_web::{error::ResponseError, http::StatusCode, HttpResponse};
 use derive_more::Display;
 use serde_json::Error as SerdeError;
 
+use crate::models::session::InvalidReferences;
+
 #[derive(Debug, Display)]
 pub enum ServiceError {
     #[display(fmt = "Internal Server Error")]
@@ -24,6 +26,12 @@ pub enum ServiceError {
     
     #[display(fmt = "Come Back Later")]
     ComeBackLater,
+    
+    #[display(fmt = "Closed")]
+    Closed,
+    
+    #[display(fmt = "BadRequest: {}", _0)]
+    InvalidReferences(InvalidReferences),
 }
 
 impl ResponseError for ServiceError {
@@ -42,6 +50,10 @@ impl ResponseError for ServiceError {
             ServiceError::ComeBackLater => {
                 HttpResponse::build(self.status_code()).json("Come Back Later")
             }
+            ServiceError::Closed => HttpResponse::build(self.status_code()).json("Closed"),
+            ServiceError::InvalidReferences(references) => HttpResponse::BadRequest().json(
+                serde_json::json!({ "message": references.to_string(), "invalid": references }),
+            ),
         }
     }
 
@@ -54,6 +66,8 @@ impl ResponseError for ServiceError {
             ServiceError::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
             ServiceError::SecretInvalid => StatusCode::FORBIDDEN,
             ServiceError::ComeBackLater => StatusCode::from_u16(477).unwrap_or(StatusCode::OK),
+            ServiceError::Closed => StatusCode::from_u16(478).unwrap_or(StatusCode::GONE),
+            ServiceError::InvalidReferences(_) => StatusCode::BAD_REQUEST,
         }
     }
 }
@@ -3,12 +3,23 @@ mod config;
 mod db;
 mod error;
 mod models;
+mod request_id;
 mod routes;
 mod services;
+mod tenancy;
 
 use actix_web::{App, HttpServer, middleware, web};
 use config::AppConfig;
+use chrono_tz::Tz;
 use db::create_pool_with_retry;
+use services::changesets::ChangesetScheduler;
+use services::clock::{Clock, SystemTimeSource};
+use services::events::EventBus;
+use services::media::MediaStore;
+use services::notifications::{NotificationWorker, Providers};
+use services::read_cache::ReadModelCache;
+use services::retention::RetentionScheduler;
+use services::storage;
 use std::sync::Arc;
 
 #[actix_web::main]
@@ -38,19 +49,78 @@ async fn main() -> std::io::Result<()> {
         }
     }
 
+    // Audit entries from before users were labelled by a digest of their token
+    match services::audit::relabel_token_actors(&pool, &config.service.secret).await {
+        Ok(0) => {}
+        Ok(relabelled) => log::info!("Relabelled {} audit entries written by users", relabelled),
+        Err(e) => log::error!("Failed to relabel audit entries written by users: {:?}", e),
+    }
+
+    // Conference clock, all time-based logic goes through it. The configured time zone applies
+    // to conferences that do not set their own.
+    let time_zone: Tz = config
+        .conference
+        .time_zone
+        .parse()
+        .expect("Invalid conference time zone");
+    let clock = Clock::new(Arc::new(SystemTimeSource), time_zone);
+
+    // Live schedule changes, fed by Postgres notifications from every instance
+    let events = EventBus::new();
+    events.start_listener(pool.clone());
+
+    // Composed read models, dropped when a change reaches the event bus
+    let cache = Arc::new(ReadModelCache::new());
+    cache.start_listener(&events);
+
+    // Uploaded images
+    let storage = storage::from_config(&config.media).expect("Invalid media storage configuration");
+    let media = web::Data::new(MediaStore::new(storage, &config.media));
+
+    // Push notification delivery
+    if config.notifications.enabled {
+        let providers = Providers::from_config(&config.notifications)
+            .expect("Invalid notification provider configuration");
+        NotificationWorker::new(pool.clone(), clock.clone(), providers, config.notifications.clone())
+            .start();
+    }
+
+    // Changesets scheduled by organisers
+    ChangesetScheduler::new(pool.clone(), clock.clone(), events.clone(), cache.clone())
+        .start(&config.changesets.schedule)
+        .await
+        .expect("Failed to start the changeset scheduler");
+
+    // Data retention
+    if config.retention.enabled {
+        RetentionScheduler::new(pool.clone(), config.retention.clone())
+            .start()
+            .await
+            .expect("Failed to start the retention scheduler");
+    }
+
     // Start server
     let server_config = config.clone();
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
-            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::from(config.clone()))
+            .app_data(web::Data::new(clock.clone()))
+            .app_data(web::Data::new(events.clone()))
+            .app_data(web::Data::from(cache.clone()))
+            .app_data(media.clone())
             // Middleware
+            .wrap(tenancy::ConferenceScope::new(time_zone))
+            .wrap(request_id::RequestIdentity)
             .wrap(middleware::Logger::default())
             .wrap(middleware::Compress::default())
             .wrap(middleware::DefaultHeaders::new().add(("X-Version", "1.0.0")))
             // Routes
             .service(routes::users::sign)
+            .service(routes::users::export_me)
+            .service(routes::users::delete_me)
             .service(routes::conference::get_conference)
+            .service(routes::speakers::get_speaker)
             // Votes routes
             .service(routes::votes::get_votes)
             .service(routes::votes::post_vote)
@@ -58,13 +128,67 @@ async fn main() -> std::io::Result<()> {
             // Feedback routes
             .service(routes::feedback::post_feedback)
             .service(routes::feedback::get_feedback_summary)
+            .service(routes::feedback::get_feedback)
+            .service(routes::feedback::delete_feedback)
+            .service(routes::feedback_forms::get_session_form)
+            .service(routes::feedback_forms::post_feedback_answers)
+            .service(routes::feedback_forms::get_admin_forms)
+            .service(routes::feedback_forms::add_admin_form)
+            .service(routes::feedback_forms::delete_admin_form)
+            .service(routes::feedback_forms::get_admin_form_summary)
+            // Notification routes
+            .service(routes::notifications::register_device)
+            .service(routes::notifications::delete_device)
+            .service(routes::notifications::get_notification_settings)
+            .service(routes::notifications::set_notification_settings)
             // Admin routes
+            .service(routes::admin::get_admin_cache_stats)
+            .service(routes::admin::get_admin_conferences)
+            .service(routes::admin::add_admin_conference)
+            .service(routes::admin::update_admin_conference)
             .service(routes::admin::get_time)
             .service(routes::admin::set_time)
+            .service(routes::admin::add_cohort_user)
+            .service(routes::admin::remove_cohort_user)
             .service(routes::admin::add_admin_session)
+            .service(routes::admin::set_admin_session_status)
+            .service(routes::admin::get_admin_session_windows)
+            .service(routes::admin::set_admin_session_windows)
             .service(routes::admin::add_admin_speaker)
+            .service(routes::admin::update_admin_speaker)
+            .service(routes::admin::delete_admin_speaker)
+            .service(routes::admin::merge_admin_speaker)
             .service(routes::admin::add_admin_room)
+            .service(routes::admin::update_admin_room)
+            .service(routes::admin::delete_admin_room)
             .service(routes::admin::add_admin_category)
+            .service(routes::admin::update_admin_category)
+            .service(routes::admin::get_admin_category_types)
+            .service(routes::admin::add_admin_category_type)
+            .service(routes::admin::update_admin_category_type)
+            .service(routes::admin::delete_admin_category_type)
+            // Audit routes
+            .service(routes::audit::get_audit_log)
+            .service(routes::audit::get_audit_history)
+            .service(routes::audit::get_audit_version)
+            .service(routes::audit::restore_audit_version)
+            // Changeset routes
+            .service(routes::changesets::get_changesets)
+            .service(routes::changesets::add_changeset)
+            .service(routes::changesets::get_changeset)
+            .service(routes::changesets::delete_changeset)
+            .service(routes::changesets::add_changeset_operation)
+            .service(routes::changesets::delete_changeset_operation)
+            .service(routes::changesets::preview_changeset)
+            .service(routes::changesets::apply_changeset)
+            .service(routes::changesets::schedule_changeset)
+            .service(routes::changesets::unschedule_changeset)
+            // Retention routes
+            .service(routes::retention::run_retention)
+            .service(routes::retention::get_retention_runs)
+            // Report routes
+            .service(routes::reports::get_session_report)
+            .service(routes::reports::get_ranking_report)
             // Session management routes
             .service(routes::sessions::get_sessions)
             .service(routes::sessions::get_categories)
@@ -76,10 +200,27 @@ async fn main() -> std::io::Result<()> {
             .service(routes::sessions::send_room)
             .service(routes::sessions::send_session_speaker)
             .service(routes::sessions::send_session_categories)
+            .service(routes::sessions::delete_session_speaker)
+            .service(routes::sessions::put_session_speakers)
+            .service(routes::sessions::delete_session_category)
+            .service(routes::sessions::put_session_categories)
             // Podcast routes
             .service(routes::podcast::send_podcast_request)
             .service(routes::podcast::import_podcast)
             .service(routes::podcast::get_all_podcasts)
+            // Translation routes
+            .service(routes::translations::put_session_translation)
+            .service(routes::translations::delete_session_translation)
+            .service(routes::translations::put_speaker_translation)
+            .service(routes::translations::delete_speaker_translation)
+            .service(routes::translations::put_category_translation)
+            .service(routes::translations::delete_category_translation)
+            // Media routes
+            .service(routes::media::upload_media)
+            .service(routes::media::get_media)
+            .service(routes::media::get_media_file)
+            // Live change events
+            .service(routes::events::get_events)
             // Sync routes
             .configure(routes::sync::config)
             // Health check
@@ -0,0 +1,131 @@
+// src/models/account.rs
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::models::audit::AuditEntry;
+
+// Everything stored about a user, as returned by /me/export
+#[derive(Debug, Serialize)]
+pub struct AccountExport {
+    pub user: ExportedUser,
+    pub devices: Vec<ExportedDevice>,
+    pub votes: Vec<ExportedVote>,
+    pub feedback: Vec<ExportedFeedback>,
+    
+    #[serde(rename = "feedbackAnswers")]
+    pub feedback_answers: Vec<ExportedAnswer>,
+    
+    #[serde(rename = "podcastRequests")]
+    pub podcast_requests: Vec<ExportedPodcastRequest>,
+    
+    pub notifications: Vec<ExportedNotification>,
+    
+    // Test users get a simulated conference clock
+    pub cohorts: Vec<ExportedCohort>,
+    
+    // Content the user changed through the /send/* endpoints
+    pub changes: Vec<AuditEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedUser {
+    pub uuid: String,
+    pub timestamp: String,
+    
+    #[serde(rename = "notificationsEnabled")]
+    pub notifications_enabled: bool,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedCohort {
+    // Slug of the conference the cohort belongs to
+    pub conference: String,
+    pub cohort: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedDevice {
+    pub token: String,
+    pub platform: String,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedVote {
+    // Slug of the conference the session belongs to
+    pub conference: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    
+    pub rating: i32,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedFeedback {
+    // Slug of the conference the session belongs to
+    pub conference: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    
+    pub feedback: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedAnswer {
+    // Slug of the conference the session belongs to
+    pub conference: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    
+    #[serde(rename = "questionId")]
+    pub question_id: i32,
+    
+    pub rating: Option<i32>,
+    pub choice: Option<String>,
+    
+    #[serde(rename = "textValue")]
+    pub text_value: Option<String>,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedPodcastRequest {
+    pub title: String,
+    pub author: String,
+    
+    #[serde(rename = "rssUrl")]
+    pub rss_url: String,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedNotification {
+    // Slug of the conference it was sent for
+    pub conference: String,
+    
+    pub kind: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+    
+    pub title: String,
+    pub body: String,
+    pub status: String,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
\ No newline at end of file
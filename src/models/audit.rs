@@ -0,0 +1,111 @@
+// src/models/audit.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::event::ChangeEntity;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    Session,
+    Speaker,
+    Room,
+    Category,
+    CategoryType,
+    Podcast,
+    MediaAsset,
+    FeedbackForm,
+}
+
+impl AuditEntity {
+    pub fn from_name(value: &str) -> Option<AuditEntity> {
+        match value {
+            "session" => Some(AuditEntity::Session),
+            "speaker" => Some(AuditEntity::Speaker),
+            "room" => Some(AuditEntity::Room),
+            "category" => Some(AuditEntity::Category),
+            "category_type" => Some(AuditEntity::CategoryType),
+            "podcast" => Some(AuditEntity::Podcast),
+            "media_asset" => Some(AuditEntity::MediaAsset),
+            "feedback_form" => Some(AuditEntity::FeedbackForm),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditEntity::Session => "session",
+            AuditEntity::Speaker => "speaker",
+            AuditEntity::Room => "room",
+            AuditEntity::Category => "category",
+            AuditEntity::CategoryType => "category_type",
+            AuditEntity::Podcast => "podcast",
+            AuditEntity::MediaAsset => "media_asset",
+            AuditEntity::FeedbackForm => "feedback_form",
+        }
+    }
+    
+    // The sync endpoint that serves the entity, None if no sync endpoint does
+    pub fn change_entity(&self) -> Option<ChangeEntity> {
+        match self {
+            AuditEntity::Session => Some(ChangeEntity::Sessions),
+            AuditEntity::Speaker => Some(ChangeEntity::Speakers),
+            AuditEntity::Room => Some(ChangeEntity::Rooms),
+            AuditEntity::Category | AuditEntity::CategoryType => Some(ChangeEntity::Categories),
+            AuditEntity::Podcast => Some(ChangeEntity::Podcasts),
+            AuditEntity::MediaAsset | AuditEntity::FeedbackForm => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+impl AuditAction {
+    pub fn from_name(value: &str) -> Option<AuditAction> {
+        match value {
+            "created" => Some(AuditAction::Created),
+            "updated" => Some(AuditAction::Updated),
+            "deleted" => Some(AuditAction::Deleted),
+            "restored" => Some(AuditAction::Restored),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditAction::Created => "created",
+            AuditAction::Updated => "updated",
+            AuditAction::Deleted => "deleted",
+            AuditAction::Restored => "restored",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub entity: AuditEntity,
+    
+    #[serde(rename = "entityId")]
+    pub entity_id: String,
+    
+    pub version: i32,
+    pub action: AuditAction,
+    pub actor: String,
+    
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+// src/models/cache.rs
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ReadModelStats {
+    pub hits: u64,
+    
+    pub misses: u64,
+    
+    // Number of cached entries dropped because their data changed
+    pub invalidations: u64,
+    
+    pub entries: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStats {
+    // Only the conference the request addressed
+    pub conference: ReadModelStats,
+    
+    pub podcasts: ReadModelStats,
+}
\ No newline at end of file
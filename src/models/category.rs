@@ -6,14 +6,45 @@ pub struct CategoriesTable {
     pub id: i64,
     pub title: String,
     pub sort: Option<i32>,
+    // Name of the category type, or the free-form type of categories without one
     #[serde(rename = "type")]
     pub type_name: Option<String>,
+    #[serde(rename = "typeId", default, skip_serializing_if = "Option::is_none")]
+    pub type_id: Option<i32>,
+    #[serde(rename = "parentId", default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConferenceCategoriesRequest {
     pub title: String,
     pub sort: Option<i32>,
+    // Older clients name the type, it is matched against the conference's category types
     #[serde(rename = "type")]
     pub type_name: Option<String>,
+    #[serde(rename = "typeId", default)]
+    pub type_id: Option<i32>,
+    #[serde(rename = "parentId", default)]
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryType {
+    pub id: i32,
+    pub name: String,
+    pub sort: Option<i32>,
+    // False when a session may only have one category of this type
+    pub multiple: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryTypeRequest {
+    pub name: String,
+    pub sort: Option<i32>,
+    #[serde(default = "default_multiple")]
+    pub multiple: bool,
+}
+
+fn default_multiple() -> bool {
+    true
 }
\ No newline at end of file
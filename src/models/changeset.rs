@@ -0,0 +1,199 @@
+// src/models/changeset.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::room::ConferenceRoomRequest;
+use crate::models::session::{ConferenceSessionRequest, ConferenceSpeakerRequest, SessionStatus};
+use crate::models::time::{deserialize_conference_time, serialize_conference_time};
+
+// Draft and failed changesets are applied by hand, scheduled ones once their time has come
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangesetStatus {
+    Draft,
+    Scheduled,
+    Applied,
+    Failed,
+}
+
+impl ChangesetStatus {
+    pub fn from_name(value: &str) -> Option<ChangesetStatus> {
+        match value {
+            "draft" => Some(ChangesetStatus::Draft),
+            "scheduled" => Some(ChangesetStatus::Scheduled),
+            "applied" => Some(ChangesetStatus::Applied),
+            "failed" => Some(ChangesetStatus::Failed),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangesetStatus::Draft => "draft",
+            ChangesetStatus::Scheduled => "scheduled",
+            ChangesetStatus::Applied => "applied",
+            ChangesetStatus::Failed => "failed",
+        }
+    }
+}
+
+// One queued edit. Sessions and speakers created by a changeset get their id when they are
+// queued, so later operations of the same changeset can refer to them.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeOperation {
+    CreateSession {
+        #[serde(rename = "sessionId", default)]
+        session_id: Option<String>,
+        session: ConferenceSessionRequest,
+    },
+    MoveSession {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(
+            rename = "startsAt",
+            serialize_with = "serialize_conference_time",
+            deserialize_with = "deserialize_conference_time"
+        )]
+        starts_at: DateTime<Utc>,
+        #[serde(
+            rename = "endsAt",
+            serialize_with = "serialize_conference_time",
+            deserialize_with = "deserialize_conference_time"
+        )]
+        ends_at: DateTime<Utc>,
+        #[serde(rename = "roomId")]
+        room_id: Option<i32>,
+    },
+    SetSessionStatus {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        status: SessionStatus,
+    },
+    CreateSpeaker {
+        #[serde(rename = "speakerId", default)]
+        speaker_id: Option<String>,
+        speaker: ConferenceSpeakerRequest,
+    },
+    UpdateSpeaker {
+        #[serde(rename = "speakerId")]
+        speaker_id: String,
+        speaker: ConferenceSpeakerRequest,
+    },
+    DeleteSpeaker {
+        #[serde(rename = "speakerId")]
+        speaker_id: String,
+    },
+    CreateRoom {
+        room: ConferenceRoomRequest,
+    },
+    UpdateRoom {
+        #[serde(rename = "roomId")]
+        room_id: i32,
+        room: ConferenceRoomRequest,
+    },
+    DeleteRoom {
+        #[serde(rename = "roomId")]
+        room_id: i32,
+    },
+}
+
+impl ChangeOperation {
+    // Ids sent by the client are replaced, they could clash with existing rows
+    pub fn assign_id(&mut self) {
+        match self {
+            ChangeOperation::CreateSession { session_id, .. } => {
+                *session_id = Some(Uuid::new_v4().to_string());
+            }
+            ChangeOperation::CreateSpeaker { speaker_id, .. } => {
+                *speaker_id = Some(Uuid::new_v4().to_string());
+            }
+            _ => {}
+        }
+    }
+    
+    // The entity the operation writes to. Rooms only get their id once they are inserted.
+    pub fn target(&self) -> Option<(AuditEntity, String)> {
+        match self {
+            ChangeOperation::CreateSession { session_id, .. } => {
+                session_id.clone().map(|id| (AuditEntity::Session, id))
+            }
+            ChangeOperation::MoveSession { session_id, .. }
+            | ChangeOperation::SetSessionStatus { session_id, .. } => {
+                Some((AuditEntity::Session, session_id.clone()))
+            }
+            ChangeOperation::CreateSpeaker { speaker_id, .. } => {
+                speaker_id.clone().map(|id| (AuditEntity::Speaker, id))
+            }
+            ChangeOperation::UpdateSpeaker { speaker_id, .. }
+            | ChangeOperation::DeleteSpeaker { speaker_id } => {
+                Some((AuditEntity::Speaker, speaker_id.clone()))
+            }
+            ChangeOperation::CreateRoom { .. } => None,
+            ChangeOperation::UpdateRoom { room_id, .. } | ChangeOperation::DeleteRoom { room_id } => {
+                Some((AuditEntity::Room, room_id.to_string()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangesetRequest {
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangesetScheduleRequest {
+    #[serde(rename = "applyAt")]
+    pub apply_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangesetOperation {
+    pub id: i32,
+    
+    #[serde(flatten)]
+    pub operation: ChangeOperation,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Changeset {
+    pub id: i32,
+    pub title: String,
+    pub status: ChangesetStatus,
+    
+    #[serde(rename = "applyAt")]
+    pub apply_at: Option<DateTime<Utc>>,
+    
+    #[serde(rename = "appliedAt")]
+    pub applied_at: Option<DateTime<Utc>>,
+    
+    pub error: Option<String>,
+    
+    #[serde(rename = "createdBy")]
+    pub created_by: String,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+    
+    pub operations: Vec<ChangesetOperation>,
+}
+
+// Difference between the live state of an entity and its state after the changeset, in the
+// same snapshot format as the audit log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeDiff {
+    pub entity: AuditEntity,
+    
+    #[serde(rename = "entityId")]
+    pub entity_id: String,
+    
+    pub action: AuditAction,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
\ No newline at end of file
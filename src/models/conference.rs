@@ -1,12 +1,15 @@
 // src/models/conference.rs
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use crate::models::session::serialize_datetime_as_gmt;
+use crate::models::session::{SessionStatus, SpeakerLink, SpeakerPhoto};
+use crate::models::time::{deserialize_conference_time, serialize_conference_time};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Conference {
     pub sessions: Vec<Session>,
     pub speakers: Vec<Speaker>,
+    pub rooms: Vec<Room>,
+    pub categories: Vec<CategoryGroup>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,13 +23,31 @@ pub struct Session {
     
     pub location: String,
     
-    #[serde(rename = "startsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(rename = "roomId")]
+    pub room_id: Option<i32>,
+    
+    #[serde(
+        rename = "startsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub starts_at: DateTime<Utc>,
     
-    #[serde(rename = "endsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(
+        rename = "endsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub ends_at: DateTime<Utc>,
     
+    // Titles of the session's categories, kept for clients that predate categoryIds
     pub tags: Vec<String>,
+    
+    #[serde(rename = "categoryIds", default)]
+    pub category_ids: Vec<i32>,
+    
+    // Cancelled sessions stay in the schedule so clients can show them as such
+    pub status: SessionStatus,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,4 +59,65 @@ pub struct Speaker {
     
     #[serde(rename = "photoUrl")]
     pub photo_url: String,
+    
+    // Newer profile fields are left out when empty so the payload stays as it was for
+    // speakers that do not use them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    
+    #[serde(rename = "jobTitle", default, skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pronouns: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<SpeakerLink>,
+    
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub photos: Vec<SpeakerPhoto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Room {
+    pub id: i32,
+    pub name: String,
+    pub capacity: Option<i32>,
+    pub floor: Option<String>,
+    pub building: Option<String>,
+    
+    #[serde(rename = "accessibilityNotes")]
+    pub accessibility_notes: Option<String>,
+    
+    #[serde(rename = "mapX")]
+    pub map_x: Option<f64>,
+    
+    #[serde(rename = "mapY")]
+    pub map_y: Option<f64>,
+}
+
+// Categories of one type, categories without a type form a group without id and name
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryGroup {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    pub multiple: bool,
+    
+    pub items: Vec<CategoryItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryItem {
+    pub id: i32,
+    pub title: String,
+    
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<CategoryItem>,
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+// src/models/edition.rs
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::config::WindowConfig;
+
+// Per-conference overrides, anything left out falls back to the service configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConferenceSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voting: Option<WindowConfig>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<WindowConfig>,
+    
+    #[serde(rename = "defaultLocale", skip_serializing_if = "Option::is_none")]
+    pub default_locale: Option<String>,
+}
+
+// A single conference edition or regional event served by this deployment
+#[derive(Debug, Clone, Serialize)]
+pub struct ConferenceEdition {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    
+    #[serde(rename = "timeZone")]
+    pub time_zone: Option<String>,
+    
+    #[serde(skip_serializing)]
+    pub admin_secret_hash: Option<String>,
+    
+    pub settings: ConferenceSettings,
+    
+    #[serde(rename = "isDefault")]
+    pub is_default: bool,
+}
+
+impl ConferenceEdition {
+    pub fn time_zone_or(&self, default: Tz) -> Tz {
+        self.time_zone
+            .as_deref()
+            .and_then(|time_zone| time_zone.parse().ok())
+            .unwrap_or(default)
+    }
+    
+    pub fn voting_window<'a>(&'a self, default: &'a WindowConfig) -> &'a WindowConfig {
+        self.settings.voting.as_ref().unwrap_or(default)
+    }
+    
+    pub fn default_locale<'a>(&'a self, default: &'a str) -> &'a str {
+        self.settings.default_locale.as_deref().unwrap_or(default)
+    }
+    
+    pub fn feedback_window<'a>(&'a self, default: &'a WindowConfig) -> &'a WindowConfig {
+        self.settings.feedback.as_ref().unwrap_or(default)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConferenceEditionRequest {
+    pub slug: String,
+    pub name: String,
+    
+    #[serde(rename = "timeZone")]
+    pub time_zone: Option<String>,
+    
+    #[serde(rename = "adminSecret")]
+    pub admin_secret: Option<String>,
+    
+    #[serde(default)]
+    pub settings: ConferenceSettings,
+}
\ No newline at end of file
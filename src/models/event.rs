@@ -0,0 +1,40 @@
+// src/models/event.rs
+use serde::{Deserialize, Serialize};
+
+// Matches the /sync/* endpoint a client should call to pick up the change
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeEntity {
+    Sessions,
+    Speakers,
+    Rooms,
+    Categories,
+    // Podcasts are shared by all conferences
+    Podcasts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+// Broadcast to live clients after a schedule mutation has been committed.
+// An empty id list means the affected rows are not known and the whole entity should be re-synced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    #[serde(rename = "conferenceId")]
+    pub conference_id: i32,
+    
+    pub entity: ChangeEntity,
+    
+    pub action: ChangeAction,
+    
+    pub ids: Vec<String>,
+    
+    // When the changed rows were committed, in milliseconds since epoch. Usable as the `since`
+    // parameter of the matching sync endpoint.
+    pub at: i64,
+}
\ No newline at end of file
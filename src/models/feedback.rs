@@ -1,4 +1,5 @@
 // src/models/feedback.rs
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -6,4 +7,37 @@ pub struct FeedbackInfo {
     #[serde(rename = "sessionId")]
     pub session_id: String,
     pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    pub value: String,
+    
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionFeedback {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub title: Option<String>,
+    pub count: i64,
+    pub entries: Vec<FeedbackEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackSummaryPage {
+    pub page: i64,
+    
+    #[serde(rename = "pageSize")]
+    pub page_size: i64,
+    
+    #[serde(rename = "totalSessions")]
+    pub total_sessions: i64,
+    
+    pub sessions: Vec<SessionFeedback>,
 }
\ No newline at end of file
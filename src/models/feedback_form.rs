@@ -0,0 +1,141 @@
+// src/models/feedback_form.rs
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionType {
+    Rating,
+    Choice,
+    Text,
+}
+
+impl QuestionType {
+    pub fn from_name(value: &str) -> Option<QuestionType> {
+        match value {
+            "rating" => Some(QuestionType::Rating),
+            "choice" => Some(QuestionType::Choice),
+            "text" => Some(QuestionType::Text),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuestionType::Rating => "rating",
+            QuestionType::Choice => "choice",
+            QuestionType::Text => "text",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackQuestion {
+    pub id: i32,
+    pub prompt: String,
+    
+    #[serde(rename = "type")]
+    pub question_type: QuestionType,
+    
+    #[serde(rename = "scaleMin")]
+    pub scale_min: Option<i32>,
+    
+    #[serde(rename = "scaleMax")]
+    pub scale_max: Option<i32>,
+    
+    pub options: Vec<String>,
+    pub required: bool,
+    pub sort: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackForm {
+    pub id: i32,
+    pub title: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+    
+    #[serde(rename = "categoryId")]
+    pub category_id: Option<i32>,
+    
+    pub questions: Vec<FeedbackQuestion>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackQuestionRequest {
+    pub prompt: String,
+    
+    #[serde(rename = "type")]
+    pub question_type: QuestionType,
+    
+    #[serde(rename = "scaleMin")]
+    pub scale_min: Option<i32>,
+    
+    #[serde(rename = "scaleMax")]
+    pub scale_max: Option<i32>,
+    
+    #[serde(default)]
+    pub options: Vec<String>,
+    
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackFormRequest {
+    pub title: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+    
+    #[serde(rename = "categoryId")]
+    pub category_id: Option<i32>,
+    
+    pub questions: Vec<FeedbackQuestionRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackAnswer {
+    #[serde(rename = "questionId")]
+    pub question_id: i32,
+    pub rating: Option<i32>,
+    pub choice: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackAnswersRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub answers: Vec<FeedbackAnswer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionSummary {
+    #[serde(rename = "questionId")]
+    pub question_id: i32,
+    pub prompt: String,
+    
+    #[serde(rename = "type")]
+    pub question_type: QuestionType,
+    
+    pub responses: i64,
+    
+    #[serde(rename = "averageRating")]
+    pub average_rating: Option<f64>,
+    
+    pub distribution: std::collections::BTreeMap<String, i64>,
+    pub texts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedbackFormSummary {
+    #[serde(rename = "formId")]
+    pub form_id: i32,
+    pub title: String,
+    
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+    
+    pub questions: Vec<QuestionSummary>,
+}
\ No newline at end of file
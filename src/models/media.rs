@@ -0,0 +1,125 @@
+// src/models/media.rs
+use serde::Serialize;
+
+use crate::models::session::{PhotoVariant, SpeakerPhoto};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaFormat {
+    Jpeg,
+    Webp,
+}
+
+impl MediaFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "jpeg" => Some(MediaFormat::Jpeg),
+            "webp" => Some(MediaFormat::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "jpeg",
+            MediaFormat::Webp => "webp",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "jpg" | "jpeg" => Some(MediaFormat::Jpeg),
+            "webp" => Some(MediaFormat::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "jpg",
+            MediaFormat::Webp => "webp",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "image/jpeg",
+            MediaFormat::Webp => "image/webp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaVariant {
+    pub variant: PhotoVariant,
+    
+    pub format: MediaFormat,
+    
+    pub url: String,
+    
+    pub width: i32,
+    
+    pub height: i32,
+    
+    #[serde(rename = "byteSize")]
+    pub byte_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaAsset {
+    pub id: String,
+    
+    // Of the uploaded file
+    #[serde(rename = "contentType")]
+    pub content_type: String,
+    
+    pub width: i32,
+    
+    pub height: i32,
+    
+    pub variants: Vec<MediaVariant>,
+}
+
+impl MediaAsset {
+    pub fn url(&self, variant: PhotoVariant, format: MediaFormat) -> Option<String> {
+        self.variants
+            .iter()
+            .find(|v| v.variant == variant && v.format == format)
+            .map(|v| v.url.clone())
+    }
+
+    // JPEG is what every client can display
+    pub fn speaker_photos(&self) -> Vec<SpeakerPhoto> {
+        self.variants
+            .iter()
+            .filter(|v| v.format == MediaFormat::Jpeg)
+            .map(|v| SpeakerPhoto {
+                variant: v.variant,
+                url: v.url.clone(),
+                width: Some(v.width),
+                height: Some(v.height),
+            })
+            .collect()
+    }
+}
+
+// An asset as recorded in the database, `storage_key` locates each variant in the storage backend
+#[derive(Debug)]
+pub struct StoredAsset {
+    pub id: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub byte_size: i64,
+    pub variants: Vec<StoredVariant>,
+}
+
+#[derive(Debug)]
+pub struct StoredVariant {
+    pub variant: PhotoVariant,
+    pub format: MediaFormat,
+    pub storage_key: String,
+    pub width: i32,
+    pub height: i32,
+    pub byte_size: i64,
+}
\ No newline at end of file
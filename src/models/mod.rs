@@ -1,9 +1,22 @@
 // src/models/mod.rs
-pub mod user;
 pub mod conference;
 pub mod vote;
 pub mod feedback;
+pub mod feedback_form;
 pub mod podcast;
 pub mod session;
 pub mod room;
-pub mod category;
\ No newline at end of file
+pub mod category;
+pub mod report;
+pub mod time;
+pub mod edition;
+pub mod event;
+pub mod notification;
+pub mod version;
+pub mod cache;
+pub mod media;
+pub mod translation;
+pub mod audit;
+pub mod changeset;
+pub mod account;
+pub mod retention;
\ No newline at end of file
@@ -0,0 +1,89 @@
+// src/models/notification.rs
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Fcm,
+    Apns,
+}
+
+impl Platform {
+    pub fn from_name(value: &str) -> Option<Platform> {
+        match value {
+            "fcm" => Some(Platform::Fcm),
+            "apns" => Some(Platform::Apns),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Platform::Fcm => "fcm",
+            Platform::Apns => "apns",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    SessionStarting,
+    RoomChanged,
+    SessionCancelled,
+}
+
+impl NotificationKind {
+    pub fn from_name(value: &str) -> Option<NotificationKind> {
+        match value {
+            "session_starting" => Some(NotificationKind::SessionStarting),
+            "room_changed" => Some(NotificationKind::RoomChanged),
+            "session_cancelled" => Some(NotificationKind::SessionCancelled),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotificationKind::SessionStarting => "session_starting",
+            NotificationKind::RoomChanged => "room_changed",
+            NotificationKind::SessionCancelled => "session_cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceRegistration {
+    pub token: String,
+    pub platform: Platform,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+}
+
+// A notice for the voters of one session, fanned out to one outbox row per recipient
+pub struct SessionNotice {
+    pub kind: NotificationKind,
+    pub session_id: String,
+    pub title: String,
+    pub body: String,
+    // Distinguishes repeated notices of the same kind, e.g. the room a session moved to
+    pub revision: String,
+}
+
+pub struct OutboxMessage {
+    pub id: i64,
+    pub uuid: String,
+    pub kind: NotificationKind,
+    pub session_id: Option<String>,
+    pub title: String,
+    pub body: String,
+    pub attempts: i32,
+}
+
+pub struct DeviceToken {
+    pub token: String,
+    pub platform: Platform,
+}
\ No newline at end of file
@@ -20,6 +20,10 @@ pub struct ChannelData {
     #[serde(rename = "imageUrl")]
     pub image_url: Option<String>,
     
+    // An uploaded asset, takes the place of imageUrl
+    #[serde(rename = "imageAssetId", default)]
+    pub image_asset_id: Option<String>,
+    
     #[serde(rename = "lastBuildDate")]
     pub last_build_date: Option<String>,
 }
@@ -80,6 +84,9 @@ pub struct ChannelFullData {
     #[serde(rename = "imageUrl")]
     pub image_url: Option<String>,
     
+    #[serde(rename = "imageAssetId", default, skip_serializing_if = "Option::is_none")]
+    pub image_asset_id: Option<String>,
+    
     #[serde(rename = "lastBuildDate")]
     pub last_build_date: Option<String>,
     
@@ -0,0 +1,82 @@
+// src/models/report.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::time::{deserialize_conference_time, serialize_conference_time};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionRating {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub title: String,
+    
+    #[serde(
+        rename = "startsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
+    pub starts_at: DateTime<Utc>,
+    
+    #[serde(rename = "roomId")]
+    pub room_id: Option<i32>,
+    
+    #[serde(rename = "roomName")]
+    pub room_name: Option<String>,
+    
+    #[serde(rename = "roomCapacity")]
+    pub room_capacity: Option<i32>,
+    
+    pub good: i64,
+    pub ok: i64,
+    pub bad: i64,
+    pub total: i64,
+    
+    #[serde(rename = "netScore")]
+    pub net_score: i64,
+    
+    #[serde(rename = "participationRate")]
+    pub participation_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RatingGroup {
+    pub key: String,
+    pub label: String,
+    pub sessions: i64,
+    pub good: i64,
+    pub ok: i64,
+    pub bad: i64,
+    pub total: i64,
+    
+    #[serde(rename = "netScore")]
+    pub net_score: i64,
+    
+    #[serde(rename = "averageScore")]
+    pub average_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RatingReport {
+    #[serde(rename = "signedUsers")]
+    pub signed_users: i64,
+    pub sessions: Vec<SessionRating>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankingDimension {
+    Category,
+    Room,
+    Speaker,
+    Day,
+}
+
+impl RankingDimension {
+    pub fn from_name(value: &str) -> Option<RankingDimension> {
+        match value {
+            "category" => Some(RankingDimension::Category),
+            "room" => Some(RankingDimension::Room),
+            "speaker" => Some(RankingDimension::Speaker),
+            "day" => Some(RankingDimension::Day),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+// src/models/retention.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetentionReport {
+    // Conferences whose last session ended before this had their votes and feedback pseudonymised
+    #[serde(rename = "anonymiseBefore")]
+    pub anonymise_before: DateTime<Utc>,
+    
+    #[serde(rename = "staleUsersBefore")]
+    pub stale_users_before: DateTime<Utc>,
+    
+    #[serde(rename = "podcastRequestsBefore")]
+    pub podcast_requests_before: DateTime<Utc>,
+    
+    // Only conferences that still had rows tied to users
+    pub conferences: Vec<ConferenceRetention>,
+    
+    #[serde(rename = "purgedUsers")]
+    pub purged_users: u64,
+    
+    #[serde(rename = "deletedPodcastRequests")]
+    pub deleted_podcast_requests: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConferenceRetention {
+    #[serde(rename = "conferenceId")]
+    pub conference_id: i32,
+    
+    pub slug: String,
+    
+    #[serde(rename = "conferenceEnd")]
+    pub conference_end: DateTime<Utc>,
+    
+    // Users that got a pseudonym for this conference
+    pub users: u64,
+    
+    pub votes: u64,
+    pub feedback: u64,
+    
+    #[serde(rename = "feedbackAnswers")]
+    pub feedback_answers: u64,
+    
+    // Deleted notifications, missing from reports of runs that kept them
+    #[serde(default)]
+    pub notifications: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetentionRun {
+    pub id: i32,
+    
+    #[serde(rename = "startedBy")]
+    pub started_by: String,
+    
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+    
+    pub report: Option<RetentionReport>,
+    pub error: Option<String>,
+    
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+    
+    #[serde(rename = "finishedAt")]
+    pub finished_at: DateTime<Utc>,
+}
\ No newline at end of file
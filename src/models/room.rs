@@ -1,17 +1,47 @@
 // src/models/room.rs
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::time::{deserialize_optional_conference_time, serialize_optional_conference_time};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RoomTable {
     pub id: Option<i64>,
     pub name: String,
     pub sort: Option<i32>,
+    pub capacity: Option<i32>,
+    pub floor: Option<String>,
+    pub building: Option<String>,
+    #[serde(rename = "accessibilityNotes")]
+    pub accessibility_notes: Option<String>,
+    #[serde(rename = "mapX")]
+    pub map_x: Option<f64>,
+    #[serde(rename = "mapY")]
+    pub map_y: Option<f64>,
+    // Only set on rooms returned by /sync/rooms after they were deleted
+    #[serde(
+        rename = "deletedAt",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConferenceRoomRequest {
     pub name: String,
     pub sort: Option<i32>,
+    pub capacity: Option<i32>,
+    pub floor: Option<String>,
+    pub building: Option<String>,
+    #[serde(rename = "accessibilityNotes")]
+    pub accessibility_notes: Option<String>,
+    #[serde(rename = "mapX")]
+    pub map_x: Option<f64>,
+    #[serde(rename = "mapY")]
+    pub map_y: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
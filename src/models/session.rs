@@ -1,18 +1,82 @@
 // src/models/session.rs
-use chrono::{DateTime, Datelike, Timelike, Utc};
-use serde::{Deserialize, Serialize, Serializer};
-
-pub fn serialize_datetime_as_gmt<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    // Format to match "yyyy-MM-dd'T'HH:mm:ss"
-    let formatted = format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", 
-        date.year(), date.month(), date.day(),
-        date.hour(), date.minute(), date.second()
-    );
-    serializer.serialize_str(&formatted)
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::media::{MediaAsset, MediaFormat};
+use crate::models::time::{
+    deserialize_conference_time, deserialize_optional_conference_time,
+    serialize_conference_time, serialize_optional_conference_time,
+};
+
+// Attendees see published and cancelled sessions, the other statuses only show up in the
+// admin preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStatus {
+    Draft,
+    Scheduled,
+    Published,
+    Cancelled,
+    Archived,
+}
+
+impl SessionStatus {
+    pub const ALL: [SessionStatus; 5] = [
+        SessionStatus::Draft,
+        SessionStatus::Scheduled,
+        SessionStatus::Published,
+        SessionStatus::Cancelled,
+        SessionStatus::Archived,
+    ];
+    
+    pub fn from_name(value: &str) -> Option<SessionStatus> {
+        SessionStatus::ALL.into_iter().find(|status| status.name() == value)
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionStatus::Draft => "draft",
+            SessionStatus::Scheduled => "scheduled",
+            SessionStatus::Published => "published",
+            SessionStatus::Cancelled => "cancelled",
+            SessionStatus::Archived => "archived",
+        }
+    }
+    
+    pub fn is_public(&self) -> bool {
+        matches!(self, SessionStatus::Published | SessionStatus::Cancelled)
+    }
+    
+    // New sessions cannot start out cancelled or archived
+    pub fn is_initial(&self) -> bool {
+        matches!(self, SessionStatus::Draft | SessionStatus::Scheduled | SessionStatus::Published)
+    }
+    
+    // Once published a session can only be cancelled or archived, taking it back to draft
+    // would make it disappear from attendees' schedules without a trace
+    pub fn can_become(&self, next: SessionStatus) -> bool {
+        use SessionStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Scheduled | Published | Archived)
+                | (Scheduled, Draft | Published | Cancelled | Archived)
+                | (Published, Cancelled | Archived)
+                | (Cancelled, Published | Archived)
+        )
+    }
+    
+    // Names of the statuses a read returns, for `status = ANY($n)` filters
+    pub fn visible_names(preview: bool) -> Vec<String> {
+        SessionStatus::ALL
+            .into_iter()
+            .filter(|status| preview || status.is_public())
+            .map(|status| status.name().to_string())
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionStatusRequest {
+    pub status: SessionStatus,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,10 +85,18 @@ pub struct SessionInfo {
     pub title: String,
     pub description: Option<String>,
     
-    #[serde(rename = "startsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(
+        rename = "startsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub starts_at: DateTime<Utc>,
     
-    #[serde(rename = "endsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(
+        rename = "endsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub ends_at: DateTime<Utc>,
     
     #[serde(rename = "roomId")]
@@ -36,7 +108,7 @@ pub struct SessionInfo {
     #[serde(rename = "isPlenumSession")]
     pub is_plenum_session: bool,
     
-    pub status: String,
+    pub status: SessionStatus,
     
     #[serde(rename = "speakerIds")]
     pub speaker_ids: Vec<String>,
@@ -50,10 +122,18 @@ pub struct ConferenceSessionRequest {
     pub title: String,
     pub description: Option<String>,
     
-    #[serde(rename = "startsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(
+        rename = "startsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub starts_at: DateTime<Utc>,
     
-    #[serde(rename = "endsAt", serialize_with = "serialize_datetime_as_gmt")]
+    #[serde(
+        rename = "endsAt",
+        serialize_with = "serialize_conference_time",
+        deserialize_with = "deserialize_conference_time"
+    )]
     pub ends_at: DateTime<Utc>,
     
     #[serde(rename = "roomId")]
@@ -65,7 +145,7 @@ pub struct ConferenceSessionRequest {
     #[serde(rename = "isPlenumSession")]
     pub is_plenum_session: bool,
     
-    pub status: String,
+    pub status: SessionStatus,
     
     #[serde(rename = "speakerIds")]
     pub speaker_ids: Vec<String>,
@@ -100,6 +180,77 @@ pub struct SessionCategoriesRequest {
     pub category_id: i32,
 }
 
+// Replaces all speakers of a session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionSpeakersRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    #[serde(rename = "speakerIds")]
+    pub speaker_ids: Vec<String>,
+}
+
+// Replaces all categories of a session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionCategoryListRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    #[serde(rename = "categoryIds")]
+    pub category_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SessionWindows {
+    #[serde(
+        rename = "voteOpensAt",
+        default,
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub vote_opens_at: Option<DateTime<Utc>>,
+    
+    #[serde(
+        rename = "voteClosesAt",
+        default,
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub vote_closes_at: Option<DateTime<Utc>>,
+    
+    #[serde(
+        rename = "feedbackOpensAt",
+        default,
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub feedback_opens_at: Option<DateTime<Utc>>,
+    
+    #[serde(
+        rename = "feedbackClosesAt",
+        default,
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub feedback_closes_at: Option<DateTime<Utc>>,
+}
+
+impl SessionWindows {
+    // An inverted window would silently never open
+    pub fn validate(&self) -> Result<(), String> {
+        let windows = [
+            ("Voting", self.vote_opens_at, self.vote_closes_at),
+            ("Feedback", self.feedback_opens_at, self.feedback_closes_at),
+        ];
+        for (name, opens_at, closes_at) in windows {
+            if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at)
+                && opens_at >= closes_at
+            {
+                return Err(format!("{} window must open before it closes", name));
+            }
+        }
+        Ok(())
+    }
+}
+
 // SpeakerInfo
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpeakerInfo {
@@ -115,6 +266,46 @@ pub struct SpeakerInfo {
     pub profile_picture: Option<String>,
     #[serde(rename = "isTopSpeaker")]
     pub is_top_speaker: bool,
+    #[serde(default)]
+    pub company: Option<String>,
+    #[serde(rename = "jobTitle", default)]
+    pub job_title: Option<String>,
+    #[serde(default)]
+    pub pronouns: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub links: Vec<SpeakerLink>,
+    #[serde(default)]
+    pub photos: Vec<SpeakerPhoto>,
+    #[serde(rename = "photoAssetId", default, skip_serializing_if = "Option::is_none")]
+    pub photo_asset_id: Option<String>,
+    // Only set on speakers returned by /sync/speakers after they were deleted or merged
+    #[serde(
+        rename = "deletedAt",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_conference_time",
+        deserialize_with = "deserialize_optional_conference_time"
+    )]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(rename = "mergedInto", default, skip_serializing_if = "Option::is_none")]
+    pub merged_into: Option<String>,
+}
+
+// Public speaker page
+#[derive(Debug, Serialize)]
+pub struct SpeakerDetails {
+    #[serde(flatten)]
+    pub speaker: SpeakerInfo,
+    pub sessions: Vec<SessionInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerMergeRequest {
+    // Speaker that is folded into the one addressed by the path
+    #[serde(rename = "duplicateId")]
+    pub duplicate_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -130,4 +321,179 @@ pub struct ConferenceSpeakerRequest {
     pub profile_picture: Option<String>,
     #[serde(rename = "isTopSpeaker")]
     pub is_top_speaker: bool,
+    #[serde(default)]
+    pub company: Option<String>,
+    #[serde(rename = "jobTitle", default)]
+    pub job_title: Option<String>,
+    #[serde(default)]
+    pub pronouns: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    // Left out on update to keep the stored links and photos
+    #[serde(default)]
+    pub links: Option<Vec<SpeakerLink>>,
+    #[serde(default)]
+    pub photos: Option<Vec<SpeakerPhoto>>,
+    // An uploaded asset, replaces profilePicture and photos with its variants
+    #[serde(rename = "photoAssetId", default)]
+    pub photo_asset_id: Option<String>,
+}
+
+impl ConferenceSpeakerRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let urls = self
+            .links
+            .iter()
+            .flatten()
+            .map(|link| &link.url)
+            .chain(self.photos.iter().flatten().map(|photo| &photo.url))
+            .chain(self.profile_picture());
+        for url in urls {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(format!("'{}' is not an http(s) URL", url));
+            }
+        }
+        Ok(())
+    }
+
+    // Some clients send an empty profilePicture for a speaker without one
+    fn profile_picture(&self) -> Option<&String> {
+        self.profile_picture.as_ref().filter(|url| !url.is_empty())
+    }
+
+    // Clients that only know profilePicture still get a photo when just variants were sent
+    pub fn primary_picture(&self) -> Option<String> {
+        let photos = self.photos.as_deref().unwrap_or_default();
+        self.profile_picture().cloned().or_else(|| {
+            photos
+                .iter()
+                .find(|photo| photo.variant == PhotoVariant::Original)
+                .or(photos.first())
+                .map(|photo| photo.url.clone())
+        })
+    }
+
+    pub fn use_photo_asset(&mut self, asset: &MediaAsset) {
+        self.profile_picture = asset.url(PhotoVariant::Original, MediaFormat::Jpeg);
+        self.photos = Some(asset.speaker_photos());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Website,
+    Blog,
+    Github,
+    Linkedin,
+    Twitter,
+    Mastodon,
+    Bluesky,
+    Youtube,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerLink {
+    pub kind: LinkKind,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhotoVariant {
+    Original,
+    Square,
+    Thumbnail,
+}
+
+impl PhotoVariant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "original" => Some(PhotoVariant::Original),
+            "square" => Some(PhotoVariant::Square),
+            "thumbnail" => Some(PhotoVariant::Thumbnail),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhotoVariant::Original => "original",
+            PhotoVariant::Square => "square",
+            PhotoVariant::Thumbnail => "thumbnail",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerPhoto {
+    pub variant: PhotoVariant,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+}
+
+// Ids in a session write that do not exist in the session's conference
+#[derive(Debug, Default, Serialize)]
+pub struct InvalidReferences {
+    #[serde(rename = "speakerIds", skip_serializing_if = "Vec::is_empty")]
+    pub speaker_ids: Vec<String>,
+    
+    #[serde(rename = "categoryIds", skip_serializing_if = "Vec::is_empty")]
+    pub category_ids: Vec<i32>,
+    
+    #[serde(rename = "roomId", skip_serializing_if = "Option::is_none")]
+    pub room_id: Option<i32>,
+}
+
+impl InvalidReferences {
+    pub fn is_empty(&self) -> bool {
+        self.speaker_ids.is_empty() && self.category_ids.is_empty() && self.room_id.is_none()
+    }
+}
+
+impl std::fmt::Display for InvalidReferences {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if !self.speaker_ids.is_empty() {
+            parts.push(format!("speakerIds {}", self.speaker_ids.join(", ")));
+        }
+        if !self.category_ids.is_empty() {
+            let ids: Vec<String> = self.category_ids.iter().map(|id| id.to_string()).collect();
+            parts.push(format!("categoryIds {}", ids.join(", ")));
+        }
+        if let Some(room_id) = self.room_id {
+            parts.push(format!("roomId {}", room_id));
+        }
+        write!(f, "Unknown {}", parts.join("; "))
+    }
+}
+
+// Session about to start, as used for reminders
+pub struct UpcomingSession {
+    pub id: String,
+    pub title: String,
+    pub starts_at: DateTime<Utc>,
+    pub room_name: Option<String>,
+}
+
+// Where and when a session takes place, compared around changes to tell voters it moved
+pub struct SessionPlacement {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub room_id: Option<i32>,
+    pub room_name: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SessionPlacement {
+    pub fn moved_from(&self, before: &SessionPlacement) -> bool {
+        self.starts_at != before.starts_at || self.ends_at != before.ends_at || self.room_id != before.room_id
+    }
 }
\ No newline at end of file
@@ -0,0 +1,228 @@
+// src/models/time.rs
+//
+// Session times are stored as conference wall-clock time. On the wire they carry the offset
+// the conference time zone has at that moment for clients that ask for it, old app builds get
+// the naive "yyyy-MM-ddTHH:mm:ss" string they were written against.
+use std::future::Future;
+
+use chrono::{
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDateTime, Offset, SecondsFormat, TimeZone,
+    Utc,
+};
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+const LEGACY_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeSettings {
+    pub time_zone: Tz,
+    pub legacy_format: bool,
+}
+
+impl TimeSettings {
+    // Wall-clock time exactly as stored. Applies outside of requests and to times kept in JSON
+    // columns, so they read back the same whichever client wrote them.
+    pub const STORED: TimeSettings = TimeSettings {
+        time_zone: chrono_tz::UTC,
+        legacy_format: true,
+    };
+}
+
+tokio::task_local! {
+    static REQUEST_SETTINGS: TimeSettings;
+}
+
+// Requests run with the time zone of the conference they address and the format their client
+// asked for
+pub async fn scope<F: Future>(settings: TimeSettings, future: F) -> F::Output {
+    REQUEST_SETTINGS.scope(settings, future).await
+}
+
+pub fn current() -> TimeSettings {
+    REQUEST_SETTINGS.try_with(|settings| *settings).unwrap_or(TimeSettings::STORED)
+}
+
+// Attach the conference offset to a wall-clock time. A repeated hour when DST ends resolves to
+// its first occurrence, an hour skipped when DST starts keeps the offset from before the jump.
+pub fn with_conference_offset(wall_clock: DateTime<Utc>, time_zone: Tz) -> DateTime<FixedOffset> {
+    let local = wall_clock.naive_utc();
+    
+    match time_zone.from_local_datetime(&local) {
+        LocalResult::Single(time) => time.fixed_offset(),
+        LocalResult::Ambiguous(earliest, _) => earliest.fixed_offset(),
+        LocalResult::None => {
+            let offset = time_zone
+                .from_local_datetime(&(local - Duration::hours(1)))
+                .earliest()
+                .map(|before| before.offset().fix())
+                .unwrap_or_else(|| Utc.fix());
+            DateTime::from_naive_utc_and_offset(local - offset, offset)
+        }
+    }
+}
+
+pub fn to_wall_clock(time: DateTime<FixedOffset>, time_zone: Tz) -> DateTime<Utc> {
+    time.with_timezone(&time_zone).naive_local().and_utc()
+}
+
+fn format_conference_time(date: &DateTime<Utc>) -> String {
+    let settings = current();
+    if settings.legacy_format {
+        date.format(LEGACY_FORMAT).to_string()
+    } else {
+        with_conference_offset(*date, settings.time_zone).to_rfc3339_opts(SecondsFormat::Secs, false)
+    }
+}
+
+// Naive input is wall-clock time already. Input with an offset is converted to the conference
+// time zone, except in legacy mode where it is stored as-is like older builds always did.
+fn parse_conference_time(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(local) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Ok(local.and_utc());
+    }
+    
+    let time = DateTime::parse_from_rfc3339(value)
+        .map_err(|e| format!("Invalid date time '{}': {}", value, e))?;
+    let settings = current();
+    if settings.legacy_format {
+        Ok(time.with_timezone(&Utc))
+    } else {
+        Ok(to_wall_clock(time, settings.time_zone))
+    }
+}
+
+pub fn serialize_conference_time<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format_conference_time(date))
+}
+
+pub fn serialize_optional_conference_time<S>(
+    date: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match date {
+        Some(date) => serializer.serialize_some(&format_conference_time(date)),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize_conference_time<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_conference_time(&value).map_err(de::Error::custom)
+}
+
+pub fn deserialize_optional_conference_time<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_conference_time(&value).map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMSTERDAM: Tz = chrono_tz::Europe::Amsterdam;
+
+    fn wall_clock(value: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap().and_utc()
+    }
+
+    fn with_settings<R>(legacy_format: bool, f: impl FnOnce() -> R) -> R {
+        let settings = TimeSettings {
+            time_zone: AMSTERDAM,
+            legacy_format,
+        };
+        REQUEST_SETTINGS.sync_scope(settings, f)
+    }
+
+    #[test]
+    fn offset_follows_summer_and_winter_time() {
+        assert_eq!(
+            with_conference_offset(wall_clock("2025-05-22T10:00:00"), AMSTERDAM).to_rfc3339(),
+            "2025-05-22T10:00:00+02:00"
+        );
+        assert_eq!(
+            with_conference_offset(wall_clock("2025-01-15T10:00:00"), AMSTERDAM).to_rfc3339(),
+            "2025-01-15T10:00:00+01:00"
+        );
+    }
+
+    #[test]
+    fn skipped_hour_keeps_the_offset_from_before_the_jump() {
+        // Clocks go from 02:00 to 03:00 on 2025-03-30
+        let time = with_conference_offset(wall_clock("2025-03-30T02:30:00"), AMSTERDAM);
+
+        assert_eq!(time.to_rfc3339(), "2025-03-30T02:30:00+01:00");
+        assert_eq!(time.with_timezone(&Utc), Utc.with_ymd_and_hms(2025, 3, 30, 1, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn repeated_hour_resolves_to_its_first_occurrence() {
+        // Clocks go from 03:00 back to 02:00 on 2025-10-26
+        let time = with_conference_offset(wall_clock("2025-10-26T02:30:00"), AMSTERDAM);
+
+        assert_eq!(time.to_rfc3339(), "2025-10-26T02:30:00+02:00");
+        assert_eq!(to_wall_clock(time, AMSTERDAM), wall_clock("2025-10-26T02:30:00"));
+    }
+
+    #[test]
+    fn second_occurrence_of_a_repeated_hour_maps_to_the_same_wall_clock() {
+        let second = DateTime::parse_from_rfc3339("2025-10-26T02:30:00+01:00").unwrap();
+
+        assert_eq!(to_wall_clock(second, AMSTERDAM), wall_clock("2025-10-26T02:30:00"));
+    }
+
+    #[test]
+    fn legacy_format_is_naive() {
+        let time = wall_clock("2025-05-22T10:00:00");
+
+        assert_eq!(with_settings(true, || format_conference_time(&time)), "2025-05-22T10:00:00");
+        assert_eq!(
+            with_settings(false, || format_conference_time(&time)),
+            "2025-05-22T10:00:00+02:00"
+        );
+    }
+
+    #[test]
+    fn stored_format_applies_outside_of_requests() {
+        assert_eq!(current(), TimeSettings::STORED);
+        assert_eq!(format_conference_time(&wall_clock("2025-03-30T02:30:00")), "2025-03-30T02:30:00");
+    }
+
+    #[test]
+    fn naive_input_is_wall_clock_time_in_every_format() {
+        for legacy_format in [true, false] {
+            assert_eq!(
+                with_settings(legacy_format, || parse_conference_time("2025-05-22T10:00:00")),
+                Ok(wall_clock("2025-05-22T10:00:00"))
+            );
+        }
+    }
+
+    #[test]
+    fn input_with_an_offset_is_converted_unless_legacy() {
+        assert_eq!(
+            with_settings(false, || parse_conference_time("2025-05-22T08:00:00Z")),
+            Ok(wall_clock("2025-05-22T10:00:00"))
+        );
+        assert_eq!(
+            with_settings(true, || parse_conference_time("2025-05-22T08:00:00Z")),
+            Ok(wall_clock("2025-05-22T08:00:00"))
+        );
+    }
+}
\ No newline at end of file
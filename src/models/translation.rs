@@ -0,0 +1,76 @@
+// src/models/translation.rs
+use serde::{Deserialize, Serialize};
+
+// Fields left out fall back to the default locale
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionTranslation {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerTranslation {
+    pub bio: Option<String>,
+    #[serde(rename = "tagLine")]
+    pub tag_line: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryTranslation {
+    pub title: Option<String>,
+}
+
+impl SessionTranslation {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none()
+    }
+}
+
+impl SpeakerTranslation {
+    pub fn is_empty(&self) -> bool {
+        self.bio.is_none() && self.tag_line.is_none()
+    }
+}
+
+impl CategoryTranslation {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+    }
+}
+
+// Canonical form of a BCP 47 language tag ("pt_br" becomes "pt-BR"), None if it is not one.
+// Only the subtags used for content locales are accepted: language, script, region and variants.
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let mut subtags = tag.split(['-', '_']);
+
+    let language = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut normalized = language.to_ascii_lowercase();
+
+    for subtag in subtags {
+        let alphabetic = subtag.chars().all(|c| c.is_ascii_alphabetic());
+        let formatted = match subtag.len() {
+            // Region
+            2 if alphabetic => subtag.to_ascii_uppercase(),
+            3 if subtag.chars().all(|c| c.is_ascii_digit()) => subtag.to_string(),
+            // Script
+            4 if alphabetic => {
+                let (first, rest) = subtag.split_at(1);
+                first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+            }
+            // Variant
+            5..=8 if subtag.chars().all(|c| c.is_ascii_alphanumeric()) => subtag.to_ascii_lowercase(),
+            _ => return None,
+        };
+        normalized.push('-');
+        normalized.push_str(&formatted);
+    }
+
+    Some(normalized)
+}
+
+pub fn language_of(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
\ No newline at end of file
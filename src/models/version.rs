@@ -0,0 +1,60 @@
+// src/models/version.rs
+use chrono::{DateTime, Utc};
+
+// Summary of a table's contents. Any write moves `last_modified`, deletes through the
+// time recorded in table_deletions, and deletes also change `rows`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableVersion {
+    pub last_modified: Option<DateTime<Utc>>,
+    pub rows: i64,
+}
+
+// Versions of the tables a conference's schedule is composed from
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleVersions {
+    pub sessions: TableVersion,
+    pub speakers: TableVersion,
+    pub rooms: TableVersion,
+    pub categories: TableVersion,
+    pub category_types: TableVersion,
+    pub session_speakers: TableVersion,
+    pub session_categories: TableVersion,
+}
+
+impl ScheduleVersions {
+    pub fn all(&self) -> [TableVersion; 7] {
+        [
+            self.sessions,
+            self.speakers,
+            self.rooms,
+            self.categories,
+            self.category_types,
+            self.session_speakers,
+            self.session_categories,
+        ]
+    }
+}
+
+// Versions of the tables the podcast listing is composed from
+#[derive(Debug, Clone, Copy)]
+pub struct PodcastVersions {
+    pub channels: TableVersion,
+    pub episodes: TableVersion,
+    pub channel_categories: TableVersion,
+    pub episode_categories: TableVersion,
+    pub channel_category_map: TableVersion,
+    pub episode_category_map: TableVersion,
+}
+
+impl PodcastVersions {
+    pub fn all(&self) -> [TableVersion; 6] {
+        [
+            self.channels,
+            self.episodes,
+            self.channel_categories,
+            self.episode_categories,
+            self.channel_category_map,
+            self.episode_category_map,
+        ]
+    }
+}
\ No newline at end of file
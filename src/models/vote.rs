@@ -3,17 +3,20 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum Score {
-    GOOD = 1,
-    OK = 0,
-    BAD = -1,
+    #[serde(rename = "GOOD")]
+    Good = 1,
+    #[serde(rename = "OK")]
+    Ok = 0,
+    #[serde(rename = "BAD")]
+    Bad = -1,
 }
 
 impl Score {
     pub fn from_value(value: i32) -> Option<Score> {
         match value {
-            1 => Some(Score::GOOD),
-            0 => Some(Score::OK),
-            -1 => Some(Score::BAD),
+            1 => Some(Score::Good),
+            0 => Some(Score::Ok),
+            -1 => Some(Score::Bad),
             _ => None,
         }
     }
@@ -1,36 +1,65 @@
 // src/routes/admin.rs
-use actix_web::{get, post, web, HttpResponse};
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono_tz::Tz;
+use serde::Deserialize;
 
-use crate::auth::{validate_admin_secret, KotlinConfPrincipal};
+use crate::auth::{hash_secret, validate_admin_secret, validate_conference_admin, Actor, KotlinConfPrincipal};
 use crate::config::AppConfig;
-use crate::db::{sessions, speakers, rooms, categories};
+use crate::db::{clock, conferences, sessions, speakers, rooms, categories, users};
 use crate::error::ServiceError;
-use crate::models::category::ConferenceCategoriesRequest;
+use crate::models::audit::AuditEntity;
+use crate::models::category::{CategoryTypeRequest, ConferenceCategoriesRequest};
+use crate::models::edition::{ConferenceEdition, ConferenceEditionRequest};
+use crate::models::event::{ChangeAction, ChangeEntity};
 use crate::models::room::ConferenceRoomRequest;
-use crate::models::session::ConferenceSpeakerRequest;
 use crate::models::session::ConferenceSessionRequest;
-use crate::services::admin;
+use crate::models::session::{ConferenceSpeakerRequest, SpeakerMergeRequest};
+use crate::models::session::{SessionStatus, SessionStatusRequest, SessionWindows};
+use crate::models::translation::normalize_locale;
+use crate::services::audit::{self, PendingChange};
+use crate::services::clock::Clock;
+use crate::services::events::EventBus;
+use crate::services::media::MediaStore;
+use crate::services::read_cache::ReadModelCache;
+use crate::services::notifications;
 
+#[derive(Debug, Deserialize)]
+pub struct TimeQuery {
+    pub cohort: Option<String>,
+}
 
+// Without a token this is the time everyone sees, with a token it includes the user's cohort
 #[get("/time")]
-async fn get_time() -> HttpResponse {
-    HttpResponse::Ok().json(admin::now())
+async fn get_time(
+    principal: Option<KotlinConfPrincipal>,
+    conference: web::ReqData<ConferenceEdition>,
+    clock: web::Data<Clock>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let user_id = principal.as_ref().map(|p| p.token.as_str());
+    let now = clock.local_now_millis(&pool, &conference, user_id).await?;
+    Ok(HttpResponse::Ok().json(now))
 }
 
 #[post("/time/{timestamp}")]
 async fn set_time(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    clock: web::Data<Clock>,
     path: web::Path<String>,
+    query: web::Query<TimeQuery>,
+    pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
+    let cohort = query.cohort.as_deref().unwrap_or(clock::GLOBAL_COHORT);
     let timestamp = path.into_inner();
     if timestamp == "null" {
-        admin::update_time(None);
+        clock.set_simulated_time(&pool, &conference, cohort, None).await?;
     } else {
         match timestamp.parse::<i64>() {
-            Ok(time) => admin::update_time(Some(time)),
+            Ok(time) => clock.set_simulated_time(&pool, &conference, cohort, Some(time)).await?,
             Err(_) => return Err(ServiceError::BadRequest("Invalid timestamp".to_string())),
         }
     }
@@ -38,54 +67,645 @@ async fn set_time(
     Ok(HttpResponse::Ok().finish())
 }
 
+#[put("/admin/cohorts/{cohort}/users/{uuid}")]
+async fn add_cohort_user(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<(String, String)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (cohort, user_id) = path.into_inner();
+    if cohort == clock::GLOBAL_COHORT {
+        return Err(ServiceError::BadRequest("Cohort name must not be empty".to_string()));
+    }
+    if !users::validate_user(&pool, &user_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    
+    clock::add_cohort_member(&pool, conference.id, &cohort, &user_id).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/cohorts/{cohort}/users/{uuid}")]
+async fn remove_cohort_user(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<(String, String)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (cohort, user_id) = path.into_inner();
+    if !clock::remove_cohort_member(&pool, conference.id, &cohort, &user_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[post("/admin/session")]
+#[allow(clippy::too_many_arguments)]
 async fn add_admin_session(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     session: web::Json<ConferenceSessionRequest>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    let session_id = sessions::add_session(&pool, &session).await?;
+    let mut tx = pool.begin().await?;
+    let session_id = sessions::add_session(&mut tx, conference.id, &session).await?;
+    PendingChange::created(conference.id, AuditEntity::Session, &session_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Created, vec![session_id.clone()])
+        .await;
     Ok(HttpResponse::Created().json(session_id))
 }
 
+#[put("/admin/sessions/{id}/status")]
+#[allow(clippy::too_many_arguments)]
+async fn set_admin_session_status(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<String>,
+    request: web::Json<SessionStatusRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let session_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_id).await?;
+    let (previous, title) = sessions::set_session_status(&mut tx, conference.id, &session_id, request.status)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    if previous == request.status {
+        return Ok(HttpResponse::Ok().finish());
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, vec![session_id.clone()])
+        .await;
+    
+    // Only attendees who could see the session hear about its cancellation
+    if request.status == SessionStatus::Cancelled
+        && previous.is_public()
+        && let Err(e) = notifications::notify_session_cancelled(&pool, conference.id, &session_id, &title).await
+    {
+        log::error!("Failed to notify cancellation of session {}: {}", session_id, e);
+    }
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/admin/sessions/{id}/windows")]
+async fn get_admin_session_windows(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let session_id = path.into_inner();
+    if sessions::get_session_by_id(&pool, conference.id, &session_id, true).await?.is_none() {
+        return Err(ServiceError::NotFound);
+    }
+    
+    let windows = sessions::get_session_windows(&pool, conference.id, &session_id).await?;
+    Ok(HttpResponse::Ok().json(windows))
+}
+
+#[put("/admin/sessions/{id}/windows")]
+#[allow(clippy::too_many_arguments)]
+async fn set_admin_session_windows(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<String>,
+    windows: web::Json<SessionWindows>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    windows.validate().map_err(ServiceError::BadRequest)?;
+    
+    let session_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_id).await?;
+    if !sessions::set_session_windows(&mut tx, conference.id, &session_id, &windows).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, vec![session_id])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
 #[post("/admin/speakers")]
+#[allow(clippy::too_many_arguments)]
 async fn add_admin_speaker(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    media: web::Data<MediaStore>,
     speaker: web::Json<ConferenceSpeakerRequest>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    speaker.validate().map_err(ServiceError::BadRequest)?;
     
-    let speaker_id = speakers::add_speaker(&pool, &speaker).await?;
+    let mut speaker = speaker.into_inner();
+    if let Some(asset_id) = &speaker.photo_asset_id {
+        let asset = media.require_asset(&pool, asset_id).await?;
+        speaker.use_photo_asset(&asset);
+    }
+    let mut tx = pool.begin().await?;
+    let speaker_id = speakers::add_speaker(&mut tx, conference.id, &speaker).await?;
+    PendingChange::created(conference.id, AuditEntity::Speaker, &speaker_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Created, vec![speaker_id.clone()])
+        .await;
     Ok(HttpResponse::Created().json(speaker_id))
 }
 
+#[put("/admin/speakers/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn update_admin_speaker(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    media: web::Data<MediaStore>,
+    path: web::Path<String>,
+    speaker: web::Json<ConferenceSpeakerRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    speaker.validate().map_err(ServiceError::BadRequest)?;
+    
+    let mut speaker = speaker.into_inner();
+    if let Some(asset_id) = &speaker.photo_asset_id {
+        let asset = media.require_asset(&pool, asset_id).await?;
+        speaker.use_photo_asset(&asset);
+    }
+    let speaker_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Speaker, &speaker_id).await?;
+    if !speakers::update_speaker(&mut tx, conference.id, &speaker_id, &speaker).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Updated, vec![speaker_id])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/speakers/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_admin_speaker(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let speaker_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Speaker, &speaker_id).await?;
+    let session_changes =
+        audit::track_related_sessions(&mut tx, conference.id, AuditEntity::Speaker, &speaker_id).await?;
+    let session_ids = speakers::delete_speaker(&mut tx, conference.id, &speaker_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    change.record(&mut tx, &actor).await?;
+    audit::record_all(session_changes, &mut tx, &actor).await?;
+    tx.commit().await?;
+    
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Deleted, vec![speaker_id])
+        .await;
+    if !session_ids.is_empty() {
+        events
+            .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, session_ids)
+            .await;
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Folds a duplicate speaker into the one addressed by the path
+#[post("/admin/speakers/{id}/merge")]
+#[allow(clippy::too_many_arguments)]
+async fn merge_admin_speaker(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<String>,
+    merge: web::Json<SpeakerMergeRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let canonical_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let mut changes = vec![
+        audit::track(&mut tx, conference.id, AuditEntity::Speaker, &canonical_id).await?,
+        audit::track(&mut tx, conference.id, AuditEntity::Speaker, &merge.duplicate_id).await?,
+    ];
+    changes.extend(
+        audit::track_related_sessions(&mut tx, conference.id, AuditEntity::Speaker, &merge.duplicate_id).await?,
+    );
+    let session_ids = speakers::merge_speakers(&mut tx, conference.id, &canonical_id, &merge.duplicate_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    audit::record_all(changes, &mut tx, &actor).await?;
+    tx.commit().await?;
+    
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Deleted, vec![merge.duplicate_id.clone()])
+        .await;
+    if !session_ids.is_empty() {
+        events
+            .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, session_ids.clone())
+            .await;
+    }
+    Ok(HttpResponse::Ok().json(session_ids))
+}
+
 #[post("/admin/rooms")]
+#[allow(clippy::too_many_arguments)]
 async fn add_admin_room(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     room: web::Json<ConferenceRoomRequest>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    let room_id = rooms::add_room(&pool, &room).await?;
+    let mut tx = pool.begin().await?;
+    let room_id = rooms::add_room(&mut tx, conference.id, &room).await?;
+    PendingChange::created(conference.id, AuditEntity::Room, room_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Rooms, ChangeAction::Created, vec![room_id.to_string()])
+        .await;
     Ok(HttpResponse::Created().json(room_id))
 }
 
+#[put("/admin/rooms/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn update_admin_room(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    room: web::Json<ConferenceRoomRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let room_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Room, room_id).await?;
+    if !rooms::update_room(&mut tx, conference.id, room_id, &room).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Rooms, ChangeAction::Updated, vec![room_id.to_string()])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/rooms/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_admin_room(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let room_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Room, room_id).await?;
+    let session_changes =
+        audit::track_related_sessions(&mut tx, conference.id, AuditEntity::Room, room_id).await?;
+    let detached = rooms::delete_room(&mut tx, conference.id, room_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    change.record(&mut tx, &actor).await?;
+    audit::record_all(session_changes, &mut tx, &actor).await?;
+    tx.commit().await?;
+    
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Rooms, ChangeAction::Deleted, vec![room_id.to_string()])
+        .await;
+    if !detached.is_empty() {
+        events
+            .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, detached.clone())
+            .await;
+        // Sessions held in the room no longer have one, let their voters know
+        notifications::announce_moved_sessions(&pool, conference.id, &detached, &[]).await;
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[post("/admin/categories")]
+#[allow(clippy::too_many_arguments)]
 async fn add_admin_category(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     category: web::Json<ConferenceCategoriesRequest>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    let category_id = categories::add_category(&pool, &category).await?;
+    let mut tx = pool.begin().await?;
+    let category_id = categories::add_category(&mut tx, conference.id, &category).await?;
+    PendingChange::created(conference.id, AuditEntity::Category, category_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Categories, ChangeAction::Created, vec![category_id.to_string()])
+        .await;
     Ok(HttpResponse::Created().json(category_id))
+}
+
+#[put("/admin/categories/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn update_admin_category(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    category: web::Json<ConferenceCategoriesRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let category_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Category, category_id).await?;
+    if !categories::update_category(&mut tx, conference.id, category_id, &category).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Categories, ChangeAction::Updated, vec![category_id.to_string()])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/admin/category-types")]
+async fn get_admin_category_types(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let types = categories::get_category_types(&pool, conference.id).await?;
+    Ok(HttpResponse::Ok().json(types))
+}
+
+// Types only show up in /conference until categories use them, so there is nothing to sync
+#[post("/admin/category-types")]
+async fn add_admin_category_type(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    cache: web::Data<ReadModelCache>,
+    category_type: web::Json<CategoryTypeRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let mut tx = pool.begin().await?;
+    let type_id = categories::add_category_type(&mut tx, conference.id, &category_type).await?;
+    PendingChange::created(conference.id, AuditEntity::CategoryType, type_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    Ok(HttpResponse::Created().json(type_id))
+}
+
+#[put("/admin/category-types/{id}")]
+#[allow(clippy::too_many_arguments)]
+async fn update_admin_category_type(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    category_type: web::Json<CategoryTypeRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let type_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::CategoryType, type_id).await?;
+    if !categories::update_category_type(&mut tx, conference.id, type_id, &category_type).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    // Every category of the type now carries the new name
+    events
+        .publish(&pool, conference.id, ChangeEntity::Categories, ChangeAction::Updated, Vec::new())
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/category-types/{id}")]
+async fn delete_admin_category_type(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let type_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::CategoryType, type_id).await?;
+    if !categories::delete_category_type(&mut tx, conference.id, type_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/admin/cache")]
+async fn get_admin_cache_stats(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    cache: web::Data<ReadModelCache>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    Ok(HttpResponse::Ok().json(cache.stats(conference.id)))
+}
+
+#[get("/admin/conferences")]
+async fn get_admin_conferences(
+    principal: KotlinConfPrincipal,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_admin_secret(&principal, &config.service.secret).await?;
+    
+    let conferences = conferences::get_all_conferences(&pool).await?;
+    Ok(HttpResponse::Ok().json(conferences))
+}
+
+#[post("/admin/conferences")]
+async fn add_admin_conference(
+    principal: KotlinConfPrincipal,
+    config: web::Data<AppConfig>,
+    conference: web::Json<ConferenceEditionRequest>,
+    pool: web::Data<sqlx::PgPool>,
+    cache: web::Data<ReadModelCache>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference(&conference)?;
+    
+    if conferences::get_conference_by_slug(&pool, &conference.slug).await?.is_some() {
+        return Err(ServiceError::BadRequest(format!("Conference '{}' already exists", conference.slug)));
+    }
+    
+    let admin_secret_hash = conference.admin_secret.as_deref().map(hash_secret);
+    let conference_id = conferences::add_conference(&pool, &conference, admin_secret_hash).await?;
+    cache.invalidate_editions();
+    Ok(HttpResponse::Created().json(conference_id))
+}
+
+#[put("/admin/conferences/{slug}")]
+async fn update_admin_conference(
+    principal: KotlinConfPrincipal,
+    config: web::Data<AppConfig>,
+    path: web::Path<String>,
+    conference: web::Json<ConferenceEditionRequest>,
+    pool: web::Data<sqlx::PgPool>,
+    cache: web::Data<ReadModelCache>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference(&conference)?;
+    
+    let slug = path.into_inner();
+    if conference.slug != slug
+        && conferences::get_conference_by_slug(&pool, &conference.slug).await?.is_some()
+    {
+        return Err(ServiceError::BadRequest(format!("Conference '{}' already exists", conference.slug)));
+    }
+    
+    let admin_secret_hash = conference.admin_secret.as_deref().map(hash_secret);
+    if !conferences::update_conference(&pool, &slug, &conference, admin_secret_hash).await? {
+        return Err(ServiceError::NotFound);
+    }
+    cache.invalidate_editions();
+    Ok(HttpResponse::Ok().finish())
+}
+
+fn validate_conference(conference: &ConferenceEditionRequest) -> Result<(), ServiceError> {
+    let valid_slug = !conference.slug.is_empty()
+        && conference
+            .slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_slug {
+        return Err(ServiceError::BadRequest(
+            "Slug may only contain lowercase letters, digits and dashes".to_string(),
+        ));
+    }
+    
+    if let Some(time_zone) = &conference.time_zone
+        && time_zone.parse::<Tz>().is_err()
+    {
+        return Err(ServiceError::BadRequest(format!("Unknown time zone: {}", time_zone)));
+    }
+    
+    if let Some(locale) = &conference.settings.default_locale
+        && normalize_locale(locale).as_ref() != Some(locale)
+    {
+        return Err(ServiceError::BadRequest(format!("Invalid default locale: {}", locale)));
+    }
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+// src/routes/audit.rs
+use actix_web::{get, post, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::auth::{validate_conference_admin, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{audit, sessions};
+use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::ChangeAction;
+use crate::services::audit as audit_service;
+use crate::services::events::EventBus;
+use crate::services::notifications;
+use crate::services::read_cache::ReadModelCache;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+fn parse_entity(name: &str) -> Result<AuditEntity, ServiceError> {
+    AuditEntity::from_name(name).ok_or(ServiceError::NotFound)
+}
+
+#[get("/admin/audit")]
+async fn get_audit_log(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    query: web::Query<AuditQuery>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let entries = audit::get_recent(&pool, conference.id, query.before, limit).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[get("/admin/audit/{entity}/{id}")]
+async fn get_audit_history(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<(String, String)>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (entity, entity_id) = path.into_inner();
+    let entity = parse_entity(&entity)?;
+    let entries = audit::get_history(&pool, conference.id, entity, &entity_id).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[get("/admin/audit/{entity}/{id}/{version}")]
+async fn get_audit_version(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<(String, String, i32)>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (entity, entity_id, version) = path.into_inner();
+    let entity = parse_entity(&entity)?;
+    let entry = audit::get_version(&pool, conference.id, entity, &entity_id, version)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+// Brings the entity back to the state it had right after the given version
+#[post("/admin/audit/{entity}/{id}/{version}/restore")]
+#[allow(clippy::too_many_arguments)]
+async fn restore_audit_version(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<(String, String, i32)>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (entity, entity_id, version) = path.into_inner();
+    let entity = parse_entity(&entity)?;
+    let entry = audit::get_version(&pool, conference.id, entity, &entity_id, version)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    let state = entry.after.ok_or_else(|| {
+        ServiceError::BadRequest("This version deleted the entity, restore an earlier one".to_string())
+    })?;
+    
+    // Restoring a session can put it back in another room or time slot
+    let before = match entity {
+        AuditEntity::Session => {
+            sessions::get_session_placements(&pool, conference.id, std::slice::from_ref(&entity_id)).await?
+        }
+        _ => vec![],
+    };
+    let mut tx = pool.begin().await?;
+    let change = audit_service::track(&mut tx, conference.id, entity, &entity_id).await?;
+    audit::restore(&mut tx, conference.id, entity, &entity_id, &state).await?;
+    change.record_restore(&mut tx, &actor).await?;
+    tx.commit().await?;
+    if !before.is_empty() {
+        notifications::announce_moved_sessions(&pool, conference.id, std::slice::from_ref(&entity_id), &before)
+            .await;
+    }
+    
+    cache.invalidate_conference(conference.id);
+    // Category types are not served on their own, their categories changed with them
+    let ids = match entity {
+        AuditEntity::CategoryType => vec![],
+        _ => vec![entity_id],
+    };
+    if let Some(change_entity) = entity.change_entity() {
+        events
+            .publish(&pool, conference.id, change_entity, ChangeAction::Updated, ids)
+            .await;
+    }
+    
+    Ok(HttpResponse::Ok().finish())
+}
\ No newline at end of file
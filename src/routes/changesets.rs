@@ -0,0 +1,197 @@
+// src/routes/changesets.rs
+use actix_web::{delete, get, post, put, web, HttpResponse};
+
+use crate::auth::{validate_conference_admin, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::changesets;
+use crate::error::ServiceError;
+use crate::models::changeset::{
+    ChangeOperation, ChangesetOperation, ChangesetRequest, ChangesetScheduleRequest,
+};
+use crate::models::edition::ConferenceEdition;
+use crate::services::changesets as changeset_service;
+use crate::services::events::EventBus;
+use crate::services::media::MediaStore;
+use crate::services::read_cache::ReadModelCache;
+
+#[get("/admin/changesets")]
+async fn get_changesets(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let changesets = changesets::get_changesets(&pool, conference.id).await?;
+    Ok(HttpResponse::Ok().json(changesets))
+}
+
+#[post("/admin/changesets")]
+async fn add_changeset(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    request: web::Json<ChangesetRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    if request.title.trim().is_empty() {
+        return Err(ServiceError::BadRequest("A changeset needs a title".to_string()));
+    }
+    
+    let changeset_id = changesets::add_changeset(&pool, conference.id, request.title.trim(), &actor.name).await?;
+    Ok(HttpResponse::Created().json(changeset_id))
+}
+
+#[get("/admin/changesets/{id}")]
+async fn get_changeset(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let changeset = changesets::get_changeset(&pool, conference.id, path.into_inner())
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(HttpResponse::Ok().json(changeset))
+}
+
+#[delete("/admin/changesets/{id}")]
+async fn delete_changeset(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    if !changesets::delete_changeset(&pool, conference.id, path.into_inner()).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Checked like the immediate admin endpoints, references to other rows are only checked
+// when the changeset is previewed or applied
+#[post("/admin/changesets/{id}/operations")]
+#[allow(clippy::too_many_arguments)]
+async fn add_changeset_operation(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    media: web::Data<MediaStore>,
+    path: web::Path<i32>,
+    operation: web::Json<ChangeOperation>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let mut operation = operation.into_inner();
+    if let ChangeOperation::CreateSpeaker { speaker, .. } | ChangeOperation::UpdateSpeaker { speaker, .. } =
+        &mut operation
+    {
+        speaker.validate().map_err(ServiceError::BadRequest)?;
+        if let Some(asset_id) = &speaker.photo_asset_id {
+            let asset = media.require_asset(&pool, asset_id).await?;
+            speaker.use_photo_asset(&asset);
+        }
+    }
+    operation.assign_id();
+    
+    let operation_id = changesets::add_operation(&pool, conference.id, path.into_inner(), &operation)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(HttpResponse::Created().json(ChangesetOperation {
+        id: operation_id,
+        operation,
+    }))
+}
+
+#[delete("/admin/changesets/{id}/operations/{operation_id}")]
+async fn delete_changeset_operation(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<(i32, i32)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (changeset_id, operation_id) = path.into_inner();
+    if !changesets::delete_operation(&pool, conference.id, changeset_id, operation_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/admin/changesets/{id}/preview")]
+async fn preview_changeset(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let diff = changeset_service::preview(&pool, conference.id, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
+
+#[post("/admin/changesets/{id}/apply")]
+#[allow(clippy::too_many_arguments)]
+async fn apply_changeset(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let diff = changeset_service::apply(&pool, &events, &cache, conference.id, path.into_inner(), &actor).await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
+
+// The changeset goes live once the conference clock reaches `applyAt`
+#[put("/admin/changesets/{id}/schedule")]
+async fn schedule_changeset(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    schedule: web::Json<ChangesetScheduleRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    if !changesets::set_schedule(&pool, conference.id, path.into_inner(), Some(schedule.apply_at)).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/changesets/{id}/schedule")]
+async fn unschedule_changeset(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    if !changesets::set_schedule(&pool, conference.id, path.into_inner(), None).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
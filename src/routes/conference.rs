@@ -1,13 +1,42 @@
 // src/routes/conference.rs
-use actix_web::{get, web, HttpResponse};
+use actix_web::{get, web, HttpRequest, HttpResponse};
 
-use crate::db::sessions;
+use crate::auth::{validate_conference_admin, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{sessions, versions};
 use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::routes::sessions::PreviewQuery;
+use crate::services::http_cache::{self, Validators};
+use crate::services::locale::ContentLanguage;
+use crate::services::read_cache::ReadModelCache;
 
 #[get("/conference")]
+#[allow(clippy::too_many_arguments)]
 async fn get_conference(
+    req: HttpRequest,
+    principal: Option<KotlinConfPrincipal>,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    cache: web::Data<ReadModelCache>,
+    query: web::Query<PreviewQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    let conference = sessions::get_conference_data(&pool).await?;
-    Ok(HttpResponse::Ok().json(conference))
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
+    if query.preview {
+        let principal = principal.ok_or(ServiceError::Unauthorized)?;
+        validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+        let data = sessions::get_conference_data(&pool, conference.id, language.translation(), true).await?;
+        return Ok(http_cache::uncached(&language).json(data));
+    }
+    
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference_in(&conference, &language, &versions.all());
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.conference));
+    }
+    
+    let data = cache.conference_data(&pool, conference.id, language.translation()).await?;
+    Ok(validators.ok(&config.cache.conference).json(&*data))
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+// src/routes/events.rs
+use std::time::Duration;
+
+use actix_web::{get, http::header::ContentEncoding, web, HttpResponse};
+use futures::stream;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::auth::{validate_user, KotlinConfPrincipal};
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::ChangeEntity;
+use crate::services::events::EventBus;
+
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+// Server-Sent Events stream of schedule changes for the conference, for signed-up users. A
+// "resync" event is sent when this client fell too far behind, it should then re-sync everything.
+#[get("/events")]
+async fn get_events(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    events: web::Data<EventBus>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    let conference_id = conference.id;
+    let receiver = events.subscribe();
+
+    let body = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            let message = match tokio::time::timeout(KEEP_ALIVE, receiver.recv()).await {
+                Ok(Ok(event))
+                    if event.conference_id == conference_id
+                        || event.entity == ChangeEntity::Podcasts =>
+                {
+                    match serde_json::to_string(&event) {
+                        Ok(data) => format!("event: change\ndata: {}\n\n", data),
+                        Err(e) => {
+                            log::error!("Failed to serialize change event: {}", e);
+                            continue;
+                        }
+                    }
+                }
+                Ok(Ok(_)) => continue,
+                Ok(Err(RecvError::Lagged(missed))) => {
+                    format!("event: resync\ndata: {}\n\n", missed)
+                }
+                Ok(Err(RecvError::Closed)) => return None,
+                Err(_) => ": keep-alive\n\n".to_string(),
+            };
+
+            return Some((Ok::<_, actix_web::Error>(web::Bytes::from(message)), receiver));
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        // Keeps the compression middleware from buffering the stream
+        .insert_header(ContentEncoding::Identity)
+        .streaming(body))
+}
\ No newline at end of file
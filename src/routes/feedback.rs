@@ -1,16 +1,59 @@
 // src/routes/feedback.rs
-use actix_web::{get, post, web, HttpResponse};
+use actix_web::{delete, get, post, web, HttpResponse};
 use chrono::Utc;
+use serde::Deserialize;
 
-use crate::auth::{validate_admin_secret, validate_user, KotlinConfPrincipal};
+use crate::auth::{validate_conference_admin, validate_user, KotlinConfPrincipal};
 use crate::config::AppConfig;
-use crate::db::feedback;
+use crate::db::{feedback, sessions};
 use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
 use crate::models::feedback::FeedbackInfo;
+use crate::services::clock::Clock;
+use crate::services::windows::check_window;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct SummaryQuery {
+    pub page: Option<i64>,
+    #[serde(rename = "pageSize")]
+    pub page_size: Option<i64>,
+}
+
+// Shared by the free-text and the structured feedback endpoints
+pub async fn check_feedback_window(
+    pool: &sqlx::PgPool,
+    config: &AppConfig,
+    conference: &ConferenceEdition,
+    clock: &Clock,
+    user_id: &str,
+    session_id: &str,
+) -> Result<(), ServiceError> {
+    let session = sessions::get_session_by_id(pool, conference.id, session_id, false)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    let windows = sessions::get_session_windows(pool, conference.id, session_id).await?;
+    let conference_end = sessions::get_conference_end(pool, conference.id).await?;
+    let now = clock.local_now_millis(pool, conference, Some(user_id)).await?;
+    
+    check_window(
+        conference.feedback_window(&config.feedback),
+        windows.feedback_opens_at,
+        windows.feedback_closes_at,
+        &session,
+        conference_end,
+        now,
+    )
+}
 
 #[post("/feedback")]
 async fn post_feedback(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    clock: web::Data<Clock>,
     feedback_info: web::Json<FeedbackInfo>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
@@ -18,8 +61,12 @@ async fn post_feedback(
         return Err(ServiceError::Unauthorized);
     }
     
+    check_feedback_window(&pool, &config, &conference, &clock, &principal.token, &feedback_info.session_id)
+        .await?;
+    
     let result = feedback::set_feedback(
         &pool,
+        conference.id,
         &principal.token,
         &feedback_info.session_id,
         &feedback_info.value,
@@ -34,14 +81,50 @@ async fn post_feedback(
     }
 }
 
+#[get("/feedback")]
+async fn get_feedback(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    let feedback_list = feedback::get_feedback(&pool, conference.id, &principal.token).await?;
+    Ok(HttpResponse::Ok().json(feedback_list))
+}
+
+#[delete("/feedback/{session_id}")]
+async fn delete_feedback(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    if !feedback::delete_feedback(&pool, conference.id, &principal.token, &path.into_inner()).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[get("/feedback/summary")]
 async fn get_feedback_summary(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
+    query: web::Query<SummaryQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
     
-    let summary = feedback::get_feedback_summary(&pool).await?;
+    let summary = feedback::get_feedback_summary(&pool, conference.id, page, page_size).await?;
     Ok(HttpResponse::Ok().json(summary))
 }
\ No newline at end of file
@@ -0,0 +1,156 @@
+// src/routes/feedback_forms.rs
+use actix_web::{delete, get, post, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::auth::{validate_conference_admin, validate_user, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{categories, feedback_forms, sessions};
+use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::edition::ConferenceEdition;
+use crate::models::feedback_form::{FeedbackAnswersRequest, FeedbackFormRequest};
+use crate::routes::feedback::check_feedback_window;
+use crate::services::audit::{self, PendingChange};
+use crate::services::clock::Clock;
+use crate::services::feedback_forms::{validate_answers, validate_form};
+
+#[derive(Debug, Deserialize)]
+pub struct FormSummaryQuery {
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+}
+
+#[get("/feedback/form/{session_id}")]
+async fn get_session_form(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    match feedback_forms::get_form_for_session(&pool, conference.id, &path.into_inner()).await? {
+        Some(form) => Ok(HttpResponse::Ok().json(form)),
+        None => Err(ServiceError::NotFound),
+    }
+}
+
+#[post("/feedback/answers")]
+async fn post_feedback_answers(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    clock: web::Data<Clock>,
+    request: web::Json<FeedbackAnswersRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    check_feedback_window(&pool, &config, &conference, &clock, &principal.token, &request.session_id)
+        .await?;
+    
+    let form = feedback_forms::get_form_for_session(&pool, conference.id, &request.session_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    validate_answers(&form, &request.answers)?;
+    
+    feedback_forms::set_answers(&pool, conference.id, &principal.token, &request.session_id, &request.answers)
+        .await?;
+    
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/admin/feedback-forms")]
+async fn get_admin_forms(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let forms = feedback_forms::get_all_forms(&pool, conference.id).await?;
+    Ok(HttpResponse::Ok().json(forms))
+}
+
+#[post("/admin/feedback-forms")]
+async fn add_admin_form(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    form: web::Json<FeedbackFormRequest>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    validate_form(&form)?;
+    
+    // A scoped form has to point at a session or category of the same conference
+    if let Some(session_id) = &form.session_id
+        && sessions::get_session_by_id(&pool, conference.id, session_id, true).await?.is_none()
+    {
+        return Err(ServiceError::BadRequest(format!("Unknown session: {}", session_id)));
+    }
+    if let Some(category_id) = form.category_id
+        && categories::get_category_by_id(&pool, conference.id, category_id).await?.is_none()
+    {
+        return Err(ServiceError::BadRequest(format!("Unknown category: {}", category_id)));
+    }
+    
+    let mut tx = pool.begin().await?;
+    let form_id = feedback_forms::add_form(&mut tx, conference.id, &form).await?;
+    PendingChange::created(conference.id, AuditEntity::FeedbackForm, form_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    Ok(HttpResponse::Created().json(form_id))
+}
+
+#[delete("/admin/feedback-forms/{id}")]
+async fn delete_admin_form(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let form_id = path.into_inner();
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::FeedbackForm, form_id).await?;
+    if !feedback_forms::delete_form(&mut tx, conference.id, form_id).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/admin/feedback-forms/{id}/summary")]
+async fn get_admin_form_summary(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<i32>,
+    query: web::Query<FormSummaryQuery>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let summary = feedback_forms::get_form_summary(
+        &pool,
+        conference.id,
+        path.into_inner(),
+        query.session_id.as_deref(),
+    )
+    .await?;
+    
+    match summary {
+        Some(summary) => Ok(HttpResponse::Ok().json(summary)),
+        None => Err(ServiceError::NotFound),
+    }
+}
\ No newline at end of file
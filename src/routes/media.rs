@@ -0,0 +1,199 @@
+// src/routes/media.rs
+use actix_multipart::Multipart;
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use futures::TryStreamExt;
+
+use crate::auth::{validate_conference_admin, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::models::media::MediaFormat;
+use crate::models::session::PhotoVariant;
+use crate::services::http_cache::Validators;
+use crate::services::media::MediaStore;
+
+// Expects a multipart form with the image in a field named "file"
+#[post("/admin/media")]
+async fn upload_media(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    media: web::Data<MediaStore>,
+    payload: Multipart,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let bytes = read_file(payload, config.media.max_upload_bytes).await?;
+    let asset = media.upload(&pool, bytes, &actor).await?;
+    Ok(HttpResponse::Created().json(asset))
+}
+
+#[get("/media/{id}")]
+async fn get_media(
+    path: web::Path<String>,
+    media: web::Data<MediaStore>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let asset = media
+        .get_asset(&pool, &path.into_inner())
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(HttpResponse::Ok().json(asset))
+}
+
+// Serves "/media/{id}/{variant}.{jpg|webp}". Assets never change, so responses can be cached forever.
+#[get("/media/{id}/{file}")]
+async fn get_media_file(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    config: web::Data<AppConfig>,
+    media: web::Data<MediaStore>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let (asset_id, file) = path.into_inner();
+    let (variant, format) = file
+        .split_once('.')
+        .and_then(|(variant, extension)| {
+            Some((PhotoVariant::from_name(variant)?, MediaFormat::from_extension(extension)?))
+        })
+        .ok_or(ServiceError::NotFound)?;
+    
+    let validators = Validators::new(&format!("media:{}:{}", asset_id, file), &[]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.media.cache_control));
+    }
+    
+    let bytes = media
+        .load_variant(&pool, &asset_id, variant, format)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    Ok(validators
+        .ok(&config.media.cache_control)
+        .content_type(format.content_type())
+        .body(bytes))
+}
+
+async fn read_file(mut payload: Multipart, max_bytes: usize) -> Result<Vec<u8>, ServiceError> {
+    let invalid = |e: actix_multipart::MultipartError| {
+        ServiceError::BadRequest(format!("Invalid upload: {}", e))
+    };
+    
+    while let Some(mut field) = payload.try_next().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.try_next().await.map_err(invalid)? {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(ServiceError::BadRequest(format!(
+                    "Uploads are limited to {} bytes",
+                    max_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+    
+    Err(ServiceError::BadRequest("Missing form field 'file'".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{test, App};
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::db::audit;
+    use crate::models::audit::{AuditAction, AuditEntity};
+    use crate::services::storage::LocalStorage;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| Rgba([x as u8, y as u8, 128, 255]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[sqlx::test]
+    async fn uploads_are_served_from_local_storage(pool: PgPool) {
+        let root = std::env::temp_dir().join(format!("media-test-{}", Uuid::new_v4()));
+        let config = AppConfig::from_env().unwrap();
+        let cache_control = config.media.cache_control.clone();
+        let media = web::Data::new(MediaStore::new(Arc::new(LocalStorage::new(&root)), &config.media));
+
+        let actor = Actor {
+            name: "admin".to_string(),
+            request_id: None,
+        };
+
+        let asset = media.upload(&pool, png(1700, 850), &actor).await.unwrap();
+        assert_eq!(asset.content_type, "image/png");
+        assert_eq!((asset.width, asset.height), (1700, 850));
+        let sizes: Vec<_> = asset
+            .variants
+            .iter()
+            .map(|variant| (variant.variant, variant.format, variant.width, variant.height))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (PhotoVariant::Original, MediaFormat::Jpeg, 1600, 800),
+                (PhotoVariant::Original, MediaFormat::Webp, 1600, 800),
+                (PhotoVariant::Square, MediaFormat::Jpeg, 400, 400),
+                (PhotoVariant::Square, MediaFormat::Webp, 400, 400),
+                (PhotoVariant::Thumbnail, MediaFormat::Jpeg, 120, 120),
+                (PhotoVariant::Thumbnail, MediaFormat::Webp, 120, 120),
+            ]
+        );
+        // Ids are content hashes, the same file is stored once
+        assert_eq!(media.upload(&pool, png(1700, 850), &actor).await.unwrap().id, asset.id);
+        let history = audit::get_history(&pool, 1, AuditEntity::MediaAsset, &asset.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, AuditAction::Created);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(media.clone())
+                .app_data(web::Data::new(config))
+                .app_data(web::Data::new(pool.clone()))
+                .service(get_media_file),
+        )
+        .await;
+        for variant in &asset.variants {
+            let response = test::call_service(&app, test::TestRequest::get().uri(&variant.url).to_request()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let headers = response.headers();
+            assert_eq!(headers.get(header::CONTENT_TYPE).unwrap(), variant.format.content_type());
+            assert_eq!(headers.get(header::CACHE_CONTROL).unwrap(), cache_control.as_str());
+            let etag = headers.get(header::ETAG).unwrap().clone();
+
+            let body = test::read_body(response).await;
+            assert_eq!(body.len() as i64, variant.byte_size);
+            let decoded = image::load_from_memory(&body).unwrap();
+            assert_eq!((decoded.width() as i32, decoded.height() as i32), (variant.width, variant.height));
+
+            let revalidated = test::TestRequest::get()
+                .uri(&variant.url)
+                .insert_header((header::IF_NONE_MATCH, etag))
+                .to_request();
+            assert_eq!(test::call_service(&app, revalidated).await.status(), StatusCode::NOT_MODIFIED);
+        }
+
+        let missing = test::TestRequest::get()
+            .uri(&format!("/media/{}/square.png", asset.id))
+            .to_request();
+        assert_eq!(test::call_service(&app, missing).await.status(), StatusCode::NOT_FOUND);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
\ No newline at end of file
@@ -3,7 +3,17 @@ pub mod users;
 pub mod conference;
 pub mod votes;
 pub mod feedback;
+pub mod feedback_forms;
 pub mod admin;
 pub mod sessions;
 pub mod podcast;
-pub mod sync;
\ No newline at end of file
+pub mod sync;
+pub mod reports;
+pub mod events;
+pub mod notifications;
+pub mod speakers;
+pub mod media;
+pub mod translations;
+pub mod audit;
+pub mod changesets;
+pub mod retention;
\ No newline at end of file
@@ -0,0 +1,66 @@
+// src/routes/notifications.rs
+use actix_web::{delete, get, post, put, web, HttpResponse};
+
+use crate::auth::{validate_user, KotlinConfPrincipal};
+use crate::db::notifications;
+use crate::error::ServiceError;
+use crate::models::notification::{DeviceRegistration, NotificationSettings};
+
+#[post("/devices")]
+async fn register_device(
+    principal: KotlinConfPrincipal,
+    device: web::Json<DeviceRegistration>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    let token = device.token.trim();
+    if token.is_empty() || token.len() > 500 {
+        return Err(ServiceError::BadRequest("Invalid device token".to_string()));
+    }
+    
+    notifications::register_device(&pool, &principal.token, token, device.platform).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/devices/{token}")]
+async fn delete_device(
+    principal: KotlinConfPrincipal,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !validate_user(&principal, &pool).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    
+    if !notifications::delete_device(&pool, &principal.token, &path.into_inner()).await? {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/notifications/settings")]
+async fn get_notification_settings(
+    principal: KotlinConfPrincipal,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let enabled = notifications::get_notifications_enabled(&pool, &principal.token)
+        .await?
+        .ok_or(ServiceError::Unauthorized)?;
+    
+    Ok(HttpResponse::Ok().json(NotificationSettings { enabled }))
+}
+
+#[put("/notifications/settings")]
+async fn set_notification_settings(
+    principal: KotlinConfPrincipal,
+    settings: web::Json<NotificationSettings>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    if !notifications::set_notifications_enabled(&pool, &principal.token, settings.enabled).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    Ok(HttpResponse::Ok().finish())
+}
\ No newline at end of file
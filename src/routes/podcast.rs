@@ -1,11 +1,22 @@
 // src/routes/podcast.rs
-use actix_web::{get, post, web, HttpResponse};
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
 use actix_web::http::header::ContentType;
 
-use crate::auth::{validate_user, KotlinConfPrincipal};
-use crate::db::podcast;
+use crate::auth::{validate_user, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{podcast, versions};
 use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::{ChangeAction, ChangeEntity};
+use crate::models::media::MediaFormat;
 use crate::models::podcast::{PodcastImportRequest, PodcastQueryInfo};
+use crate::models::session::PhotoVariant;
+use crate::services::audit::PendingChange;
+use crate::services::events::EventBus;
+use crate::services::http_cache::Validators;
+use crate::services::media::MediaStore;
+use crate::services::read_cache::ReadModelCache;
 
 #[post("/podcast/sendRequest")]
 async fn send_podcast_request(
@@ -35,10 +46,28 @@ async fn send_podcast_request(
 
 #[post("/podcast/import")]
 async fn import_podcast(
+    actor: Actor,
     import_request: web::Json<PodcastImportRequest>,
+    conference: web::ReqData<ConferenceEdition>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    media: web::Data<MediaStore>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    let channel_id = podcast::store_podcast_data(&pool, &import_request).await?;
+    let mut import_request = import_request.into_inner();
+    if let Some(asset_id) = &import_request.channel.image_asset_id {
+        let asset = media.require_asset(&pool, asset_id).await?;
+        import_request.channel.image_url = asset.url(PhotoVariant::Original, MediaFormat::Jpeg);
+    }
+    
+    let mut tx = pool.begin().await?;
+    let channel_id = podcast::store_podcast_data(&mut tx, &import_request).await?;
+    PendingChange::created_shared(AuditEntity::Podcast, channel_id).record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_podcasts();
+    events
+        .publish(&pool, conference.id, ChangeEntity::Podcasts, ChangeAction::Updated, vec![channel_id.to_string()])
+        .await;
     
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "status": "ok",
@@ -48,13 +77,22 @@ async fn import_podcast(
 
 #[get("/podcast/all")]
 async fn get_all_podcasts(
+    req: HttpRequest,
+    config: web::Data<AppConfig>,
+    cache: web::Data<ReadModelCache>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    let data = podcast::get_all_podcast_data(&pool).await?;
+    let versions = versions::get_podcast_versions(&pool).await?;
+    let validators = Validators::new("podcasts", &versions.all());
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.podcasts));
+    }
+    
+    let data = cache.podcast_data(&pool).await?;
     
     // In the Kotlin version, this uses ProtoBuf serialization
     // In this Rust version, we'll use JSON for simplicity
-    Ok(HttpResponse::Ok()
+    Ok(validators.ok(&config.cache.podcasts)
         .content_type(ContentType::json())
-        .json(data))
+        .json(&*data))
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+// src/routes/reports.rs
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::auth::{validate_conference_admin, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::reports;
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::models::report::RankingDimension;
+use crate::services::reports::{rating_groups_to_csv, session_ratings_to_csv};
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    pub format: Option<String>,
+}
+
+impl ReportQuery {
+    fn is_csv(&self) -> Result<bool, ServiceError> {
+        match self.format.as_deref() {
+            None | Some("json") => Ok(false),
+            Some("csv") => Ok(true),
+            Some(other) => Err(ServiceError::BadRequest(format!("Unsupported format: {}", other))),
+        }
+    }
+}
+
+fn csv_response(filename: &str, body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        ))
+        .body(body)
+}
+
+#[get("/admin/reports/sessions")]
+async fn get_session_report(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    query: web::Query<ReportQuery>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let report = reports::get_session_ratings(&pool, conference.id).await?;
+    if query.is_csv()? {
+        return Ok(csv_response("session-ratings.csv", session_ratings_to_csv(&report.sessions)));
+    }
+    
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[get("/admin/reports/rankings/{dimension}")]
+async fn get_ranking_report(
+    principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    path: web::Path<String>,
+    query: web::Query<ReportQuery>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let name = path.into_inner();
+    let dimension = RankingDimension::from_name(&name)
+        .ok_or_else(|| ServiceError::BadRequest(format!("Unknown ranking dimension: {}", name)))?;
+    
+    let groups = reports::get_rankings(&pool, conference.id, dimension).await?;
+    if query.is_csv()? {
+        return Ok(csv_response(&format!("ranking-{}.csv", name), rating_groups_to_csv(&groups)));
+    }
+    
+    Ok(HttpResponse::Ok().json(groups))
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+// src/routes/retention.rs
+use actix_web::{get, post, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::auth::{validate_admin_secret, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::retention;
+use crate::error::ServiceError;
+use crate::services::retention as retention_service;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct RetentionRunQuery {
+    #[serde(rename = "dryRun")]
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetentionRunsQuery {
+    pub limit: Option<i64>,
+}
+
+// Retention covers every conference, so only the service secret may start it
+#[post("/admin/retention/run")]
+async fn run_retention(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    query: web::Query<RetentionRunQuery>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_admin_secret(&principal, &config.service.secret).await?;
+    
+    let dry_run = query.dry_run.unwrap_or(config.retention.dry_run);
+    let run = retention_service::run(&pool, &config.retention, &actor.name, dry_run).await?;
+    Ok(HttpResponse::Ok().json(run))
+}
+
+#[get("/admin/retention/runs")]
+async fn get_retention_runs(
+    principal: KotlinConfPrincipal,
+    query: web::Query<RetentionRunsQuery>,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_admin_secret(&principal, &config.service.secret).await?;
+    
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let runs = retention::get_runs(&pool, limit).await?;
+    Ok(HttpResponse::Ok().json(runs))
+}
\ No newline at end of file
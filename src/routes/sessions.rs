@@ -1,113 +1,217 @@
 // src/routes/sessions.rs
-use actix_web::{get, post, web, HttpResponse};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
 
-use crate::auth::{validate_user, KotlinConfPrincipal};
-use crate::db::{categories, rooms, sessions, speakers};
+use crate::auth::{validate_conference_admin, validate_user, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{categories, rooms, sessions, speakers, versions};
 use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::{ChangeAction, ChangeEntity};
 use crate::models::room::{ConferenceRoomRequest, RoomResponse};
-use crate::models::session::{ConferenceSessionRequest, SessionCategoriesRequest, SessionResponse, SessionSpeakerRequest};
+use crate::models::session::{
+    ConferenceSessionRequest, SessionCategoriesRequest, SessionCategoryListRequest, SessionResponse,
+    SessionSpeakerRequest, SessionSpeakersRequest,
+};
+use crate::services::audit::{self, PendingChange};
+use crate::services::events::EventBus;
+use crate::services::read_cache::ReadModelCache;
+use crate::services::http_cache::{self, Validators};
+use crate::services::locale::ContentLanguage;
+
+// `?preview=true` lets conference admins see sessions that are not published yet
+#[derive(Debug, Deserialize)]
+pub struct PreviewQuery {
+    #[serde(default)]
+    pub preview: bool,
+}
 
 #[get("/get/sessions")]
 async fn get_sessions(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    query: web::Query<PreviewQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    if !validate_user(&principal, &pool).await? {
+    if query.preview {
+        validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    } else if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let sessions_data = sessions::get_all_sessions(&pool).await?;
-    Ok(HttpResponse::Ok().json(sessions_data))
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
+    if query.preview {
+        let sessions_data = sessions::get_all_sessions(&pool, conference.id, language.translation(), true).await?;
+        return Ok(http_cache::uncached(&language).json(sessions_data));
+    }
+    
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference_in(&conference, &language, &[versions.sessions, versions.session_speakers, versions.session_categories]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.sessions));
+    }
+    
+    let sessions_data = sessions::get_all_sessions(&pool, conference.id, language.translation(), false).await?;
+    Ok(validators.ok(&config.cache.sessions).json(sessions_data))
 }
 
 #[get("/get/categories")]
 async fn get_categories(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let categories_data = categories::get_all_categories(&pool).await?;
-    Ok(HttpResponse::Ok().json(categories_data))
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference_in(&conference, &language, &[versions.categories, versions.category_types]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.categories));
+    }
+    
+    let categories_data = categories::get_all_categories(&pool, conference.id, language.translation()).await?;
+    Ok(validators.ok(&config.cache.categories).json(categories_data))
 }
 
 #[get("/get/rooms")]
 async fn get_rooms(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let rooms_data = rooms::get_all_rooms(&pool).await?;
-    Ok(HttpResponse::Ok().json(rooms_data))
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference(&conference, &[versions.rooms]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.rooms));
+    }
+    
+    let rooms_data = rooms::get_all_rooms(&pool, conference.id).await?;
+    Ok(validators.ok(&config.cache.rooms).json(rooms_data))
 }
 
 #[get("/get/speakers")]
 async fn get_speakers(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let speakers_data = speakers::get_all_speakers(&pool).await?;
-    Ok(HttpResponse::Ok().json(speakers_data))
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference_in(&conference, &language, &[versions.speakers]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.speakers));
+    }
+    
+    let speakers_data = speakers::get_all_speakers(&pool, conference.id, language.translation()).await?;
+    Ok(validators.ok(&config.cache.speakers).json(speakers_data))
 }
 
 #[get("/get/session-speakers")]
 async fn get_session_speakers(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let sessions_data = sessions::get_all_sessions(&pool).await?;
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference(&conference, &[versions.sessions, versions.session_speakers]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.session_speakers));
+    }
+    
+    let sessions_data = sessions::get_all_sessions(&pool, conference.id, None, false).await?;
     let mut session_speakers = std::collections::HashMap::new();
     
     for session in sessions_data {
         session_speakers.insert(session.id, session.speaker_ids);
     }
     
-    Ok(HttpResponse::Ok().json(session_speakers))
+    Ok(validators.ok(&config.cache.session_speakers).json(session_speakers))
 }
 
 #[get("/get/session-categories")]
 async fn get_session_categories(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let sessions_data = sessions::get_all_sessions(&pool).await?;
+    let versions = versions::get_schedule_versions(&pool, conference.id).await?;
+    let validators = Validators::for_conference(&conference, &[versions.sessions, versions.session_categories]);
+    if validators.is_fresh(&req) {
+        return Ok(validators.not_modified(&config.cache.session_categories));
+    }
+    
+    let sessions_data = sessions::get_all_sessions(&pool, conference.id, None, false).await?;
     let mut session_categories = std::collections::HashMap::new();
     
     for session in sessions_data {
         session_categories.insert(session.id, session.category_ids);
     }
     
-    Ok(HttpResponse::Ok().json(session_categories))
+    Ok(validators.ok(&config.cache.session_categories).json(session_categories))
 }
 
 #[post("/send/sessions")]
 async fn send_session(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     session: web::Json<ConferenceSessionRequest>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    match sessions::add_session(&pool, &session).await {
+    let mut tx = pool.begin().await?;
+    let result = sessions::add_session(&mut tx, conference.id, &session).await;
+    if let Ok(session_id) = &result {
+        PendingChange::created(conference.id, AuditEntity::Session, session_id).record(&mut tx, &actor).await?;
+        tx.commit().await?;
+        let ids = vec![session_id.clone()];
+        cache.invalidate_conference(conference.id);
+        events
+            .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Created, ids)
+            .await;
+    }
+    
+    match result {
         Ok(session_id) => Ok(HttpResponse::Created().json(SessionResponse {
             success: true,
             session_id: Some(session_id),
@@ -124,14 +228,30 @@ async fn send_session(
 #[post("/send/rooms")]
 async fn send_room(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     room: web::Json<ConferenceRoomRequest>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    match rooms::add_room(&pool, &room).await {
+    let mut tx = pool.begin().await?;
+    let result = rooms::add_room(&mut tx, conference.id, &room).await;
+    if let Ok(room_id) = &result {
+        PendingChange::created(conference.id, AuditEntity::Room, room_id).record(&mut tx, &actor).await?;
+        tx.commit().await?;
+        let ids = vec![room_id.to_string()];
+        cache.invalidate_conference(conference.id);
+        events
+            .publish(&pool, conference.id, ChangeEntity::Rooms, ChangeAction::Created, ids)
+            .await;
+    }
+    
+    match result {
         Ok(room_id) => Ok(HttpResponse::Created().json(RoomResponse {
             success: true,
             room_id: Some(room_id),
@@ -148,26 +268,28 @@ async fn send_room(
 #[post("/send/session-speaker")]
 async fn send_session_speaker(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     session_speaker: web::Json<SessionSpeakerRequest>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    // Verify that both session and speaker exist
-    let session = sessions::get_session_by_id(&pool, &session_speaker.session_id).await?;
-    if session.is_none() {
-        return Err(ServiceError::NotFound);
-    }
-    
-    let speaker = speakers::get_speaker_by_id(&pool, &session_speaker.speaker_id).await?;
-    if speaker.is_none() {
-        return Err(ServiceError::NotFound);
-    }
-    
-    // Add the relationship
-    sessions::add_session_speaker(&pool, &session_speaker.session_id, &session_speaker.speaker_id).await?;
+    // Adds the relationship, an unknown session is not found and an unknown speaker rejected
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_speaker.session_id).await?;
+    sessions::add_session_speaker(&mut tx, conference.id, &session_speaker.session_id, &session_speaker.speaker_id).await?;
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_speaker.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
     
     Ok(HttpResponse::Created().json("Session-speaker relationship added successfully"))
 }
@@ -175,26 +297,176 @@ async fn send_session_speaker(
 #[post("/send/session-categories")]
 async fn send_session_categories(
     principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
     session_categories: web::Json<SessionCategoriesRequest>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    // Verify that both session and category exist
-    let session = sessions::get_session_by_id(&pool, &session_categories.session_id).await?;
-    if session.is_none() {
+    // Adds the relationship, an unknown session is not found and an unknown category rejected
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_categories.session_id).await?;
+    sessions::add_session_category(&mut tx, conference.id, &session_categories.session_id, session_categories.category_id).await?;
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_categories.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
+    
+    Ok(HttpResponse::Created().json("Session-category relationship added successfully"))
+}
+
+#[delete("/send/session-speaker")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_session_speaker(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    session_speaker: web::Json<SessionSpeakerRequest>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_speaker.session_id).await?;
+    if !sessions::remove_session_speaker(
+        &mut tx,
+        conference.id,
+        &session_speaker.session_id,
+        &session_speaker.speaker_id,
+    )
+    .await?
+    {
         return Err(ServiceError::NotFound);
     }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_speaker.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
+    
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[put("/send/session-speakers")]
+#[allow(clippy::too_many_arguments)]
+async fn put_session_speakers(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    session_speakers: web::Json<SessionSpeakersRequest>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_speakers.session_id).await?;
+    if !sessions::set_session_speakers(
+        &mut tx,
+        conference.id,
+        &session_speakers.session_id,
+        &session_speakers.speaker_ids,
+    )
+    .await?
+    {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_speakers.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
+    
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/send/session-categories")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_session_category(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    session_categories: web::Json<SessionCategoriesRequest>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    let category = categories::get_category_by_id(&pool, session_categories.category_id).await?;
-    if category.is_none() {
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_categories.session_id).await?;
+    if !sessions::remove_session_category(
+        &mut tx,
+        conference.id,
+        &session_categories.session_id,
+        session_categories.category_id,
+    )
+    .await?
+    {
         return Err(ServiceError::NotFound);
     }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_categories.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
     
-    // Add the relationship
-    sessions::add_session_category(&pool, &session_categories.session_id, session_categories.category_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[put("/send/session-categories")]
+#[allow(clippy::too_many_arguments)]
+async fn put_session_categories(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    session_categories: web::Json<SessionCategoryListRequest>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    Ok(HttpResponse::Created().json("Session-category relationship added successfully"))
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_categories.session_id).await?;
+    if !sessions::set_session_categories(
+        &mut tx,
+        conference.id,
+        &session_categories.session_id,
+        &session_categories.category_ids,
+    )
+    .await?
+    {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    let ids = vec![session_categories.session_id.clone()];
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, ids)
+        .await;
+    
+    Ok(HttpResponse::Ok().finish())
 }
\ No newline at end of file
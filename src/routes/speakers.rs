@@ -0,0 +1,29 @@
+// src/routes/speakers.rs
+use actix_web::{get, web, HttpResponse};
+
+use crate::db::{sessions, speakers};
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::models::session::SpeakerDetails;
+
+// Ids of merged duplicates redirect to the speaker they were merged into
+#[get("/speakers/{id}")]
+async fn get_speaker(
+    conference: web::ReqData<ConferenceEdition>,
+    path: web::Path<String>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let speaker_id = path.into_inner();
+    let Some(speaker) = speakers::get_speaker_by_id(&pool, conference.id, &speaker_id).await? else {
+        return match speakers::get_merged_into(&pool, conference.id, &speaker_id).await? {
+            // Relative to the current path, so a conference prefix is kept
+            Some(canonical_id) => Ok(HttpResponse::MovedPermanently()
+                .insert_header(("Location", canonical_id))
+                .finish()),
+            None => Err(ServiceError::NotFound),
+        };
+    };
+    
+    let sessions = sessions::get_sessions_for_speaker(&pool, conference.id, &speaker_id).await?;
+    Ok(HttpResponse::Ok().json(SpeakerDetails { speaker, sessions }))
+}
\ No newline at end of file
@@ -1,34 +1,56 @@
-use actix_web::{get, web, HttpResponse};
+use actix_web::{get, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 
-use crate::auth::{validate_user, KotlinConfPrincipal};
+use crate::auth::{validate_conference_admin, validate_user, KotlinConfPrincipal};
+use crate::config::AppConfig;
 use crate::db::sync;
 use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::services::http_cache;
+use crate::services::locale::ContentLanguage;
 
 #[derive(Debug, Deserialize)]
 pub struct SyncQuery {
     pub since: Option<i64>,
+    // Only honoured for /sync/sessions, which then includes unpublished sessions for admins
+    #[serde(default)]
+    pub preview: bool,
 }
 
 #[get("/sync/sessions")]
 async fn sync_sessions(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     query: web::Query<SyncQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    if !validate_user(&principal, &pool).await? {
+    if query.preview {
+        validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    } else if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
     let since_timestamp = query.since.unwrap_or(0);
-    let sessions = sync::get_sessions_changed_since(&pool, since_timestamp).await?;
+    let sessions = sync::get_sessions_changed_since(&pool, conference.id, since_timestamp, language.translation(), query.preview).await?;
     
-    Ok(HttpResponse::Ok().json(sessions))
+    if query.preview {
+        return Ok(http_cache::uncached(&language).json(sessions));
+    }
+    let mut response = HttpResponse::Ok();
+    language.apply(&mut response);
+    Ok(response.json(sessions))
 }
 
 #[get("/sync/speakers")]
 async fn sync_speakers(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     query: web::Query<SyncQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
@@ -36,15 +58,20 @@ async fn sync_speakers(
         return Err(ServiceError::Unauthorized);
     }
     
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
     let since_timestamp = query.since.unwrap_or(0);
-    let speakers = sync::get_speakers_changed_since(&pool, since_timestamp).await?;
+    let speakers = sync::get_speakers_changed_since(&pool, conference.id, since_timestamp, language.translation()).await?;
     
-    Ok(HttpResponse::Ok().json(speakers))
+    let mut response = HttpResponse::Ok();
+    language.apply(&mut response);
+    Ok(response.json(speakers))
 }
 
 #[get("/sync/rooms")]
 async fn sync_rooms(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
     query: web::Query<SyncQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
@@ -53,14 +80,17 @@ async fn sync_rooms(
     }
     
     let since_timestamp = query.since.unwrap_or(0);
-    let rooms = sync::get_rooms_changed_since(&pool, since_timestamp).await?;
+    let rooms = sync::get_rooms_changed_since(&pool, conference.id, since_timestamp).await?;
     
     Ok(HttpResponse::Ok().json(rooms))
 }
 
 #[get("/sync/categories")]
 async fn sync_categories(
+    req: HttpRequest,
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
     query: web::Query<SyncQuery>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
@@ -68,10 +98,14 @@ async fn sync_categories(
         return Err(ServiceError::Unauthorized);
     }
     
+    let language =
+        ContentLanguage::negotiate(&req, &pool, &conference, &config.conference.default_locale).await?;
     let since_timestamp = query.since.unwrap_or(0);
-    let categories = sync::get_categories_changed_since(&pool, since_timestamp).await?;
+    let categories = sync::get_categories_changed_since(&pool, conference.id, since_timestamp, language.translation()).await?;
     
-    Ok(HttpResponse::Ok().json(categories))
+    let mut response = HttpResponse::Ok();
+    language.apply(&mut response);
+    Ok(response.json(categories))
 }
 
 #[get("/sync/podcasts")]
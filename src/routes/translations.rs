@@ -0,0 +1,228 @@
+// src/routes/translations.rs
+use actix_web::{delete, put, web, HttpResponse};
+
+use crate::auth::{validate_conference_admin, Actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::translations;
+use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::{ChangeAction, ChangeEntity};
+use crate::models::translation::{
+    normalize_locale, CategoryTranslation, SessionTranslation, SpeakerTranslation,
+};
+use crate::services::audit;
+use crate::services::events::EventBus;
+use crate::services::read_cache::ReadModelCache;
+
+// Content in the default locale lives on the record itself, translating into it would hide it
+fn translation_locale(
+    locale: &str,
+    conference: &ConferenceEdition,
+    config: &AppConfig,
+) -> Result<String, ServiceError> {
+    let locale = normalize_locale(locale)
+        .ok_or_else(|| ServiceError::BadRequest(format!("Invalid locale: {}", locale)))?;
+    if locale == conference.default_locale(&config.conference.default_locale) {
+        return Err(ServiceError::BadRequest(format!(
+            "{} is the default locale, update the record instead",
+            locale
+        )));
+    }
+    Ok(locale)
+}
+
+fn require_fields(is_empty: bool) -> Result<(), ServiceError> {
+    if is_empty {
+        return Err(ServiceError::BadRequest(
+            "A translation needs at least one field".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[put("/admin/translations/sessions/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn put_session_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(String, String)>,
+    translation: web::Json<SessionTranslation>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    require_fields(translation.is_empty())?;
+    
+    let (session_id, locale) = path.into_inner();
+    let locale = translation_locale(&locale, &conference, &config)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_id).await?;
+    if !translations::set_session_translation(&mut tx, conference.id, &session_id, &locale, &translation).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, vec![session_id])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/translations/sessions/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_session_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(String, String)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (session_id, locale) = path.into_inner();
+    let locale = normalize_locale(&locale).ok_or(ServiceError::NotFound)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Session, &session_id).await?;
+    if !translations::delete_session_translation(&mut tx, conference.id, &session_id, &locale).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Sessions, ChangeAction::Updated, vec![session_id])
+        .await;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[put("/admin/translations/speakers/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn put_speaker_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(String, String)>,
+    translation: web::Json<SpeakerTranslation>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    require_fields(translation.is_empty())?;
+    
+    let (speaker_id, locale) = path.into_inner();
+    let locale = translation_locale(&locale, &conference, &config)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Speaker, &speaker_id).await?;
+    if !translations::set_speaker_translation(&mut tx, conference.id, &speaker_id, &locale, &translation).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Updated, vec![speaker_id])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/translations/speakers/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_speaker_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(String, String)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (speaker_id, locale) = path.into_inner();
+    let locale = normalize_locale(&locale).ok_or(ServiceError::NotFound)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Speaker, &speaker_id).await?;
+    if !translations::delete_speaker_translation(&mut tx, conference.id, &speaker_id, &locale).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Speakers, ChangeAction::Updated, vec![speaker_id])
+        .await;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[put("/admin/translations/categories/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn put_category_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(i32, String)>,
+    translation: web::Json<CategoryTranslation>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    require_fields(translation.is_empty())?;
+    
+    let (category_id, locale) = path.into_inner();
+    let locale = translation_locale(&locale, &conference, &config)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Category, category_id).await?;
+    if !translations::set_category_translation(&mut tx, conference.id, category_id, &locale, &translation).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Categories, ChangeAction::Updated, vec![category_id.to_string()])
+        .await;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/admin/translations/categories/{id}/{locale}")]
+#[allow(clippy::too_many_arguments)]
+async fn delete_category_translation(
+    principal: KotlinConfPrincipal,
+    actor: Actor,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    events: web::Data<EventBus>,
+    cache: web::Data<ReadModelCache>,
+    path: web::Path<(i32, String)>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
+    
+    let (category_id, locale) = path.into_inner();
+    let locale = normalize_locale(&locale).ok_or(ServiceError::NotFound)?;
+    let mut tx = pool.begin().await?;
+    let change = audit::track(&mut tx, conference.id, AuditEntity::Category, category_id).await?;
+    if !translations::delete_category_translation(&mut tx, conference.id, category_id, &locale).await? {
+        return Err(ServiceError::NotFound);
+    }
+    change.record(&mut tx, &actor).await?;
+    tx.commit().await?;
+    cache.invalidate_conference(conference.id);
+    events
+        .publish(&pool, conference.id, ChangeEntity::Categories, ChangeAction::Updated, vec![category_id.to_string()])
+        .await;
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
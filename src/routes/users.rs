@@ -1,8 +1,11 @@
 // src/routes/users.rs
-use actix_web::{post, web, HttpResponse};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{delete, get, post, web, HttpResponse};
 use chrono::Utc;
 
-use crate::db::users;
+use crate::auth::{user_actor, KotlinConfPrincipal};
+use crate::config::AppConfig;
+use crate::db::{account, users};
 use crate::error::ServiceError;
 
 #[post("/sign")]
@@ -17,4 +20,33 @@ async fn sign(
         Ok(false) => Ok(HttpResponse::Conflict().finish()),
         Err(e) => Err(e.into()),
     }
+}
+
+#[get("/me/export")]
+async fn export_me(
+    principal: KotlinConfPrincipal,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let actor = user_actor(&principal.token, &config.service.secret);
+    let export = account::get_export(&pool, &principal.token, &actor)
+        .await?
+        .ok_or(ServiceError::Unauthorized)?;
+    
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::Private, CacheDirective::NoStore]))
+        .json(export))
+}
+
+#[delete("/me")]
+async fn delete_me(
+    principal: KotlinConfPrincipal,
+    config: web::Data<AppConfig>,
+    pool: web::Data<sqlx::PgPool>,
+) -> Result<HttpResponse, ServiceError> {
+    let actor = user_actor(&principal.token, &config.service.secret);
+    if !account::delete_account(&pool, &principal.token, &actor, config.privacy.deletion_policy).await? {
+        return Err(ServiceError::Unauthorized);
+    }
+    Ok(HttpResponse::NoContent().finish())
 }
\ No newline at end of file
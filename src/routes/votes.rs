@@ -2,29 +2,35 @@
 use actix_web::{get, post, web, HttpResponse};
 use chrono::Utc;
 
-use crate::auth::{validate_admin_secret, validate_user, KotlinConfPrincipal};
+use crate::auth::{validate_conference_admin, validate_user, KotlinConfPrincipal};
 use crate::config::AppConfig;
 use crate::db::{sessions, votes};
 use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
 use crate::models::vote::{VoteInfo, Votes};
-use crate::services::admin::now;
+use crate::services::clock::Clock;
+use crate::services::windows::check_window;
 
 #[get("/vote")]
 async fn get_votes(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
     if !validate_user(&principal, &pool).await? {
         return Err(ServiceError::Unauthorized);
     }
     
-    let votes_list = votes::get_votes(&pool, &principal.token).await?;
+    let votes_list = votes::get_votes(&pool, conference.id, &principal.token).await?;
     Ok(HttpResponse::Ok().json(Votes { votes: votes_list }))
 }
 
 #[post("/vote")]
 async fn post_vote(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
+    config: web::Data<AppConfig>,
+    clock: web::Data<Clock>,
     vote_info: web::Json<VoteInfo>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
@@ -33,20 +39,28 @@ async fn post_vote(
     }
     
     // Get the session to check if voting is allowed
-    let session = match sessions::get_session_by_id(&pool, &vote_info.session_id).await? {
+    let session = match sessions::get_session_by_id(&pool, conference.id, &vote_info.session_id, false).await? {
         Some(s) => s,
         None => return Err(ServiceError::NotFound),
     };
     
-    // Check if voting period has started
-    let now_time = now();
-    if now_time < session.starts_at.timestamp_millis() {
-        return Err(ServiceError::ComeBackLater);  // Use ComeBackLater instead of building a response manually
-    }
+    // Check that the voting window is open
+    let windows = sessions::get_session_windows(&pool, conference.id, &session.id).await?;
+    let conference_end = sessions::get_conference_end(&pool, conference.id).await?;
+    let now = clock.local_now_millis(&pool, &conference, Some(&principal.token)).await?;
+    check_window(
+        conference.voting_window(&config.voting),
+        windows.vote_opens_at,
+        windows.vote_closes_at,
+        &session,
+        conference_end,
+        now,
+    )?;
     
     // Change the vote
     votes::change_vote(
         &pool,
+        conference.id,
         &principal.token,
         &vote_info.session_id,
         vote_info.score,
@@ -60,11 +74,12 @@ async fn post_vote(
 #[get("/vote/all")]
 async fn get_all_votes(
     principal: KotlinConfPrincipal,
+    conference: web::ReqData<ConferenceEdition>,
     config: web::Data<AppConfig>,
     pool: web::Data<sqlx::PgPool>,
 ) -> Result<HttpResponse, ServiceError> {
-    validate_admin_secret(&principal, &config.service.secret).await?;
+    validate_conference_admin(&principal, &config.service.secret, &conference).await?;
     
-    let votes_list = votes::get_all_votes(&pool).await?;
+    let votes_list = votes::get_all_votes(&pool, conference.id).await?;
     Ok(HttpResponse::Ok().json(votes_list))
 }
\ No newline at end of file
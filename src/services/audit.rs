@@ -0,0 +1,328 @@
+// src/services/audit.rs
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+
+use crate::auth::{user_actor, Actor};
+use crate::db::audit;
+use crate::error::ServiceError;
+use crate::models::audit::{AuditAction, AuditEntity};
+
+// An entity captured before a mutation. Record it in the transaction of the mutation once the
+// writes are done, the state after is read then and the action follows from the two snapshots.
+pub struct PendingChange {
+    conference_id: Option<i32>,
+    entity: AuditEntity,
+    entity_id: String,
+    before: Option<Value>,
+}
+
+impl PendingChange {
+    // For entities that are being created
+    pub fn created(conference_id: i32, entity: AuditEntity, entity_id: impl ToString) -> Self {
+        PendingChange {
+            conference_id: Some(conference_id),
+            entity,
+            entity_id: entity_id.to_string(),
+            before: None,
+        }
+    }
+
+    // Podcasts and media assets are shared by all conferences
+    pub fn created_shared(entity: AuditEntity, entity_id: impl ToString) -> Self {
+        PendingChange {
+            conference_id: None,
+            entity,
+            entity_id: entity_id.to_string(),
+            before: None,
+        }
+    }
+
+    // The audit entry is part of the change, if it cannot be written the change fails as well
+    pub async fn record(self, conn: &mut PgConnection, actor: &Actor) -> Result<(), ServiceError> {
+        self.append(conn, actor, None).await
+    }
+
+    pub async fn record_restore(self, conn: &mut PgConnection, actor: &Actor) -> Result<(), ServiceError> {
+        self.append(conn, actor, Some(AuditAction::Restored)).await
+    }
+
+    async fn append(
+        self,
+        conn: &mut PgConnection,
+        actor: &Actor,
+        action: Option<AuditAction>,
+    ) -> Result<(), ServiceError> {
+        let after = audit::snapshot(conn, self.conference_id, self.entity, &self.entity_id).await?;
+        self.append_with(conn, actor, action, after).await
+    }
+
+    async fn append_with(
+        self,
+        conn: &mut PgConnection,
+        actor: &Actor,
+        action: Option<AuditAction>,
+        after: Option<Value>,
+    ) -> Result<(), ServiceError> {
+        // Writes that changed nothing, such as linking a speaker twice, are not recorded
+        let Some(action) = action.or_else(|| change_action(&self.before, &after)) else {
+            return Ok(());
+        };
+
+        audit::append(
+            conn,
+            self.conference_id,
+            self.entity,
+            &self.entity_id,
+            action,
+            &actor.name,
+            actor.request_id.as_deref(),
+            self.before.as_ref(),
+            after.as_ref(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+// Locks the entity for the rest of the transaction and takes its state before the change
+pub async fn track(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: impl ToString,
+) -> Result<PendingChange, ServiceError> {
+    let entity_id = entity_id.to_string();
+    audit::lock(conn, Some(conference_id), entity, std::slice::from_ref(&entity_id)).await?;
+    let before = audit::snapshot(conn, Some(conference_id), entity, &entity_id).await?;
+
+    Ok(PendingChange {
+        conference_id: Some(conference_id),
+        entity,
+        entity_id,
+        before,
+    })
+}
+
+// Tracks the sessions that a change of the entity carries over to
+pub async fn track_related_sessions(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    entity: AuditEntity,
+    entity_id: impl ToString,
+) -> Result<Vec<PendingChange>, ServiceError> {
+    let session_ids = audit::get_related_sessions(conn, conference_id, entity, &entity_id.to_string()).await?;
+    audit::lock(conn, Some(conference_id), AuditEntity::Session, &session_ids).await?;
+    let mut before = audit::snapshot_sessions(conn, Some(conference_id), &session_ids).await?;
+
+    Ok(session_ids
+        .into_iter()
+        .map(|session_id| {
+            let state = before
+                .iter()
+                .position(|(id, _)| *id == session_id)
+                .map(|index| before.swap_remove(index).1);
+            PendingChange {
+                conference_id: Some(conference_id),
+                entity: AuditEntity::Session,
+                entity_id: session_id,
+                before: state,
+            }
+        })
+        .collect())
+}
+
+// Entries written before users were labelled by a digest carry the user's token
+pub async fn relabel_token_actors(pool: &PgPool, admin_secret: &str) -> Result<u64, ServiceError> {
+    let mut tx = pool.begin().await?;
+    let mut relabelled = 0;
+    for actor in audit::get_token_actors(&mut tx).await? {
+        let token = actor.trim_start_matches("user:");
+        relabelled += audit::rename_actor(&mut tx, &actor, &user_actor(token, admin_secret)).await?;
+    }
+    tx.commit().await?;
+
+    Ok(relabelled)
+}
+
+// Sessions are read back in one query, whatever their number
+pub async fn record_all(
+    changes: Vec<PendingChange>,
+    conn: &mut PgConnection,
+    actor: &Actor,
+) -> Result<(), ServiceError> {
+    let (sessions, others): (Vec<_>, Vec<_>) = changes
+        .into_iter()
+        .partition(|change| change.entity == AuditEntity::Session && change.conference_id.is_some());
+
+    if let Some(conference_id) = sessions.first().and_then(|change| change.conference_id) {
+        let session_ids: Vec<String> = sessions.iter().map(|change| change.entity_id.clone()).collect();
+        let mut after = audit::snapshot_sessions(conn, Some(conference_id), &session_ids).await?;
+        for change in sessions {
+            let state = after
+                .iter()
+                .position(|(id, _)| *id == change.entity_id)
+                .map(|index| after.swap_remove(index).1);
+            change.append_with(conn, actor, None, state).await?;
+        }
+    }
+    for change in others {
+        change.record(conn, actor).await?;
+    }
+    Ok(())
+}
+
+// How an entity changed between two snapshots, None if it did not
+pub fn change_action(before: &Option<Value>, after: &Option<Value>) -> Option<AuditAction> {
+    match (is_live(before), is_live(after)) {
+        _ if before == after => None,
+        (false, true) => Some(AuditAction::Created),
+        (true, false) => Some(AuditAction::Deleted),
+        _ => Some(AuditAction::Updated),
+    }
+}
+
+// Soft-deleted speakers and rooms still have a row
+fn is_live(state: &Option<Value>) -> bool {
+    state
+        .as_ref()
+        .is_some_and(|state| state.get("deleted_at").is_none_or(Value::is_null))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::db::{conferences, sessions, speakers, translations};
+    use crate::models::edition::ConferenceEditionRequest;
+    use crate::models::session::{ConferenceSessionRequest, ConferenceSpeakerRequest};
+    use crate::models::translation::SessionTranslation;
+
+    fn actor() -> Actor {
+        Actor {
+            name: "admin".to_string(),
+            request_id: None,
+        }
+    }
+
+    // A speaker who gives `count` talks, numbered from session-0
+    async fn add_programme(pool: &PgPool, count: usize) -> i32 {
+        let request = ConferenceEditionRequest {
+            slug: "audit".to_string(),
+            name: "Audit".to_string(),
+            time_zone: None,
+            admin_secret: None,
+            settings: Default::default(),
+        };
+        let conference_id = conferences::add_conference(pool, &request, None).await.unwrap();
+
+        let speaker: ConferenceSpeakerRequest = serde_json::from_value(json!({
+            "firstName": "Ada",
+            "lastName": "Lovelace",
+            "isTopSpeaker": false
+        }))
+        .unwrap();
+        let mut conn = pool.acquire().await.unwrap();
+        speakers::add_speaker_in(&mut conn, conference_id, "speaker-1", &speaker).await.unwrap();
+        for index in 0..count {
+            let session: ConferenceSessionRequest = serde_json::from_value(json!({
+                "title": format!("Talk {}", index),
+                "description": "Imported",
+                "startsAt": "2026-05-21T10:00:00",
+                "endsAt": "2026-05-21T11:00:00",
+                "isServiceSession": false,
+                "isPlenumSession": false,
+                "status": "published",
+                "speakerIds": ["speaker-1"],
+                "categoryIds": []
+            }))
+            .unwrap();
+            sessions::add_session_in(&mut conn, conference_id, &format!("session-{}", index), &session)
+                .await
+                .unwrap();
+        }
+        conference_id
+    }
+
+    #[sqlx::test]
+    async fn deleting_a_speaker_records_the_sessions_it_leaves(pool: PgPool) {
+        let conference_id = add_programme(&pool, 3).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let change = track(&mut tx, conference_id, AuditEntity::Speaker, "speaker-1").await.unwrap();
+        let session_changes = track_related_sessions(&mut tx, conference_id, AuditEntity::Speaker, "speaker-1")
+            .await
+            .unwrap();
+        assert_eq!(session_changes.len(), 3);
+        speakers::delete_speaker(&mut tx, conference_id, "speaker-1").await.unwrap().unwrap();
+        change.record(&mut tx, &actor()).await.unwrap();
+        record_all(session_changes, &mut tx, &actor()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let history = audit::get_history(&pool, conference_id, AuditEntity::Speaker, "speaker-1")
+            .await
+            .unwrap();
+        assert_eq!(history[0].action, AuditAction::Deleted);
+        for index in 0..3 {
+            let history = audit::get_history(&pool, conference_id, AuditEntity::Session, &format!("session-{}", index))
+                .await
+                .unwrap();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].action, AuditAction::Updated);
+            assert_eq!(history[0].before.as_ref().unwrap()["speaker_ids"], json!(["speaker-1"]));
+            assert_eq!(history[0].after.as_ref().unwrap()["speaker_ids"], json!([]));
+        }
+    }
+
+    #[sqlx::test]
+    async fn restoring_a_session_brings_its_translations_back(pool: PgPool) {
+        let conference_id = add_programme(&pool, 1).await;
+        let translation = SessionTranslation {
+            title: Some("Vortrag".to_string()),
+            description: None,
+        };
+
+        let mut tx = pool.begin().await.unwrap();
+        let change = track(&mut tx, conference_id, AuditEntity::Session, "session-0").await.unwrap();
+        translations::set_session_translation(&mut tx, conference_id, "session-0", "de", &translation)
+            .await
+            .unwrap();
+        change.record(&mut tx, &actor()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let change = track(&mut tx, conference_id, AuditEntity::Session, "session-0").await.unwrap();
+        translations::delete_session_translation(&mut tx, conference_id, "session-0", "de")
+            .await
+            .unwrap();
+        change.record(&mut tx, &actor()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let translated = audit::get_version(&pool, conference_id, AuditEntity::Session, "session-0", 1)
+            .await
+            .unwrap()
+            .unwrap()
+            .after
+            .unwrap();
+        assert_eq!(
+            translated["translations"],
+            json!([{"locale": "de", "title": "Vortrag", "description": null}])
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let change = track(&mut tx, conference_id, AuditEntity::Session, "session-0").await.unwrap();
+        audit::restore(&mut tx, conference_id, AuditEntity::Session, "session-0", &translated)
+            .await
+            .unwrap();
+        change.record_restore(&mut tx, &actor()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let history = audit::get_history(&pool, conference_id, AuditEntity::Session, "session-0")
+            .await
+            .unwrap();
+        assert_eq!(history[0].version, 3);
+        assert_eq!(history[0].action, AuditAction::Restored);
+        assert_eq!(history[0].after.as_ref(), Some(&translated));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,371 @@
+// src/services/changesets.rs
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+
+use crate::auth::Actor;
+use crate::db::{audit, changesets, conferences, rooms, sessions, speakers};
+use crate::error::ServiceError;
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::changeset::{ChangeDiff, ChangeOperation, ChangesetOperation};
+use crate::models::event::{ChangeAction, ChangeEntity};
+use crate::models::session::SessionStatus;
+use crate::services::audit::change_action;
+use crate::services::clock::Clock;
+use crate::services::events::EventBus;
+use crate::services::notifications;
+use crate::services::read_cache::ReadModelCache;
+
+// Runs the changeset against live data and rolls it back. Rooms are numbered by the database,
+// the ids they get in the preview are not the ones they get when the changeset is applied.
+pub async fn preview(
+    pool: &PgPool,
+    conference_id: i32,
+    changeset_id: i32,
+) -> Result<Vec<ChangeDiff>, ServiceError> {
+    let mut tx = pool.begin().await?;
+    let operations = changesets::lock_for_apply(&mut tx, conference_id, changeset_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    let diff = run_operations(&mut tx, conference_id, &operations).await?;
+    tx.rollback().await?;
+
+    Ok(diff)
+}
+
+// All operations go live in one transaction, or none of them does
+pub async fn apply(
+    pool: &PgPool,
+    events: &EventBus,
+    cache: &ReadModelCache,
+    conference_id: i32,
+    changeset_id: i32,
+    actor: &Actor,
+) -> Result<Vec<ChangeDiff>, ServiceError> {
+    let mut tx = pool.begin().await?;
+    let operations = changesets::lock_for_apply(&mut tx, conference_id, changeset_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    go_live(pool, events, cache, tx, conference_id, changeset_id, &operations, actor).await
+}
+
+// None if the changeset stopped being due after the scheduler listed it
+async fn apply_scheduled(
+    pool: &PgPool,
+    events: &EventBus,
+    cache: &ReadModelCache,
+    conference_id: i32,
+    changeset_id: i32,
+    now: DateTime<Utc>,
+    actor: &Actor,
+) -> Result<Option<Vec<ChangeDiff>>, ServiceError> {
+    let mut tx = pool.begin().await?;
+    if !changesets::lock_due_changeset(&mut tx, conference_id, changeset_id, now).await? {
+        return Ok(None);
+    }
+    let operations = changesets::lock_for_apply(&mut tx, conference_id, changeset_id)
+        .await?
+        .ok_or(ServiceError::NotFound)?;
+    go_live(pool, events, cache, tx, conference_id, changeset_id, &operations, actor)
+        .await
+        .map(Some)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn go_live(
+    pool: &PgPool,
+    events: &EventBus,
+    cache: &ReadModelCache,
+    mut tx: Transaction<'_, Postgres>,
+    conference_id: i32,
+    changeset_id: i32,
+    operations: &[ChangesetOperation],
+    actor: &Actor,
+) -> Result<Vec<ChangeDiff>, ServiceError> {
+    let diff = run_operations(&mut tx, conference_id, operations).await?;
+    for change in &diff {
+        audit::append(
+            &mut tx,
+            Some(conference_id),
+            change.entity,
+            &change.entity_id,
+            change.action,
+            &actor.name,
+            actor.request_id.as_deref(),
+            change.before.as_ref(),
+            change.after.as_ref(),
+        )
+        .await?;
+    }
+    changesets::mark_applied(&mut tx, changeset_id).await?;
+    tx.commit().await?;
+
+    cache.invalidate_conference(conference_id);
+    publish_changes(pool, events, conference_id, &diff).await;
+    notify_cancellations(pool, conference_id, &diff).await;
+    notify_moves(pool, conference_id, &diff).await;
+
+    Ok(diff)
+}
+
+async fn run_operations(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    operations: &[ChangesetOperation],
+) -> Result<Vec<ChangeDiff>, ServiceError> {
+    // Every entity the changeset writes to, with its state before the first write
+    let mut touched: Vec<(AuditEntity, String, Option<Value>)> = Vec::new();
+
+    for operation in operations {
+        let mut targets = Vec::new();
+        if let Some((entity, entity_id)) = operation.operation.target() {
+            // Sessions lose their room or speaker along with it
+            if matches!(
+                operation.operation,
+                ChangeOperation::DeleteSpeaker { .. } | ChangeOperation::DeleteRoom { .. }
+            ) {
+                for session_id in audit::get_related_sessions(conn, conference_id, entity, &entity_id).await? {
+                    targets.push((AuditEntity::Session, session_id));
+                }
+            }
+            targets.push((entity, entity_id));
+        }
+        for (entity, entity_id) in targets {
+            if !touched.iter().any(|(e, id, _)| *e == entity && *id == entity_id) {
+                audit::lock(conn, Some(conference_id), entity, std::slice::from_ref(&entity_id)).await?;
+                let before = audit::snapshot(conn, Some(conference_id), entity, &entity_id).await?;
+                touched.push((entity, entity_id, before));
+            }
+        }
+
+        let created_room = execute(conn, conference_id, &operation.operation)
+            .await
+            .map_err(|e| operation_error(operation, e))?;
+        if let Some(room_id) = created_room {
+            touched.push((AuditEntity::Room, room_id.to_string(), None));
+        }
+    }
+
+    let mut diff = Vec::new();
+    for (entity, entity_id, before) in touched {
+        let after = audit::snapshot(conn, Some(conference_id), entity, &entity_id).await?;
+        if let Some(action) = change_action(&before, &after) {
+            diff.push(ChangeDiff {
+                entity,
+                entity_id,
+                action,
+                before,
+                after,
+            });
+        }
+    }
+    Ok(diff)
+}
+
+// Returns the id of a created room, the other ids are known up front
+async fn execute(
+    conn: &mut PgConnection,
+    conference_id: i32,
+    operation: &ChangeOperation,
+) -> Result<Option<i32>, ServiceError> {
+    let found = match operation {
+        ChangeOperation::CreateSession { session_id, session } => {
+            let session_id = session_id.as_deref().ok_or(ServiceError::NotFound)?;
+            sessions::add_session_in(conn, conference_id, session_id, session).await?;
+            true
+        }
+        ChangeOperation::MoveSession {
+            session_id,
+            starts_at,
+            ends_at,
+            room_id,
+        } => sessions::move_session(conn, conference_id, session_id, *starts_at, *ends_at, *room_id).await?,
+        ChangeOperation::SetSessionStatus { session_id, status } => {
+            sessions::set_session_status(conn, conference_id, session_id, *status)
+                .await?
+                .is_some()
+        }
+        ChangeOperation::CreateSpeaker { speaker_id, speaker } => {
+            let speaker_id = speaker_id.as_deref().ok_or(ServiceError::NotFound)?;
+            speakers::add_speaker_in(conn, conference_id, speaker_id, speaker).await?;
+            true
+        }
+        ChangeOperation::UpdateSpeaker { speaker_id, speaker } => {
+            speakers::update_speaker(conn, conference_id, speaker_id, speaker).await?
+        }
+        ChangeOperation::DeleteSpeaker { speaker_id } => {
+            speakers::delete_speaker(conn, conference_id, speaker_id)
+                .await?
+                .is_some()
+        }
+        ChangeOperation::CreateRoom { room } => {
+            return Ok(Some(rooms::add_room(conn, conference_id, room).await?));
+        }
+        ChangeOperation::UpdateRoom { room_id, room } => {
+            rooms::update_room(conn, conference_id, *room_id, room).await?
+        }
+        ChangeOperation::DeleteRoom { room_id } => {
+            rooms::delete_room(conn, conference_id, *room_id)
+                .await?
+                .is_some()
+        }
+    };
+
+    if !found {
+        return Err(ServiceError::NotFound);
+    }
+    Ok(None)
+}
+
+// Points organisers at the operation that keeps the changeset from being applied
+fn operation_error(operation: &ChangesetOperation, error: ServiceError) -> ServiceError {
+    let message = match error {
+        ServiceError::NotFound => match operation.operation.target() {
+            Some((entity, entity_id)) => format!("{} {} does not exist", entity.name(), entity_id),
+            None => "Not Found".to_string(),
+        },
+        ServiceError::BadRequest(message) => message,
+        ServiceError::InvalidReferences(references) => references.to_string(),
+        error => return error,
+    };
+    ServiceError::BadRequest(format!("Operation {}: {}", operation.id, message))
+}
+
+// One event per kind of change, as if the operations had been made one by one
+async fn publish_changes(pool: &PgPool, events: &EventBus, conference_id: i32, diff: &[ChangeDiff]) {
+    let mut groups: Vec<(ChangeEntity, ChangeAction, Vec<String>)> = Vec::new();
+    for change in diff {
+        let Some(entity) = change.entity.change_entity() else {
+            continue;
+        };
+        let action = match change.action {
+            AuditAction::Created => ChangeAction::Created,
+            AuditAction::Deleted => ChangeAction::Deleted,
+            AuditAction::Updated | AuditAction::Restored => ChangeAction::Updated,
+        };
+        match groups.iter_mut().find(|(e, a, _)| *e == entity && *a == action) {
+            Some((_, _, ids)) => ids.push(change.entity_id.clone()),
+            None => groups.push((entity, action, vec![change.entity_id.clone()])),
+        }
+    }
+
+    for (entity, action, ids) in groups {
+        events.publish(pool, conference_id, entity, action, ids).await;
+    }
+}
+
+// Same rule as a single status change: only attendees who could see the session are told
+async fn notify_cancellations(pool: &PgPool, conference_id: i32, diff: &[ChangeDiff]) {
+    let status = |state: &Option<Value>| {
+        state
+            .as_ref()
+            .and_then(|state| state.get("status"))
+            .and_then(Value::as_str)
+            .and_then(SessionStatus::from_name)
+    };
+
+    for change in diff.iter().filter(|change| change.entity == AuditEntity::Session) {
+        if status(&change.after) != Some(SessionStatus::Cancelled)
+            || !status(&change.before).is_some_and(|status| status.is_public())
+        {
+            continue;
+        }
+        let title = change
+            .after
+            .as_ref()
+            .and_then(|state| state.get("title"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if let Err(e) = notifications::notify_session_cancelled(pool, conference_id, &change.entity_id, title).await {
+            log::error!("Failed to notify cancellation of session {}: {}", change.entity_id, e);
+        }
+    }
+}
+
+// Sessions that changed room or time slot, whether moved on their own or detached from a
+// deleted room, are announced like a single move
+async fn notify_moves(pool: &PgPool, conference_id: i32, diff: &[ChangeDiff]) {
+    let placement = |state: &Value| {
+        ["room_id", "starts_at", "ends_at"].map(|field| state.get(field).cloned())
+    };
+
+    let moved: Vec<String> = diff
+        .iter()
+        .filter(|change| change.entity == AuditEntity::Session)
+        .filter(|change| match (&change.before, &change.after) {
+            (Some(before), Some(after)) => placement(before) != placement(after),
+            _ => false,
+        })
+        .map(|change| change.entity_id.clone())
+        .collect();
+    notifications::announce_moved_sessions(pool, conference_id, &moved, &[]).await;
+}
+
+// Applies scheduled changesets once their time has come on the conference clock. Every
+// instance runs the job, the row lock in `apply` keeps a changeset from going live twice.
+pub struct ChangesetScheduler {
+    pool: PgPool,
+    clock: Clock,
+    events: EventBus,
+    cache: Arc<ReadModelCache>,
+}
+
+impl ChangesetScheduler {
+    pub fn new(pool: PgPool, clock: Clock, events: EventBus, cache: Arc<ReadModelCache>) -> Self {
+        ChangesetScheduler {
+            pool,
+            clock,
+            events,
+            cache,
+        }
+    }
+
+    pub async fn start(self, schedule: &str) -> Result<(), JobSchedulerError> {
+        let scheduler = JobScheduler::new().await?;
+        let worker = Arc::new(self);
+        scheduler
+            .add(Job::new_async(schedule, move |_, _| {
+                let worker = worker.clone();
+                Box::pin(async move {
+                    if let Err(e) = worker.apply_due().await {
+                        log::error!("Failed to apply scheduled changesets: {}", e);
+                    }
+                })
+            })?)
+            .await?;
+        scheduler.start().await
+    }
+
+    async fn apply_due(&self) -> Result<(), ServiceError> {
+        for conference in conferences::get_all_conferences(&self.pool).await? {
+            let now = self.clock.now(&self.pool, &conference, None).await?;
+
+            for (changeset_id, created_by) in changesets::get_due_changesets(&self.pool, conference.id, now).await? {
+                let actor = Actor {
+                    name: format!("scheduler ({})", created_by),
+                    request_id: None,
+                };
+                let applied =
+                    apply_scheduled(&self.pool, &self.events, &self.cache, conference.id, changeset_id, now, &actor);
+                match applied.await {
+                    Ok(Some(diff)) => log::info!("Applied changeset {} with {} changes", changeset_id, diff.len()),
+                    Ok(None) => log::info!("Changeset {} is no longer due, skipping it", changeset_id),
+                    // A changeset that does not fit the schedule any more waits for an organiser
+                    Err(ServiceError::BadRequest(message)) => {
+                        log::warn!("Scheduled changeset {} failed: {}", changeset_id, message);
+                        // The other due changesets still get their turn
+                        if let Err(e) = changesets::mark_failed(&self.pool, changeset_id, &message).await {
+                            log::error!("Failed to mark changeset {} as failed: {}", changeset_id, e);
+                        }
+                    }
+                    // Anything else is tried again on the next run
+                    Err(e) => log::error!("Failed to apply changeset {}: {}", changeset_id, e),
+                }
+            }
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
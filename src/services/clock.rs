@@ -0,0 +1,221 @@
+// src/services/clock.rs
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+
+use crate::db::clock;
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+
+// Source of real time, swappable so time-based logic can run against a fixed instant
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[cfg(test)]
+pub struct FixedTimeSource(pub DateTime<Utc>);
+
+#[cfg(test)]
+impl TimeSource for FixedTimeSource {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+#[derive(Clone)]
+pub struct Clock {
+    source: Arc<dyn TimeSource>,
+    time_zone: Tz,
+}
+
+impl Clock {
+    // The time zone is the default for conferences that do not configure their own
+    pub fn new(source: Arc<dyn TimeSource>, time_zone: Tz) -> Self {
+        Clock { source, time_zone }
+    }
+    
+    // Current instant as seen by the user, taking a global or cohort simulation of the
+    // conference into account. A simulated clock keeps ticking from the moment it was set.
+    pub async fn now(
+        &self,
+        pool: &PgPool,
+        conference: &ConferenceEdition,
+        user_id: Option<&str>,
+    ) -> Result<DateTime<Utc>, ServiceError> {
+        let real_now = self.source.now();
+        
+        match clock::get_simulated_time(pool, conference.id, user_id).await? {
+            Some(simulated) => Ok(simulated.simulated_at + (real_now - simulated.set_at)),
+            None => Ok(real_now),
+        }
+    }
+    
+    // Session times are stored as conference wall-clock time, so comparisons against them
+    // use the local time of the conference time zone expressed in milliseconds
+    pub async fn local_now_millis(
+        &self,
+        pool: &PgPool,
+        conference: &ConferenceEdition,
+        user_id: Option<&str>,
+    ) -> Result<i64, ServiceError> {
+        let now = self.now(pool, conference, user_id).await?;
+        Ok(instant_to_local_millis(now, conference.time_zone_or(self.time_zone)))
+    }
+    
+    pub async fn set_simulated_time(
+        &self,
+        pool: &PgPool,
+        conference: &ConferenceEdition,
+        cohort: &str,
+        local_millis: Option<i64>,
+    ) -> Result<(), ServiceError> {
+        let simulated_at = match local_millis {
+            Some(millis) => Some(
+                local_millis_to_instant(millis, conference.time_zone_or(self.time_zone))
+                    .ok_or_else(|| ServiceError::BadRequest("Invalid timestamp".to_string()))?,
+            ),
+            None => None,
+        };
+        
+        clock::set_simulated_time(pool, conference.id, cohort, simulated_at, self.source.now()).await
+    }
+}
+
+fn instant_to_local_millis(instant: DateTime<Utc>, time_zone: Tz) -> i64 {
+    instant
+        .with_timezone(&time_zone)
+        .naive_local()
+        .and_utc()
+        .timestamp_millis()
+}
+
+fn local_millis_to_instant(millis: i64, time_zone: Tz) -> Option<DateTime<Utc>> {
+    let local = DateTime::from_timestamp_millis(millis)?.naive_utc();
+    
+    // Wall-clock times skipped by a DST jump do not exist, repeated ones take the first occurrence
+    time_zone
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|instant| instant.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::db::{conferences, users};
+
+    fn clock_at(now: DateTime<Utc>) -> Clock {
+        Clock::new(Arc::new(FixedTimeSource(now)), chrono_tz::Europe::Amsterdam)
+    }
+
+    fn real_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap()
+    }
+
+    async fn default_conference(pool: &PgPool) -> ConferenceEdition {
+        let editions = conferences::get_all_conferences(pool).await.unwrap();
+        editions.into_iter().find(|edition| edition.is_default).unwrap()
+    }
+
+    #[sqlx::test]
+    async fn now_is_the_source_time_without_a_simulation(pool: PgPool) {
+        let conference = default_conference(&pool).await;
+
+        let now = clock_at(real_now()).now(&pool, &conference, Some("u1")).await.unwrap();
+
+        assert_eq!(now, real_now());
+    }
+
+    #[sqlx::test]
+    async fn cohort_simulation_only_applies_to_its_members(pool: PgPool) {
+        let conference = default_conference(&pool).await;
+        let clock = clock_at(real_now());
+        users::create_user(&pool, "member", "0").await.unwrap();
+        users::create_user(&pool, "outsider", "0").await.unwrap();
+        clock::add_cohort_member(&pool, conference.id, "testers", "member").await.unwrap();
+
+        // 2025-05-22 10:00 in Amsterdam is 08:00 UTC
+        let local = Utc.with_ymd_and_hms(2025, 5, 22, 10, 0, 0).unwrap().timestamp_millis();
+        clock.set_simulated_time(&pool, &conference, "testers", Some(local)).await.unwrap();
+
+        let simulated = Utc.with_ymd_and_hms(2025, 5, 22, 8, 0, 0).unwrap();
+        assert_eq!(clock.now(&pool, &conference, Some("member")).await.unwrap(), simulated);
+        assert_eq!(clock.now(&pool, &conference, Some("outsider")).await.unwrap(), real_now());
+        assert_eq!(clock.now(&pool, &conference, None).await.unwrap(), real_now());
+        assert_eq!(
+            clock.local_now_millis(&pool, &conference, Some("member")).await.unwrap(),
+            local
+        );
+    }
+
+    #[sqlx::test]
+    async fn cohort_simulation_wins_over_the_global_one(pool: PgPool) {
+        let conference = default_conference(&pool).await;
+        let clock = clock_at(real_now());
+        users::create_user(&pool, "member", "0").await.unwrap();
+        clock::add_cohort_member(&pool, conference.id, "testers", "member").await.unwrap();
+
+        let global = Utc.with_ymd_and_hms(2025, 5, 21, 9, 0, 0).unwrap();
+        let cohort = Utc.with_ymd_and_hms(2025, 5, 23, 9, 0, 0).unwrap();
+        clock::set_simulated_time(&pool, conference.id, clock::GLOBAL_COHORT, Some(global), real_now())
+            .await
+            .unwrap();
+        clock::set_simulated_time(&pool, conference.id, "testers", Some(cohort), real_now())
+            .await
+            .unwrap();
+
+        assert_eq!(clock.now(&pool, &conference, Some("member")).await.unwrap(), cohort);
+        assert_eq!(clock.now(&pool, &conference, Some("u1")).await.unwrap(), global);
+    }
+
+    #[sqlx::test]
+    async fn simulated_time_keeps_ticking(pool: PgPool) {
+        let conference = default_conference(&pool).await;
+        let simulated = Utc.with_ymd_and_hms(2025, 5, 22, 8, 0, 0).unwrap();
+        clock::set_simulated_time(&pool, conference.id, clock::GLOBAL_COHORT, Some(simulated), real_now())
+            .await
+            .unwrap();
+
+        let later = clock_at(real_now() + Duration::minutes(90));
+
+        assert_eq!(
+            later.now(&pool, &conference, None).await.unwrap(),
+            simulated + Duration::minutes(90)
+        );
+    }
+
+    #[test]
+    fn local_millis_skip_the_missing_hour_and_take_the_first_repeated_one() {
+        let time_zone = chrono_tz::Europe::Amsterdam;
+        let local = |day, hour| Utc.with_ymd_and_hms(2025, 3, day, hour, 30, 0).unwrap().timestamp_millis();
+        let fall_back = Utc.with_ymd_and_hms(2025, 10, 26, 2, 30, 0).unwrap().timestamp_millis();
+
+        // 02:30 does not exist on the last Sunday of March
+        assert_eq!(local_millis_to_instant(local(30, 2), time_zone), None);
+        assert_eq!(
+            local_millis_to_instant(local(30, 3), time_zone),
+            Some(Utc.with_ymd_and_hms(2025, 3, 30, 1, 30, 0).unwrap())
+        );
+        // 02:30 happens twice on the last Sunday of October, first in summer time
+        assert_eq!(
+            local_millis_to_instant(fall_back, time_zone),
+            Some(Utc.with_ymd_and_hms(2025, 10, 26, 0, 30, 0).unwrap())
+        );
+        assert_eq!(
+            instant_to_local_millis(Utc.with_ymd_and_hms(2025, 10, 26, 1, 30, 0).unwrap(), time_zone),
+            fall_back
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,133 @@
+// src/services/events.rs
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::sync::{broadcast, watch};
+
+use crate::db::events::{self, CHANGES_CHANNEL};
+use crate::error::ServiceError;
+use crate::models::event::{ChangeAction, ChangeEntity, ChangeEvent};
+
+const BUFFER_SIZE: usize = 256;
+
+// Fans schedule changes out to the live clients of this instance. Changes travel through
+// Postgres NOTIFY so clients connected to other instances see them as well.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ChangeEvent>,
+    // Counts the listener's connections. Notifications sent while it was not connected are lost.
+    connections: watch::Sender<u64>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BUFFER_SIZE);
+        let (connections, _) = watch::channel(0);
+        EventBus { sender, connections }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    // Changes once the listener has (re)connected, after which changes made in the meantime
+    // may have been missed
+    pub fn subscribe_connections(&self) -> watch::Receiver<u64> {
+        self.connections.subscribe()
+    }
+
+    // Call only after the mutation has been committed, a failed notification is logged
+    // and does not fail the request since clients still catch up through /sync/*
+    pub async fn publish(
+        &self,
+        pool: &PgPool,
+        conference_id: i32,
+        entity: ChangeEntity,
+        action: ChangeAction,
+        ids: Vec<String>,
+    ) {
+        let at = changed_at(pool, conference_id, entity, &ids).await;
+        let event = ChangeEvent {
+            conference_id,
+            entity,
+            action,
+            ids,
+            at: at.timestamp_millis(),
+        };
+
+        if let Err(e) = events::notify_change(pool, &event).await {
+            log::error!("Failed to publish change event {:?}: {}", event, e);
+        }
+    }
+
+    // Forwards notifications from Postgres to local subscribers, reconnecting when the
+    // listener connection is lost
+    pub fn start_listener(&self, pool: PgPool) {
+        let sender = self.sender.clone();
+        let connections = self.connections.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = listen(&pool, &sender, &connections).await {
+                    log::error!("Change listener failed, restarting in 5 seconds: {}", e);
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        });
+    }
+}
+
+// The rows' own updated_at, which the sync endpoints compare `since` against. Stamping the
+// event when it is published would be later than the commit and could skip the change.
+// Without ids there is nothing to look up, clients re-sync the whole entity anyway.
+async fn changed_at(
+    pool: &PgPool,
+    conference_id: i32,
+    entity: ChangeEntity,
+    ids: &[String],
+) -> DateTime<Utc> {
+    if ids.is_empty() {
+        return Utc::now();
+    }
+    match events::get_changed_at(pool, conference_id, entity, ids).await {
+        Ok(changed_at) => changed_at.unwrap_or_else(Utc::now),
+        Err(e) => {
+            log::error!("Failed to read when {:?} {:?} changed: {}", entity, ids, e);
+            Utc::now()
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Returns when the connection is lost, PgListener would otherwise reconnect on its own without
+// telling anyone that notifications were dropped
+async fn listen(
+    pool: &PgPool,
+    sender: &broadcast::Sender<ChangeEvent>,
+    connections: &watch::Sender<u64>,
+) -> Result<(), ServiceError> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CHANGES_CHANNEL).await?;
+    log::info!("Listening for changes on '{}'", CHANGES_CHANNEL);
+    connections.send_modify(|count| *count += 1);
+
+    loop {
+        let Some(notification) = listener.try_recv().await? else {
+            log::warn!("Change listener lost its connection, restarting in 5 seconds");
+            return Ok(());
+        };
+        match serde_json::from_str::<ChangeEvent>(notification.payload()) {
+            // Sending only fails when nobody is connected, which is fine
+            Ok(event) => {
+                let _ = sender.send(event);
+            }
+            Err(e) => log::warn!("Ignoring malformed change notification: {}", e),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+// src/services/feedback_forms.rs
+use std::collections::HashSet;
+
+use crate::error::ServiceError;
+use crate::models::feedback_form::{
+    FeedbackAnswer, FeedbackForm, FeedbackFormRequest, QuestionType,
+};
+
+pub fn validate_form(form: &FeedbackFormRequest) -> Result<(), ServiceError> {
+    if form.session_id.is_some() && form.category_id.is_some() {
+        return Err(ServiceError::BadRequest(
+            "A form can be scoped to a session or a category, not both".to_string(),
+        ));
+    }
+    if form.questions.is_empty() {
+        return Err(ServiceError::BadRequest("A form needs at least one question".to_string()));
+    }
+
+    for question in &form.questions {
+        match question.question_type {
+            QuestionType::Rating => match (question.scale_min, question.scale_max) {
+                (Some(min), Some(max)) if min < max => {}
+                _ => {
+                    return Err(ServiceError::BadRequest(format!(
+                        "Rating question '{}' needs scaleMin lower than scaleMax",
+                        question.prompt
+                    )))
+                }
+            },
+            QuestionType::Choice => {
+                if question.options.is_empty() {
+                    return Err(ServiceError::BadRequest(format!(
+                        "Choice question '{}' needs at least one option",
+                        question.prompt
+                    )));
+                }
+            }
+            QuestionType::Text => {}
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_answers(form: &FeedbackForm, answers: &[FeedbackAnswer]) -> Result<(), ServiceError> {
+    let mut answered = HashSet::new();
+
+    for answer in answers {
+        let question = form
+            .questions
+            .iter()
+            .find(|q| q.id == answer.question_id)
+            .ok_or_else(|| {
+                ServiceError::BadRequest(format!(
+                    "Question {} does not belong to this form",
+                    answer.question_id
+                ))
+            })?;
+
+        if !answered.insert(answer.question_id) {
+            return Err(ServiceError::BadRequest(format!(
+                "Question {} is answered more than once",
+                answer.question_id
+            )));
+        }
+
+        let valid = match question.question_type {
+            QuestionType::Rating => match answer.rating {
+                Some(rating) => {
+                    question.scale_min.is_none_or(|min| rating >= min)
+                        && question.scale_max.is_none_or(|max| rating <= max)
+                }
+                None => false,
+            },
+            QuestionType::Choice => answer
+                .choice
+                .as_ref()
+                .is_some_and(|choice| question.options.contains(choice)),
+            QuestionType::Text => answer.text.as_ref().is_some_and(|text| text.len() <= 5000),
+        };
+
+        if !valid {
+            return Err(ServiceError::BadRequest(format!(
+                "Invalid answer for question {}",
+                answer.question_id
+            )));
+        }
+    }
+
+    if let Some(missing) = form
+        .questions
+        .iter()
+        .find(|q| q.required && !answered.contains(&q.id))
+    {
+        return Err(ServiceError::BadRequest(format!(
+            "Question {} is required",
+            missing.id
+        )));
+    }
+
+    Ok(())
+}
\ No newline at end of file
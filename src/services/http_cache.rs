@@ -0,0 +1,139 @@
+// src/services/http_cache.rs
+//
+// Conditional GET support for the read endpoints. Validators are derived from the versions
+// of the tables a response is built from, so a revalidation costs one aggregate query
+// instead of composing and serializing the full payload.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::http::header::{
+    self, CacheControl, CacheDirective, EntityTag, ETag, Header, HttpDate, IfModifiedSince, IfNoneMatch,
+    LastModified, TryIntoHeaderValue,
+};
+use actix_web::{HttpRequest, HttpResponse, HttpResponseBuilder};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::models::edition::ConferenceEdition;
+use crate::models::time;
+use crate::models::version::TableVersion;
+use crate::services::locale::ContentLanguage;
+use crate::tenancy::TIMESTAMP_FORMAT_HEADER;
+
+pub struct Validators {
+    etag: EntityTag,
+    last_modified: Option<DateTime<Utc>>,
+    // Set for negotiated representations, which then carry Vary and Content-Language
+    language: Option<ContentLanguage>,
+    // Conference payloads carry timestamps in the format the client asked for
+    timestamps: bool,
+}
+
+impl Validators {
+    // `variant` captures everything besides the data that changes the representation
+    pub fn new(variant: &str, versions: &[TableVersion]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(variant.as_bytes());
+        for version in versions {
+            let micros = version.last_modified.map(|at| at.timestamp_micros()).unwrap_or(0);
+            hasher.update(micros.to_be_bytes());
+            hasher.update(version.rows.to_be_bytes());
+        }
+        let tag: String = hasher.finalize()[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        // Strong, content encodings are applied afterwards by the Compress middleware
+        Validators {
+            etag: EntityTag::new_strong(tag),
+            last_modified: versions.iter().filter_map(|version| version.last_modified).max(),
+            language: None,
+            timestamps: false,
+        }
+    }
+
+    pub fn for_conference(conference: &ConferenceEdition, versions: &[TableVersion]) -> Self {
+        Validators {
+            timestamps: true,
+            ..Self::new(&conference_variant(conference), versions)
+        }
+    }
+
+    // For payloads with translated content
+    pub fn for_conference_in(
+        conference: &ConferenceEdition,
+        language: &ContentLanguage,
+        versions: &[TableVersion],
+    ) -> Self {
+        let variant = format!("{}:{}", conference_variant(conference), language.locale);
+        Validators {
+            language: Some(language.clone()),
+            timestamps: true,
+            ..Self::new(&variant, versions)
+        }
+    }
+
+    // If-Modified-Since is only consulted when the client sent no If-None-Match (RFC 9110 13.1.3)
+    pub fn is_fresh(&self, req: &HttpRequest) -> bool {
+        if req.headers().contains_key(header::IF_NONE_MATCH) {
+            return match IfNoneMatch::parse(req) {
+                Ok(IfNoneMatch::Any) => true,
+                Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&self.etag)),
+                Err(_) => false,
+            };
+        }
+
+        match (IfModifiedSince::parse(req), self.http_last_modified()) {
+            (Ok(IfModifiedSince(since)), Some(last_modified)) => SystemTime::from(since) >= last_modified,
+            _ => false,
+        }
+    }
+
+    // HTTP dates have second precision
+    fn http_last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+            .map(|at| UNIX_EPOCH + Duration::from_secs(at.timestamp().max(0) as u64))
+    }
+
+    pub fn not_modified(&self, cache_control: &str) -> HttpResponse {
+        let mut response = HttpResponse::NotModified();
+        self.apply(&mut response, cache_control);
+        response.finish()
+    }
+
+    pub fn ok(&self, cache_control: &str) -> HttpResponseBuilder {
+        let mut response = HttpResponse::Ok();
+        self.apply(&mut response, cache_control);
+        response
+    }
+
+    fn apply(&self, response: &mut HttpResponseBuilder, cache_control: &str) {
+        response.insert_header(ETag(self.etag.clone()));
+        if let Some(last_modified) = self.http_last_modified() {
+            response.insert_header(LastModified(HttpDate::from(last_modified)));
+        }
+        if let Ok(value) = cache_control.try_into_value() {
+            response.insert_header((CacheControl::name(), value));
+        }
+        if let Some(language) = &self.language {
+            language.apply(response);
+        }
+        if self.timestamps {
+            response.append_header((header::VARY, TIMESTAMP_FORMAT_HEADER));
+        }
+    }
+}
+
+// For responses that must not end up in shared caches, such as admin previews
+pub fn uncached(language: &ContentLanguage) -> HttpResponseBuilder {
+    let mut response = HttpResponse::Ok();
+    response.insert_header(CacheControl(vec![CacheDirective::Private, CacheDirective::NoStore]));
+    language.apply(&mut response);
+    response
+}
+
+// Conference payloads also depend on the time zone and timestamp format they are rendered with
+fn conference_variant(conference: &ConferenceEdition) -> String {
+    let settings = time::current();
+    format!("{}:{}:{}", conference.id, settings.time_zone, settings.legacy_format)
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+// src/services/locale.rs
+//
+// Picks the locale content is served in. Clients ask through the "lang" query parameter or
+// Accept-Language, the answer is one of the locales the conference has content in so caches
+// only ever see a handful of variants.
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponseBuilder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::db::translations;
+use crate::error::ServiceError;
+use crate::models::edition::ConferenceEdition;
+use crate::models::translation::{language_of, normalize_locale};
+
+#[derive(Debug, Deserialize)]
+struct LanguageQuery {
+    lang: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentLanguage {
+    pub locale: String,
+    is_default: bool,
+}
+
+impl ContentLanguage {
+    pub async fn negotiate(
+        req: &HttpRequest,
+        pool: &PgPool,
+        conference: &ConferenceEdition,
+        default_locale: &str,
+    ) -> Result<Self, ServiceError> {
+        let default_locale = conference.default_locale(default_locale);
+        let requested = requested_locales(req);
+        if requested.is_empty() {
+            return Ok(Self::default_for(default_locale));
+        }
+
+        let mut available = translations::get_locales(pool, conference.id).await?;
+        available.push(default_locale.to_string());
+
+        let chosen = requested
+            .iter()
+            .find_map(|locale| best_match(locale, &available))
+            .unwrap_or(default_locale);
+        Ok(ContentLanguage {
+            locale: chosen.to_string(),
+            is_default: chosen == default_locale,
+        })
+    }
+
+    fn default_for(locale: &str) -> Self {
+        ContentLanguage { locale: locale.to_string(), is_default: true }
+    }
+
+    // Locale to look translations up in, None when the base content is what was asked for
+    pub fn translation(&self) -> Option<&str> {
+        (!self.is_default).then_some(self.locale.as_str())
+    }
+
+    pub fn apply(&self, response: &mut HttpResponseBuilder) {
+        response.insert_header((header::CONTENT_LANGUAGE, self.locale.clone()));
+        response.append_header((header::VARY, "Accept-Language"));
+    }
+}
+
+// "lang" first, then Accept-Language by descending quality. A wildcard ends the list since
+// anything, including the default locale, is acceptable from there on.
+fn requested_locales(req: &HttpRequest) -> Vec<String> {
+    let mut requested = Vec::new();
+
+    if let Ok(query) = web::Query::<LanguageQuery>::from_query(req.query_string())
+        && let Some(locale) = query.lang.as_deref().and_then(normalize_locale)
+    {
+        requested.push(locale);
+    }
+
+    let Some(accept) = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return requested;
+    };
+
+    let mut ranges: Vec<(f32, &str)> = accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (quality > 0.0).then_some((quality, tag))
+        })
+        .collect();
+    // Stable, so equal qualities keep the client's order
+    ranges.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (_, tag) in ranges {
+        if tag == "*" {
+            break;
+        }
+        if let Some(locale) = normalize_locale(tag) {
+            requested.push(locale);
+        }
+    }
+    requested
+}
+
+// Exact match first, then the bare language ("de" for "de-AT"), then any regional variant of it
+fn best_match<'a>(requested: &str, available: &'a [String]) -> Option<&'a str> {
+    let language = language_of(requested);
+    available
+        .iter()
+        .find(|locale| locale.as_str() == requested)
+        .or_else(|| available.iter().find(|locale| locale.as_str() == language))
+        .or_else(|| available.iter().find(|locale| language_of(locale) == language))
+        .map(String::as_str)
+}
\ No newline at end of file
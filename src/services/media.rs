@@ -0,0 +1,285 @@
+// src/services/media.rs
+use std::io::Cursor;
+use std::sync::Arc;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, Rgb, RgbImage};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::auth::Actor;
+use crate::config::MediaConfig;
+use crate::db::media;
+use crate::error::ServiceError;
+use crate::models::audit::AuditEntity;
+use crate::models::media::{MediaAsset, MediaFormat, MediaVariant, StoredAsset, StoredVariant};
+use crate::models::session::PhotoVariant;
+use crate::services::audit::PendingChange;
+use crate::services::storage::StorageBackend;
+
+// Longest side of the "original" variant, larger uploads are scaled down
+const ORIGINAL_MAX_SIZE: u32 = 1600;
+const SQUARE_SIZE: u32 = 400;
+const THUMBNAIL_SIZE: u32 = 120;
+const JPEG_QUALITY: u8 = 85;
+
+// Refuse to decode anything that would not fit a sensible photo
+const MAX_DIMENSION: u32 = 12_000;
+const MAX_DECODE_BYTES: u64 = 512 * 1024 * 1024;
+
+// Uploaded images are decoded, scaled to a fixed set of variants and re-encoded as JPEG and WebP.
+// The uploaded file itself is not kept, which also strips its metadata.
+pub struct MediaStore {
+    storage: Arc<dyn StorageBackend>,
+    public_base_url: String,
+}
+
+struct EncodedVariant {
+    variant: PhotoVariant,
+    format: MediaFormat,
+    width: u32,
+    height: u32,
+    bytes: Vec<u8>,
+}
+
+impl MediaStore {
+    pub fn new(storage: Arc<dyn StorageBackend>, config: &MediaConfig) -> Self {
+        MediaStore {
+            storage,
+            public_base_url: config.public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn upload(
+        &self,
+        pool: &PgPool,
+        bytes: Vec<u8>,
+        actor: &Actor,
+    ) -> Result<MediaAsset, ServiceError> {
+        // Ids are content hashes, re-uploading a file returns the existing asset
+        let id = Sha256::digest(&bytes)[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        if let Some(asset) = media::get_asset(pool, &id).await? {
+            return Ok(self.to_asset(asset));
+        }
+
+        let byte_size = bytes.len() as i64;
+        let (format, image) = tokio::task::spawn_blocking(move || decode(&bytes))
+            .await
+            .map_err(|_| ServiceError::InternalServerError)??;
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        let encoded = tokio::task::spawn_blocking(move || encode_variants(&image))
+            .await
+            .map_err(|_| ServiceError::InternalServerError)??;
+
+        let mut variants = Vec::with_capacity(encoded.len());
+        for encoded in encoded {
+            let storage_key = format!(
+                "{}/{}.{}",
+                id,
+                encoded.variant.name(),
+                encoded.format.extension()
+            );
+            variants.push(StoredVariant {
+                variant: encoded.variant,
+                format: encoded.format,
+                storage_key: storage_key.clone(),
+                width: encoded.width as i32,
+                height: encoded.height as i32,
+                byte_size: encoded.bytes.len() as i64,
+            });
+            self.storage
+                .put(&storage_key, encoded.format.content_type(), encoded.bytes)
+                .await?;
+        }
+
+        let asset = StoredAsset {
+            id,
+            content_type: format.to_mime_type().to_string(),
+            width,
+            height,
+            byte_size,
+            variants,
+        };
+        let mut tx = pool.begin().await?;
+        if media::add_asset(&mut tx, &asset).await? {
+            PendingChange::created_shared(AuditEntity::MediaAsset, &asset.id)
+                .record(&mut tx, actor)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(self.to_asset(asset))
+    }
+
+    pub async fn get_asset(
+        &self,
+        pool: &PgPool,
+        asset_id: &str,
+    ) -> Result<Option<MediaAsset>, ServiceError> {
+        Ok(media::get_asset(pool, asset_id)
+            .await?
+            .map(|asset| self.to_asset(asset)))
+    }
+
+    // Same as get_asset, but a missing asset is the caller's mistake
+    pub async fn require_asset(
+        &self,
+        pool: &PgPool,
+        asset_id: &str,
+    ) -> Result<MediaAsset, ServiceError> {
+        self.get_asset(pool, asset_id)
+            .await?
+            .ok_or_else(|| ServiceError::BadRequest(format!("Unknown media asset '{}'", asset_id)))
+    }
+
+    pub async fn load_variant(
+        &self,
+        pool: &PgPool,
+        asset_id: &str,
+        variant: PhotoVariant,
+        format: MediaFormat,
+    ) -> Result<Option<Vec<u8>>, ServiceError> {
+        match media::get_storage_key(pool, asset_id, variant, format).await? {
+            Some(storage_key) => self.storage.get(&storage_key).await,
+            None => Ok(None),
+        }
+    }
+
+    fn url(&self, asset_id: &str, variant: PhotoVariant, format: MediaFormat) -> String {
+        format!(
+            "{}/media/{}/{}.{}",
+            self.public_base_url,
+            asset_id,
+            variant.name(),
+            format.extension()
+        )
+    }
+
+    fn to_asset(&self, asset: StoredAsset) -> MediaAsset {
+        MediaAsset {
+            variants: asset
+                .variants
+                .into_iter()
+                .map(|variant| MediaVariant {
+                    url: self.url(&asset.id, variant.variant, variant.format),
+                    variant: variant.variant,
+                    format: variant.format,
+                    width: variant.width,
+                    height: variant.height,
+                    byte_size: variant.byte_size,
+                })
+                .collect(),
+            id: asset.id,
+            content_type: asset.content_type,
+            width: asset.width,
+            height: asset.height,
+        }
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<(ImageFormat, DynamicImage), ServiceError> {
+    let invalid = |e: image::ImageError| ServiceError::BadRequest(format!("Invalid image: {}", e));
+
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|_| ServiceError::InternalServerError)?;
+    let format = match reader.format() {
+        Some(format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)) => format,
+        _ => {
+            return Err(ServiceError::BadRequest(
+                "Only JPEG, PNG and WebP images are supported".to_string(),
+            ));
+        }
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_BYTES);
+    reader.limits(limits);
+
+    // Phone cameras store rotation as metadata, which is gone after re-encoding
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().map_err(invalid)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+
+    Ok((format, image))
+}
+
+fn encode_variants(image: &DynamicImage) -> Result<Vec<EncodedVariant>, ServiceError> {
+    let original = if image.width() > ORIGINAL_MAX_SIZE || image.height() > ORIGINAL_MAX_SIZE {
+        image.resize(ORIGINAL_MAX_SIZE, ORIGINAL_MAX_SIZE, FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+    let sized = [
+        (PhotoVariant::Original, original),
+        (
+            PhotoVariant::Square,
+            image.resize_to_fill(SQUARE_SIZE, SQUARE_SIZE, FilterType::Lanczos3),
+        ),
+        (
+            PhotoVariant::Thumbnail,
+            image.resize_to_fill(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Lanczos3),
+        ),
+    ];
+
+    let mut encoded = Vec::with_capacity(sized.len() * 2);
+    for (variant, image) in sized {
+        for format in [MediaFormat::Jpeg, MediaFormat::Webp] {
+            encoded.push(EncodedVariant {
+                variant,
+                format,
+                width: image.width(),
+                height: image.height(),
+                bytes: encode(&image, format)?,
+            });
+        }
+    }
+    Ok(encoded)
+}
+
+fn encode(image: &DynamicImage, format: MediaFormat) -> Result<Vec<u8>, ServiceError> {
+    let mut bytes = Vec::new();
+    let result = match format {
+        MediaFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY);
+            DynamicImage::ImageRgb8(flatten(image)).write_with_encoder(encoder)
+        }
+        // The bundled WebP encoder is lossless only, it keeps transparency
+        MediaFormat::Webp => {
+            let encoder = WebPEncoder::new_lossless(&mut bytes);
+            if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8()).write_with_encoder(encoder)
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)
+            }
+        }
+    };
+
+    result.map_err(|e| {
+        log::error!("Encoding {} failed: {:?}", format.name(), e);
+        ServiceError::InternalServerError
+    })?;
+    Ok(bytes)
+}
+
+// JPEG has no transparency, transparent areas become white instead of black
+fn flatten(image: &DynamicImage) -> RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
\ No newline at end of file
@@ -1,2 +1,15 @@
 // src/services/mod.rs
-pub mod admin;
+pub mod clock;
+pub mod reports;
+pub mod feedback_forms;
+pub mod windows;
+pub mod events;
+pub mod notifications;
+pub mod http_cache;
+pub mod read_cache;
+pub mod storage;
+pub mod media;
+pub mod locale;
+pub mod audit;
+pub mod changesets;
+pub mod retention;
\ No newline at end of file
@@ -0,0 +1,775 @@
+// src/services/notifications.rs
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+
+use crate::config::NotificationsConfig;
+use crate::db::{conferences, notifications, sessions};
+use crate::error::ServiceError;
+use crate::models::notification::{NotificationKind, OutboxMessage, Platform, SessionNotice};
+use crate::models::session::{SessionPlacement, SessionStatus};
+use crate::services::clock::Clock;
+
+const BATCH_SIZE: i64 = 100;
+const LEASE: Duration = Duration::from_secs(5 * 60);
+const RETRY_BASE: Duration = Duration::from_secs(30);
+const RETRY_MAX: Duration = Duration::from_secs(60 * 60);
+
+pub struct PushMessage {
+    pub title: String,
+    pub body: String,
+    pub data: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+pub enum PushError {
+    // The device is gone, its token gets removed
+    InvalidToken,
+    Retryable(String),
+    Permanent(String),
+}
+
+#[async_trait]
+pub trait PushProvider: Send + Sync {
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<(), PushError>;
+}
+
+// Logs instead of delivering. With simulated failures on, tokens starting with "invalid" or "fail"
+// stand for an unknown device and a temporary outage so the retry paths can be exercised locally.
+pub struct MockProvider {
+    name: &'static str,
+    simulate_failures: bool,
+}
+
+#[async_trait]
+impl PushProvider for MockProvider {
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<(), PushError> {
+        if self.simulate_failures && token.starts_with("invalid") {
+            return Err(PushError::InvalidToken);
+        }
+        if self.simulate_failures && token.starts_with("fail") {
+            return Err(PushError::Retryable("Simulated outage".to_string()));
+        }
+
+        // Device tokens are credentials for pushing to the device, they stay out of the log
+        log::info!(
+            "[{} mock] '{}': {} {:?}",
+            self.name,
+            message.title,
+            message.body,
+            message.data
+        );
+        Ok(())
+    }
+}
+
+// Short-lived bearer token, regenerated shortly before it expires
+struct CachedToken {
+    value: String,
+    expires: Instant,
+}
+
+impl CachedToken {
+    fn valid(&self) -> bool {
+        self.expires > Instant::now() + Duration::from_secs(60)
+    }
+}
+
+#[derive(Serialize)]
+struct GoogleAssertion<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+// Firebase Cloud Messaging HTTP v1, authenticated with a service account
+pub struct FcmProvider {
+    client: reqwest::Client,
+    project_id: String,
+    client_email: String,
+    key: EncodingKey,
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl FcmProvider {
+    pub fn new(project_id: &str, client_email: &str, private_key: &str) -> Result<Self, String> {
+        let key = EncodingKey::from_rsa_pem(private_key.replace("\\n", "\n").as_bytes())
+            .map_err(|e| format!("Invalid FCM private key: {}", e))?;
+
+        Ok(FcmProvider {
+            client: reqwest::Client::new(),
+            project_id: project_id.to_string(),
+            client_email: client_email.to_string(),
+            key,
+            token: Mutex::new(None),
+        })
+    }
+
+    async fn access_token(&self) -> Result<String, PushError> {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.valid()) {
+            return Ok(token.value.clone());
+        }
+
+        let now = Utc::now().timestamp();
+        let assertion = jsonwebtoken::encode(
+            &Header::new(Algorithm::RS256),
+            &GoogleAssertion {
+                iss: &self.client_email,
+                scope: "https://www.googleapis.com/auth/firebase.messaging",
+                aud: "https://oauth2.googleapis.com/token",
+                iat: now,
+                exp: now + 3600,
+            },
+            &self.key,
+        )
+        .map_err(|e| PushError::Permanent(format!("Failed to sign FCM assertion: {}", e)))?;
+
+        let response = self
+            .client
+            .post("https://oauth2.googleapis.com/token")
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| PushError::Retryable(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(PushError::Retryable(format!(
+                "FCM token exchange failed with {}",
+                response.status()
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| PushError::Retryable(e.to_string()))?;
+        let value = body["access_token"]
+            .as_str()
+            .ok_or_else(|| PushError::Retryable("FCM token response without token".to_string()))?
+            .to_string();
+        let expires_in = body["expires_in"].as_u64().unwrap_or(3600);
+
+        *cached = Some(CachedToken {
+            value: value.clone(),
+            expires: Instant::now() + Duration::from_secs(expires_in),
+        });
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl PushProvider for FcmProvider {
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<(), PushError> {
+        let access_token = self.access_token().await?;
+        let response = self
+            .client
+            .post(format!(
+                "https://fcm.googleapis.com/v1/projects/{}/messages:send",
+                self.project_id
+            ))
+            .bearer_auth(access_token)
+            .json(&json!({
+                "message": {
+                    "token": token,
+                    "notification": { "title": message.title, "body": message.body },
+                    "data": message.data,
+                }
+            }))
+            .send()
+            .await
+            .map_err(|e| PushError::Retryable(e.to_string()))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::NOT_FOUND => Err(PushError::InvalidToken),
+            StatusCode::UNAUTHORIZED => {
+                *self.token.lock().await = None;
+                Err(PushError::Retryable("FCM rejected the access token".to_string()))
+            }
+            status if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() => {
+                Err(PushError::Retryable(format!("FCM responded with {}", status)))
+            }
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                if body.contains("UNREGISTERED") || body.contains("registration token") {
+                    Err(PushError::InvalidToken)
+                } else {
+                    Err(PushError::Permanent(format!("FCM responded with {}: {}", status, body)))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApnsClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+}
+
+// Apple Push Notification service with token based authentication (.p8 key)
+pub struct ApnsProvider {
+    client: reqwest::Client,
+    host: &'static str,
+    team_id: String,
+    key_id: String,
+    topic: String,
+    key: EncodingKey,
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl ApnsProvider {
+    pub fn new(
+        team_id: &str,
+        key_id: &str,
+        private_key: &str,
+        topic: &str,
+        sandbox: bool,
+    ) -> Result<Self, String> {
+        let key = EncodingKey::from_ec_pem(private_key.replace("\\n", "\n").as_bytes())
+            .map_err(|e| format!("Invalid APNs private key: {}", e))?;
+        // APNs only speaks HTTP/2, offer nothing else during ALPN
+        let client = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .build()
+            .map_err(|e| format!("Failed to build the APNs client: {}", e))?;
+
+        Ok(ApnsProvider {
+            client,
+            host: if sandbox {
+                "https://api.sandbox.push.apple.com"
+            } else {
+                "https://api.push.apple.com"
+            },
+            team_id: team_id.to_string(),
+            key_id: key_id.to_string(),
+            topic: topic.to_string(),
+            key,
+            token: Mutex::new(None),
+        })
+    }
+
+    // Apple accepts provider tokens for an hour and rejects refreshing them more than every 20 minutes
+    async fn provider_token(&self) -> Result<String, PushError> {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.valid()) {
+            return Ok(token.value.clone());
+        }
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(self.key_id.clone());
+        let value = jsonwebtoken::encode(
+            &header,
+            &ApnsClaims {
+                iss: &self.team_id,
+                iat: Utc::now().timestamp(),
+            },
+            &self.key,
+        )
+        .map_err(|e| PushError::Permanent(format!("Failed to sign APNs token: {}", e)))?;
+
+        *cached = Some(CachedToken {
+            value: value.clone(),
+            expires: Instant::now() + Duration::from_secs(50 * 60),
+        });
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl PushProvider for ApnsProvider {
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<(), PushError> {
+        let provider_token = self.provider_token().await?;
+
+        let mut payload = json!({
+            "aps": {
+                "alert": { "title": message.title, "body": message.body },
+                "sound": "default",
+            }
+        });
+        for (key, value) in &message.data {
+            payload[key] = json!(value);
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/3/device/{}", self.host, token))
+            .bearer_auth(provider_token)
+            .header("apns-topic", &self.topic)
+            .header("apns-push-type", "alert")
+            .header("apns-priority", "10")
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| PushError::Retryable(e.to_string()))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::GONE => Err(PushError::InvalidToken),
+            status if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() => {
+                Err(PushError::Retryable(format!("APNs responded with {}", status)))
+            }
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                if body.contains("BadDeviceToken") || body.contains("Unregistered") {
+                    Err(PushError::InvalidToken)
+                } else {
+                    Err(PushError::Permanent(format!("APNs responded with {}: {}", status, body)))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Providers {
+    pub fcm: Option<Arc<dyn PushProvider>>,
+    pub apns: Option<Arc<dyn PushProvider>>,
+}
+
+impl Providers {
+    pub fn from_config(config: &NotificationsConfig) -> Result<Self, String> {
+        match config.provider.as_str() {
+            "mock" => Ok(Providers {
+                fcm: Some(Arc::new(MockProvider {
+                    name: "fcm",
+                    simulate_failures: config.mock_failures,
+                })),
+                apns: Some(Arc::new(MockProvider {
+                    name: "apns",
+                    simulate_failures: config.mock_failures,
+                })),
+            }),
+            "live" => {
+                let mut providers = Providers::default();
+                if let (Some(project_id), Some(client_email), Some(private_key)) = (
+                    &config.fcm_project_id,
+                    &config.fcm_client_email,
+                    &config.fcm_private_key,
+                ) {
+                    providers.fcm = Some(Arc::new(FcmProvider::new(
+                        project_id,
+                        client_email,
+                        private_key,
+                    )?));
+                }
+                if let (Some(team_id), Some(key_id), Some(private_key), Some(topic)) = (
+                    &config.apns_team_id,
+                    &config.apns_key_id,
+                    &config.apns_private_key,
+                    &config.apns_topic,
+                ) {
+                    providers.apns = Some(Arc::new(ApnsProvider::new(
+                        team_id,
+                        key_id,
+                        private_key,
+                        topic,
+                        config.apns_sandbox,
+                    )?));
+                }
+                Ok(providers)
+            }
+            other => Err(format!("Unknown notification provider '{}'", other)),
+        }
+    }
+
+    fn for_platform(&self, platform: Platform) -> Option<&Arc<dyn PushProvider>> {
+        match platform {
+            Platform::Fcm => self.fcm.as_ref(),
+            Platform::Apns => self.apns.as_ref(),
+        }
+    }
+}
+
+// Tells the voters of the sessions that moved compared with where they were before a change,
+// sessions without an earlier placement are known to have moved. Runs once the change is
+// committed, so failures are logged instead of failing the request.
+pub async fn announce_moved_sessions(
+    pool: &PgPool,
+    conference_id: i32,
+    session_ids: &[String],
+    before: &[SessionPlacement],
+) {
+    if session_ids.is_empty() {
+        return;
+    }
+    let result = match sessions::get_session_placements(pool, conference_id, session_ids).await {
+        Ok(after) => {
+            let moved: Vec<SessionPlacement> = after
+                .into_iter()
+                .filter(|session| {
+                    before
+                        .iter()
+                        .find(|earlier| earlier.id == session.id)
+                        .is_none_or(|earlier| session.moved_from(earlier))
+                })
+                .collect();
+            notify_sessions_moved(pool, conference_id, &moved).await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::error!("Failed to notify moved sessions {}: {}", session_ids.join(", "), e);
+    }
+}
+
+// Tells the voters of each published session where and when it now takes place. The session's
+// updated_at is part of the dedup key so every move is announced once, even back to an earlier room.
+async fn notify_sessions_moved(
+    pool: &PgPool,
+    conference_id: i32,
+    sessions: &[SessionPlacement],
+) -> Result<(), ServiceError> {
+    for session in sessions {
+        if session.status != SessionStatus::Published.name() {
+            continue;
+        }
+        let time = format!("{}-{}", session.starts_at.format("%H:%M"), session.ends_at.format("%H:%M"));
+        let body = match &session.room_name {
+            Some(room) => format!("Now taking place {} in {}", time, room),
+            None => format!("Now taking place {}, check the schedule for the room", time),
+        };
+
+        notifications::enqueue_session_notice(
+            pool,
+            conference_id,
+            &SessionNotice {
+                kind: NotificationKind::RoomChanged,
+                session_id: session.id.clone(),
+                title: session.title.clone(),
+                body,
+                revision: session.updated_at.timestamp_millis().to_string(),
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn notify_session_cancelled(
+    pool: &PgPool,
+    conference_id: i32,
+    session_id: &str,
+    session_title: &str,
+) -> Result<(), ServiceError> {
+    notifications::enqueue_session_notice(
+        pool,
+        conference_id,
+        &SessionNotice {
+            kind: NotificationKind::SessionCancelled,
+            session_id: session_id.to_string(),
+            title: session_title.to_string(),
+            body: "This session has been cancelled".to_string(),
+            // A session cancelled again after being reinstated notifies again
+            revision: Utc::now().timestamp_millis().to_string(),
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+pub struct NotificationWorker {
+    pool: PgPool,
+    clock: Clock,
+    providers: Providers,
+    config: NotificationsConfig,
+}
+
+impl NotificationWorker {
+    pub fn new(pool: PgPool, clock: Clock, providers: Providers, config: NotificationsConfig) -> Self {
+        NotificationWorker {
+            pool,
+            clock,
+            providers,
+            config,
+        }
+    }
+
+    pub fn start(self) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(self.config.poll_seconds.max(1)));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.enqueue_reminders().await {
+                    log::error!("Failed to enqueue session reminders: {}", e);
+                }
+                if let Err(e) = self.deliver_due().await {
+                    log::error!("Failed to deliver notifications: {}", e);
+                }
+            }
+        });
+    }
+
+    // The dedup key holds the start time, so a rescheduled session gets a fresh reminder
+    async fn enqueue_reminders(&self) -> Result<(), ServiceError> {
+        for conference in conferences::get_all_conferences(&self.pool).await? {
+            let now = self.clock.local_now_millis(&self.pool, &conference, None).await?;
+            let Some(from) = chrono::DateTime::from_timestamp_millis(now) else {
+                continue;
+            };
+            let to = from + chrono::Duration::minutes(self.config.reminder_minutes);
+
+            for session in sessions::get_sessions_starting_between(&self.pool, conference.id, from, to).await? {
+                let body = match &session.room_name {
+                    Some(room) => format!("Starts at {} in {}", session.starts_at.format("%H:%M"), room),
+                    None => format!("Starts at {}", session.starts_at.format("%H:%M")),
+                };
+                notifications::enqueue_session_notice(
+                    &self.pool,
+                    conference.id,
+                    &SessionNotice {
+                        kind: NotificationKind::SessionStarting,
+                        session_id: session.id,
+                        title: session.title,
+                        body,
+                        revision: session.starts_at.timestamp().to_string(),
+                    },
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver_due(&self) -> Result<(), ServiceError> {
+        let lease_until = Utc::now() + LEASE;
+        let messages = notifications::claim_due_messages(&self.pool, BATCH_SIZE, lease_until).await?;
+
+        for message in messages {
+            if let Err(e) = self.deliver(&message).await {
+                log::error!("Failed to deliver notification {}: {}", message.id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), ServiceError> {
+        // Users can opt out after the notice was queued
+        if notifications::get_notifications_enabled(&self.pool, &message.uuid).await? != Some(true) {
+            return notifications::mark_skipped(&self.pool, message.id, "Notifications disabled").await;
+        }
+
+        let devices = notifications::get_device_tokens(&self.pool, &message.uuid).await?;
+        if devices.is_empty() {
+            return notifications::mark_skipped(&self.pool, message.id, "No registered devices").await;
+        }
+
+        let mut data = HashMap::new();
+        data.insert("kind".to_string(), message.kind.name().to_string());
+        if let Some(session_id) = &message.session_id {
+            data.insert("sessionId".to_string(), session_id.clone());
+        }
+        let push = PushMessage {
+            title: message.title.clone(),
+            body: message.body.clone(),
+            data,
+        };
+
+        let mut delivered = false;
+        let mut retry_error = None;
+        let mut last_error = None;
+        for device in devices {
+            let Some(provider) = self.providers.for_platform(device.platform) else {
+                last_error = Some(format!("No provider configured for {}", device.platform.name()));
+                continue;
+            };
+
+            match provider.send(&device.token, &push).await {
+                Ok(()) => delivered = true,
+                Err(PushError::InvalidToken) => {
+                    log::info!("Removing invalid {} token", device.platform.name());
+                    notifications::delete_token(&self.pool, &device.token).await?;
+                }
+                Err(PushError::Retryable(e)) => retry_error = Some(e),
+                Err(PushError::Permanent(e)) => last_error = Some(e),
+            }
+        }
+
+        // Once any device got it the notice counts as sent, retrying would duplicate it there
+        if delivered {
+            return notifications::mark_sent(&self.pool, message.id).await;
+        }
+
+        match retry_error {
+            Some(error) if message.attempts < self.config.max_attempts => {
+                let next_attempt_at = Utc::now() + retry_delay(message.attempts);
+                notifications::schedule_retry(&self.pool, message.id, next_attempt_at, &error).await
+            }
+            Some(error) => notifications::mark_failed(&self.pool, message.id, &error).await,
+            None => match last_error {
+                Some(error) => notifications::mark_failed(&self.pool, message.id, &error).await,
+                None => notifications::mark_skipped(&self.pool, message.id, "No valid devices").await,
+            },
+        }
+    }
+}
+
+// Exponential backoff starting at the base delay, capped at the maximum
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE.saturating_mul(2u32.pow(exponent)).min(RETRY_MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::UTC;
+
+    use super::*;
+    use crate::db::{users, votes};
+    use crate::models::vote::Score;
+    use crate::services::clock::SystemTimeSource;
+
+    const CONFERENCE: i32 = 1;
+
+    fn worker(pool: &PgPool) -> NotificationWorker {
+        let config = NotificationsConfig {
+            enabled: true,
+            provider: "mock".to_string(),
+            mock_failures: true,
+            poll_seconds: 30,
+            reminder_minutes: 15,
+            max_attempts: 3,
+            fcm_project_id: None,
+            fcm_client_email: None,
+            fcm_private_key: None,
+            apns_team_id: None,
+            apns_key_id: None,
+            apns_private_key: None,
+            apns_topic: None,
+            apns_sandbox: false,
+        };
+        let providers = Providers::from_config(&config).unwrap();
+        NotificationWorker::new(pool.clone(), Clock::new(Arc::new(SystemTimeSource), UTC), providers, config)
+    }
+
+    // A user who voted on session-1 from a device with the given token
+    async fn add_voter(pool: &PgPool, user_id: &str, token: &str) {
+        users::create_user(pool, user_id, "2026-05-21T09:00:00Z").await.unwrap();
+        votes::change_vote(pool, CONFERENCE, user_id, "session-1", Some(Score::Good), Utc::now())
+            .await
+            .unwrap();
+        notifications::register_device(pool, user_id, token, Platform::Fcm).await.unwrap();
+    }
+
+    async fn cancel(pool: &PgPool, revision: &str) -> u64 {
+        let notice = SessionNotice {
+            kind: NotificationKind::SessionCancelled,
+            session_id: "session-1".to_string(),
+            title: "Keynote".to_string(),
+            body: "This session has been cancelled".to_string(),
+            revision: revision.to_string(),
+        };
+        notifications::enqueue_session_notice(pool, CONFERENCE, &notice).await.unwrap()
+    }
+
+    struct Delivery {
+        status: String,
+        attempts: i32,
+        last_error: Option<String>,
+        next_attempt_at: chrono::DateTime<Utc>,
+    }
+
+    async fn delivery(pool: &PgPool, user_id: &str) -> Delivery {
+        sqlx::query_as!(
+            Delivery,
+            "SELECT status, attempts, last_error, next_attempt_at FROM notification_outbox WHERE uuid = $1",
+            user_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn a_notice_is_sent_once(pool: PgPool) {
+        add_voter(&pool, "voter", "device-1").await;
+
+        assert_eq!(cancel(&pool, "1").await, 1);
+        assert_eq!(cancel(&pool, "1").await, 0);
+        worker(&pool).deliver_due().await.unwrap();
+        assert_eq!(cancel(&pool, "1").await, 0);
+
+        let sent = delivery(&pool, "voter").await;
+        assert_eq!(sent.status, "sent");
+        assert_eq!(sent.attempts, 1);
+    }
+
+    #[sqlx::test]
+    async fn users_who_opted_out_are_skipped(pool: PgPool) {
+        add_voter(&pool, "voter", "device-1").await;
+        cancel(&pool, "1").await;
+        notifications::set_notifications_enabled(&pool, "voter", false).await.unwrap();
+
+        worker(&pool).deliver_due().await.unwrap();
+
+        let skipped = delivery(&pool, "voter").await;
+        assert_eq!(skipped.status, "skipped");
+        assert_eq!(skipped.last_error.as_deref(), Some("Notifications disabled"));
+        // Opted out users are not queued in the first place
+        assert_eq!(cancel(&pool, "2").await, 0);
+    }
+
+    #[sqlx::test]
+    async fn outages_are_retried_with_backoff_until_attempts_run_out(pool: PgPool) {
+        add_voter(&pool, "voter", "fail-device").await;
+        cancel(&pool, "1").await;
+        let worker = worker(&pool);
+
+        let before = Utc::now();
+        worker.deliver_due().await.unwrap();
+        let retry = delivery(&pool, "voter").await;
+        assert_eq!(retry.status, "pending");
+        assert_eq!(retry.last_error.as_deref(), Some("Simulated outage"));
+        assert!(retry.next_attempt_at >= before + RETRY_BASE);
+
+        // Not due yet
+        worker.deliver_due().await.unwrap();
+        assert_eq!(delivery(&pool, "voter").await.attempts, 1);
+
+        for attempt in 2..=3 {
+            sqlx::query!("UPDATE notification_outbox SET next_attempt_at = NOW() WHERE uuid = 'voter'")
+                .execute(&pool)
+                .await
+                .unwrap();
+            worker.deliver_due().await.unwrap();
+            assert_eq!(delivery(&pool, "voter").await.attempts, attempt);
+        }
+        assert_eq!(delivery(&pool, "voter").await.status, "failed");
+    }
+
+    #[sqlx::test]
+    async fn invalid_tokens_are_removed(pool: PgPool) {
+        add_voter(&pool, "voter", "invalid-device").await;
+        cancel(&pool, "1").await;
+
+        worker(&pool).deliver_due().await.unwrap();
+
+        assert!(notifications::get_device_tokens(&pool, "voter").await.unwrap().is_empty());
+        let skipped = delivery(&pool, "voter").await;
+        assert_eq!(skipped.status, "skipped");
+        assert_eq!(skipped.last_error.as_deref(), Some("No valid devices"));
+    }
+
+    #[test]
+    fn retries_back_off_exponentially_up_to_the_maximum() {
+        assert_eq!(retry_delay(1), RETRY_BASE);
+        assert_eq!(retry_delay(2), RETRY_BASE * 2);
+        assert_eq!(retry_delay(4), RETRY_BASE * 8);
+        assert_eq!(retry_delay(20), RETRY_MAX);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,258 @@
+// src/services/read_cache.rs
+//
+// In-process cache for the composed read models served by /conference and /podcast/all.
+// Write paths invalidate entries as soon as their transaction has committed, other instances
+// drop theirs when the change reaches them through the event bus. Changes sent while the bus
+// was reconnecting never arrive, so reconnects flush everything and entries expire regardless.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::db::{conferences, podcast, sessions};
+use crate::error::ServiceError;
+use crate::models::cache::{CacheStats, ReadModelStats};
+use crate::models::conference::Conference;
+use crate::models::edition::ConferenceEdition;
+use crate::models::event::ChangeEntity;
+use crate::models::podcast::ChannelFullData;
+use crate::services::events::EventBus;
+
+// The generation is bumped on every invalidation so a load that raced with a write does
+// not put data read before the write back into the cache
+struct Slots<K, T> {
+    generation: u64,
+    // With the time each value was loaded
+    values: HashMap<K, (Instant, Arc<T>)>,
+}
+
+impl<K, T> Default for Slots<K, T> {
+    fn default() -> Self {
+        Slots { generation: 0, values: HashMap::new() }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl Counters {
+    fn stats(&self, entries: usize) -> ReadModelStats {
+        ReadModelStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries,
+        }
+    }
+}
+
+struct CachedModel<K, T> {
+    slots: RwLock<Slots<K, T>>,
+    // Per conference, models shared by all conferences count under None
+    counters: RwLock<HashMap<Option<i32>, Arc<Counters>>>,
+    conference_of: fn(&K) -> Option<i32>,
+    ttl: Duration,
+}
+
+impl<K: Eq + std::hash::Hash, T> CachedModel<K, T> {
+    fn new(conference_of: fn(&K) -> Option<i32>, ttl: Duration) -> Self {
+        CachedModel {
+            slots: RwLock::new(Slots::default()),
+            counters: RwLock::new(HashMap::new()),
+            conference_of,
+            ttl,
+        }
+    }
+
+    fn counters(&self, conference_id: Option<i32>) -> Arc<Counters> {
+        if let Some(counters) = self.counters.read().unwrap_or_else(|e| e.into_inner()).get(&conference_id) {
+            return Arc::clone(counters);
+        }
+        let mut counters = self.counters.write().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(counters.entry(conference_id).or_default())
+    }
+
+    async fn get_or_load<F, Fut>(&self, key: K, load: F) -> Result<Arc<T>, ServiceError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ServiceError>>,
+    {
+        let counters = self.counters((self.conference_of)(&key));
+        let generation = {
+            let slots = self.slots.read().unwrap_or_else(|e| e.into_inner());
+            if let Some((loaded_at, value)) = slots.values.get(&key)
+                && loaded_at.elapsed() < self.ttl
+            {
+                counters.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Arc::clone(value));
+            }
+            slots.generation
+        };
+        counters.misses.fetch_add(1, Ordering::Relaxed);
+
+        let loaded_at = Instant::now();
+        let value = Arc::new(load().await?);
+
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        if slots.generation == generation {
+            slots.values.insert(key, (loaded_at, Arc::clone(&value)));
+        }
+        Ok(value)
+    }
+
+    // Any invalidation keeps loads in flight from being stored, not only those for `matches`
+    fn invalidate(&self, matches: impl Fn(&K) -> bool) {
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        slots.generation += 1;
+        slots.values.retain(|key, _| {
+            if !matches(key) {
+                return true;
+            }
+            self.counters((self.conference_of)(key))
+                .invalidations
+                .fetch_add(1, Ordering::Relaxed);
+            false
+        });
+    }
+
+    fn invalidate_all(&self) {
+        self.invalidate(|_| true);
+    }
+
+    fn stats(&self, conference_id: Option<i32>) -> ReadModelStats {
+        let entries = {
+            let slots = self.slots.read().unwrap_or_else(|e| e.into_inner());
+            slots
+                .values
+                .keys()
+                .filter(|key| (self.conference_of)(key) == conference_id)
+                .count()
+        };
+        self.counters(conference_id).stats(entries)
+    }
+}
+
+// Conference changes do not go through the event bus, other instances see them once their
+// copy has expired
+const EDITIONS_TTL: Duration = Duration::from_secs(10);
+
+// Backstop for changes that never reached this instance through the event bus
+const READ_MODEL_TTL: Duration = Duration::from_secs(300);
+
+pub struct ReadModelCache {
+    // Per conference and translation locale, None being the content as stored
+    conferences: CachedModel<(i32, Option<String>), Conference>,
+    podcasts: CachedModel<(), Vec<ChannelFullData>>,
+    // Every conference, looked up by each request
+    editions: CachedModel<(), Vec<ConferenceEdition>>,
+}
+
+impl ReadModelCache {
+    pub fn new() -> Self {
+        ReadModelCache {
+            conferences: CachedModel::new(|(conference_id, _)| Some(*conference_id), READ_MODEL_TTL),
+            podcasts: CachedModel::new(|_| None, READ_MODEL_TTL),
+            editions: CachedModel::new(|_| None, EDITIONS_TTL),
+        }
+    }
+
+    pub async fn editions(&self, pool: &PgPool) -> Result<Arc<Vec<ConferenceEdition>>, ServiceError> {
+        self.editions
+            .get_or_load((), || conferences::get_all_conferences(pool))
+            .await
+    }
+
+    // A conference addressed by slug, or the default one
+    pub async fn edition(&self, pool: &PgPool, slug: Option<&str>) -> Result<Option<ConferenceEdition>, ServiceError> {
+        let editions = self.editions(pool).await?;
+        Ok(editions
+            .iter()
+            .find(|edition| match slug {
+                Some(slug) => edition.slug == slug,
+                None => edition.is_default,
+            })
+            .cloned())
+    }
+
+    pub fn invalidate_editions(&self) {
+        self.editions.invalidate_all();
+    }
+
+    // Only the attendee view is cached, admin previews are loaded directly
+    pub async fn conference_data(
+        &self,
+        pool: &PgPool,
+        conference_id: i32,
+        locale: Option<&str>,
+    ) -> Result<Arc<Conference>, ServiceError> {
+        self.conferences
+            .get_or_load((conference_id, locale.map(str::to_string)), || {
+                sessions::get_conference_data(pool, conference_id, locale, false)
+            })
+            .await
+    }
+
+    pub async fn podcast_data(&self, pool: &PgPool) -> Result<Arc<Vec<ChannelFullData>>, ServiceError> {
+        self.podcasts
+            .get_or_load((), || podcast::get_all_podcast_data(pool))
+            .await
+    }
+
+    pub fn invalidate_conference(&self, conference_id: i32) {
+        self.conferences.invalidate(|(id, _)| *id == conference_id);
+    }
+
+    pub fn invalidate_podcasts(&self) {
+        self.podcasts.invalidate_all();
+    }
+
+    pub fn stats(&self, conference_id: i32) -> CacheStats {
+        CacheStats {
+            conference: self.conferences.stats(Some(conference_id)),
+            podcasts: self.podcasts.stats(None),
+        }
+    }
+
+    // Drops entries changed by any instance. Local writes arrive here as well, invalidating
+    // a second time is harmless.
+    pub fn start_listener(self: &Arc<Self>, events: &EventBus) {
+        let cache = Arc::clone(self);
+        let mut receiver = events.subscribe();
+        let mut connections = events.subscribe_connections();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    received = receiver.recv() => match received {
+                        Ok(event) if event.entity == ChangeEntity::Podcasts => cache.invalidate_podcasts(),
+                        Ok(event) => cache.invalidate_conference(event.conference_id),
+                        // Changes were missed, nothing cached can be trusted
+                        Err(RecvError::Lagged(missed)) => {
+                            log::warn!("Read model cache missed {} changes, flushing", missed);
+                            cache.flush();
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                    // Changes made while the bus was reconnecting were not delivered
+                    connected = connections.changed() => match connected {
+                        Ok(()) => cache.flush(),
+                        Err(_) => break,
+                    },
+                }
+            }
+        });
+    }
+
+    fn flush(&self) {
+        self.conferences.invalidate_all();
+        self.podcasts.invalidate_all();
+    }
+}
\ No newline at end of file
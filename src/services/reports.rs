@@ -0,0 +1,65 @@
+// src/services/reports.rs
+use crate::models::report::{RatingGroup, SessionRating};
+
+// Quote a CSV field when it contains a separator, quote or line break. Text that a spreadsheet
+// would evaluate as a formula is prefixed with an apostrophe.
+fn escape_csv(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub fn session_ratings_to_csv(sessions: &[SessionRating]) -> String {
+    let mut csv = String::from(
+        "sessionId,title,startsAt,roomId,roomName,roomCapacity,good,ok,bad,total,netScore,participationRate\n",
+    );
+
+    for session in sessions {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{:.4}\n",
+            escape_csv(&session.session_id),
+            escape_csv(&session.title),
+            session.starts_at.format("%Y-%m-%dT%H:%M:%S"),
+            session.room_id.map(|id| id.to_string()).unwrap_or_default(),
+            escape_csv(session.room_name.as_deref().unwrap_or_default()),
+            session.room_capacity.map(|c| c.to_string()).unwrap_or_default(),
+            session.good,
+            session.ok,
+            session.bad,
+            session.total,
+            session.net_score,
+            session.participation_rate,
+        ));
+    }
+
+    csv
+}
+
+pub fn rating_groups_to_csv(groups: &[RatingGroup]) -> String {
+    let mut csv = String::from("rank,key,label,sessions,good,ok,bad,total,netScore,averageScore\n");
+
+    for (index, group) in groups.iter().enumerate() {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            index + 1,
+            escape_csv(&group.key),
+            escape_csv(&group.label),
+            group.sessions,
+            group.good,
+            group.ok,
+            group.bad,
+            group.total,
+            group.net_score,
+            group.average_score.map(|s| format!("{:.4}", s)).unwrap_or_default(),
+        ));
+    }
+
+    csv
+}
\ No newline at end of file
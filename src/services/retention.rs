@@ -0,0 +1,120 @@
+// src/services/retention.rs
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Months, Utc};
+use sqlx::{PgConnection, PgPool};
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+
+use crate::config::RetentionConfig;
+use crate::db::retention;
+use crate::error::ServiceError;
+use crate::models::retention::{RetentionReport, RetentionRun};
+
+// Pseudonymises votes and feedback of ended conferences, drops their notification history and
+// removes stale users and podcast requests, all in one transaction. A dry run rolls it back, so its report counts exactly what
+// a real run would have changed. Every run that got the lock is recorded, failed ones with their
+// error.
+pub async fn run(
+    pool: &PgPool,
+    config: &RetentionConfig,
+    started_by: &str,
+    dry_run: bool,
+) -> Result<RetentionRun, ServiceError> {
+    let started_at = Utc::now();
+    let mut tx = pool.begin().await?;
+    if !retention::try_lock(&mut tx).await? {
+        return Err(ServiceError::BadRequest("A retention run is already in progress".to_string()));
+    }
+
+    let outcome = apply(&mut tx, config, started_at).await;
+    let outcome = match outcome {
+        Ok(report) if dry_run => tx.rollback().await.map(|_| report).map_err(ServiceError::from),
+        Ok(report) => tx.commit().await.map(|_| report).map_err(ServiceError::from),
+        // The run is recorded either way, a failed rollback only ends the connection early
+        Err(e) => {
+            if let Err(rollback) = tx.rollback().await {
+                log::error!("Failed to roll back retention run by {}: {}", started_by, rollback);
+            }
+            Err(e)
+        }
+    };
+
+    match outcome {
+        Ok(report) => {
+            log::info!(
+                "Retention run by {} ({}): {} conferences pseudonymised, {} users purged, {} podcast requests deleted",
+                started_by,
+                if dry_run { "dry run" } else { "applied" },
+                report.conferences.len(),
+                report.purged_users,
+                report.deleted_podcast_requests
+            );
+            retention::add_run(pool, started_by, dry_run, started_at, Some(&report), None).await
+        }
+        Err(e) => {
+            log::error!("Retention run by {} failed: {}", started_by, e);
+            retention::add_run(pool, started_by, dry_run, started_at, None, Some(&e.to_string())).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn apply(
+    conn: &mut PgConnection,
+    config: &RetentionConfig,
+    now: DateTime<Utc>,
+) -> Result<RetentionReport, ServiceError> {
+    let anonymise_before = now
+        .checked_sub_months(Months::new(config.anonymise_after_months))
+        .ok_or(ServiceError::InternalServerError)?;
+    let stale_users_before = now - Duration::days(config.stale_user_days);
+    let podcast_requests_before = now - Duration::days(config.podcast_request_days);
+
+    let mut conferences = Vec::new();
+    for (conference_id, slug, conference_end) in retention::get_ended_conferences(conn, anonymise_before).await? {
+        let conference = retention::pseudonymise_conference(conn, conference_id, slug, conference_end).await?;
+        if conference.users > 0 || conference.notifications > 0 {
+            conferences.push(conference);
+        }
+    }
+
+    // After pseudonymisation, so users whose rows all moved to pseudonyms count as stale
+    let purged_users = retention::purge_stale_users(conn, stale_users_before).await?;
+    let deleted_podcast_requests = retention::delete_podcast_requests(conn, podcast_requests_before).await?;
+
+    Ok(RetentionReport {
+        anonymise_before,
+        stale_users_before,
+        podcast_requests_before,
+        conferences,
+        purged_users,
+        deleted_podcast_requests,
+    })
+}
+
+pub struct RetentionScheduler {
+    pool: PgPool,
+    config: RetentionConfig,
+}
+
+impl RetentionScheduler {
+    pub fn new(pool: PgPool, config: RetentionConfig) -> Self {
+        RetentionScheduler { pool, config }
+    }
+
+    pub async fn start(self) -> Result<(), JobSchedulerError> {
+        let scheduler = JobScheduler::new().await?;
+        let schedule = self.config.schedule.clone();
+        let worker = Arc::new(self);
+        scheduler
+            .add(Job::new_async(schedule.as_str(), move |_, _| {
+                let worker = worker.clone();
+                Box::pin(async move {
+                    // Failures are logged and recorded by the run itself
+                    let _ = run(&worker.pool, &worker.config, "scheduler", worker.config.dry_run).await;
+                })
+            })?)
+            .await?;
+        scheduler.start().await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+// src/services/windows.rs
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::{WindowAnchor, WindowConfig};
+use crate::error::ServiceError;
+use crate::models::session::SessionInfo;
+
+fn resolve_anchor(
+    anchor: WindowAnchor,
+    offset_minutes: i64,
+    session: &SessionInfo,
+    conference_end: Option<DateTime<Utc>>,
+) -> Option<i64> {
+    let base = match anchor {
+        WindowAnchor::None => return None,
+        WindowAnchor::Start => session.starts_at,
+        WindowAnchor::End => session.ends_at,
+        // Without any sessions there is no conference end, fall back to the session itself
+        WindowAnchor::ConferenceEnd => conference_end.unwrap_or(session.ends_at),
+    };
+
+    Some((base + Duration::minutes(offset_minutes)).timestamp_millis())
+}
+
+// Checks that `now` lies inside the window. Explicit per-session boundaries win over the
+// configured defaults. Service sessions (breaks, lunch, ...) cannot be rated at all.
+pub fn check_window(
+    config: &WindowConfig,
+    opens_at: Option<DateTime<Utc>>,
+    closes_at: Option<DateTime<Utc>>,
+    session: &SessionInfo,
+    conference_end: Option<DateTime<Utc>>,
+    now: i64,
+) -> Result<(), ServiceError> {
+    if session.is_service_session {
+        return Err(ServiceError::BadRequest(
+            "Service sessions do not accept votes or feedback".to_string(),
+        ));
+    }
+
+    let opens = opens_at.map(|t| t.timestamp_millis()).or_else(|| {
+        resolve_anchor(config.open_anchor, config.open_offset_minutes, session, conference_end)
+    });
+    let closes = closes_at.map(|t| t.timestamp_millis()).or_else(|| {
+        resolve_anchor(config.close_anchor, config.close_offset_minutes, session, conference_end)
+    });
+
+    if opens.is_some_and(|opens| now < opens) {
+        return Err(ServiceError::ComeBackLater);
+    }
+    if closes.is_some_and(|closes| now > closes) {
+        return Err(ServiceError::Closed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::models::session::SessionStatus;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 22, hour, minute, 0).unwrap()
+    }
+
+    fn session(is_service_session: bool) -> SessionInfo {
+        SessionInfo {
+            id: "s1".to_string(),
+            title: "Keynote".to_string(),
+            description: None,
+            starts_at: at(10, 0),
+            ends_at: at(11, 0),
+            room_id: None,
+            is_service_session,
+            is_plenum_session: false,
+            status: SessionStatus::Published,
+            speaker_ids: vec![],
+            category_ids: vec![],
+        }
+    }
+
+    fn config(open_anchor: WindowAnchor, close_anchor: WindowAnchor) -> WindowConfig {
+        WindowConfig {
+            open_anchor,
+            open_offset_minutes: -15,
+            close_anchor,
+            close_offset_minutes: 30,
+        }
+    }
+
+    #[test]
+    fn resolve_anchor_applies_the_offset_to_the_anchor() {
+        let session = session(false);
+        let conference_end = Some(at(18, 0));
+
+        assert_eq!(resolve_anchor(WindowAnchor::None, 10, &session, conference_end), None);
+        assert_eq!(
+            resolve_anchor(WindowAnchor::Start, -15, &session, conference_end),
+            Some(at(9, 45).timestamp_millis())
+        );
+        assert_eq!(
+            resolve_anchor(WindowAnchor::End, 30, &session, conference_end),
+            Some(at(11, 30).timestamp_millis())
+        );
+        assert_eq!(
+            resolve_anchor(WindowAnchor::ConferenceEnd, 0, &session, conference_end),
+            Some(at(18, 0).timestamp_millis())
+        );
+    }
+
+    #[test]
+    fn resolve_anchor_falls_back_to_the_session_end_without_a_conference_end() {
+        assert_eq!(
+            resolve_anchor(WindowAnchor::ConferenceEnd, 0, &session(false), None),
+            Some(at(11, 0).timestamp_millis())
+        );
+    }
+
+    #[test]
+    fn check_window_uses_the_configured_anchors() {
+        let config = config(WindowAnchor::Start, WindowAnchor::End);
+        let session = session(false);
+        let check = |now: DateTime<Utc>| check_window(&config, None, None, &session, None, now.timestamp_millis());
+
+        assert!(matches!(check(at(9, 44)), Err(ServiceError::ComeBackLater)));
+        assert!(check(at(9, 45)).is_ok());
+        assert!(check(at(11, 30)).is_ok());
+        assert!(matches!(check(at(11, 31)), Err(ServiceError::Closed)));
+    }
+
+    #[test]
+    fn check_window_prefers_explicit_boundaries() {
+        let config = config(WindowAnchor::Start, WindowAnchor::End);
+        let session = session(false);
+        let check = |now: DateTime<Utc>| {
+            check_window(&config, Some(at(12, 0)), Some(at(13, 0)), &session, None, now.timestamp_millis())
+        };
+
+        assert!(matches!(check(at(10, 30)), Err(ServiceError::ComeBackLater)));
+        assert!(check(at(12, 30)).is_ok());
+        assert!(matches!(check(at(13, 1)), Err(ServiceError::Closed)));
+    }
+
+    #[test]
+    fn check_window_without_anchors_is_always_open() {
+        let config = config(WindowAnchor::None, WindowAnchor::None);
+        let now = at(23, 59).timestamp_millis();
+
+        assert!(check_window(&config, None, None, &session(false), None, now).is_ok());
+    }
+
+    #[test]
+    fn check_window_rejects_service_sessions() {
+        let config = config(WindowAnchor::None, WindowAnchor::None);
+        let now = at(10, 30).timestamp_millis();
+
+        assert!(matches!(
+            check_window(&config, None, None, &session(true), None, now),
+            Err(ServiceError::BadRequest(_))
+        ));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+// src/tenancy.rs
+//
+// Every request addresses one conference. Routes can be prefixed with "/conferences/{slug}",
+// requests without the prefix go to the default conference so existing app builds keep working.
+// The prefix is stripped before routing and the conference is made available to handlers
+// through `web::ReqData<ConferenceEdition>`. Timestamps are rendered in the conference time
+// zone, with an offset for clients that send "X-Timestamp-Format: offset". Conferences are
+// looked up through the read model cache, health checks skip the lookup.
+use std::rc::Rc;
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::Uri,
+    web, Error, HttpMessage,
+};
+use chrono_tz::Tz;
+use futures::future::{ready, LocalBoxFuture, Ready};
+use sqlx::PgPool;
+
+use crate::error::ServiceError;
+use crate::models::time::{self, TimeSettings};
+use crate::services::read_cache::ReadModelCache;
+
+const CONFERENCE_PREFIX: &str = "/conferences/";
+const HEALTH_CHECK_PATH: &str = "/healthz";
+pub const TIMESTAMP_FORMAT_HEADER: &str = "X-Timestamp-Format";
+
+pub struct ConferenceScope {
+    // For conferences without a time zone of their own
+    time_zone: Tz,
+}
+
+impl ConferenceScope {
+    pub fn new(time_zone: Tz) -> Self {
+        ConferenceScope { time_zone }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ConferenceScope
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = ConferenceScopeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ConferenceScopeMiddleware {
+            service: Rc::new(service),
+            time_zone: self.time_zone,
+        }))
+    }
+}
+
+pub struct ConferenceScopeMiddleware<S> {
+    service: Rc<S>,
+    time_zone: Tz,
+}
+
+impl<S, B> Service<ServiceRequest> for ConferenceScopeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let time_zone = self.time_zone;
+
+        Box::pin(async move {
+            if req.path() == HEALTH_CHECK_PATH {
+                return service.call(req).await;
+            }
+
+            let slug = strip_conference_prefix(&mut req)?;
+            let pool = req
+                .app_data::<web::Data<PgPool>>()
+                .cloned()
+                .ok_or(ServiceError::InternalServerError)?;
+            let cache = req
+                .app_data::<web::Data<ReadModelCache>>()
+                .cloned()
+                .ok_or(ServiceError::InternalServerError)?;
+
+            let conference = cache
+                .edition(&pool, slug.as_deref())
+                .await?
+                .ok_or(ServiceError::NotFound)?;
+
+            let settings = TimeSettings {
+                time_zone: conference.time_zone_or(time_zone),
+                legacy_format: !requests_offsets(&req),
+            };
+            req.extensions_mut().insert(conference);
+
+            time::scope(settings, service.call(req)).await
+        })
+    }
+}
+
+// App builds that do not send the header keep the naive format they were written against
+fn requests_offsets(req: &ServiceRequest) -> bool {
+    req.headers()
+        .get(TIMESTAMP_FORMAT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("offset"))
+}
+
+// Rewrites "/conferences/{slug}/rest" to "/rest" and returns the slug
+fn strip_conference_prefix(req: &mut ServiceRequest) -> Result<Option<String>, ServiceError> {
+    let path = req.path();
+    let Some(scoped) = path.strip_prefix(CONFERENCE_PREFIX) else {
+        return Ok(None);
+    };
+
+    let (slug, rest) = match scoped.find('/') {
+        Some(index) => (&scoped[..index], &scoped[index..]),
+        None => (scoped, "/"),
+    };
+    if slug.is_empty() {
+        return Err(ServiceError::NotFound);
+    }
+    let slug = slug.to_string();
+
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{}?{}", rest, query),
+        None => rest.to_string(),
+    };
+    let uri: Uri = path_and_query
+        .parse()
+        .map_err(|_| ServiceError::BadRequest("Invalid path".to_string()))?;
+
+    req.match_info_mut().get_mut().update(&uri);
+    req.head_mut().uri = uri;
+
+    Ok(Some(slug))
+}
\ No newline at end of file